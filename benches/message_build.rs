@@ -0,0 +1,45 @@
+//! Building a 40-field message with `set_fields_batch` vs `set_field`
+//!
+//! Run with `cargo bench --bench message_build`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use iso8583_core::field::{Field, FieldValue};
+use iso8583_core::{ISO8583Message, MessageType};
+
+/// 40 fields spread over the primary and secondary bitmaps
+fn fields() -> Vec<(Field, FieldValue)> {
+    (2..=128u8)
+        .filter(|&num| num != 65)
+        .filter_map(|num| Field::from_number(num).ok())
+        .filter(|field| field.definition().field_type == iso8583_core::field::FieldType::Numeric)
+        .take(40)
+        .map(|field| (field, FieldValue::from_string("1")))
+        .collect()
+}
+
+fn bench_build(c: &mut Criterion) {
+    let fields = fields();
+    assert_eq!(fields.len(), 40);
+
+    let mut group = c.benchmark_group("build_40_fields");
+    group.bench_function("set_field", |b| {
+        b.iter(|| {
+            let mut msg = ISO8583Message::new(MessageType::FINANCIAL_REQUEST);
+            for (field, value) in fields.iter().cloned() {
+                msg.set_field(field, value).unwrap();
+            }
+            black_box(msg)
+        })
+    });
+    group.bench_function("set_fields_batch", |b| {
+        b.iter(|| {
+            let mut msg = ISO8583Message::new(MessageType::FINANCIAL_REQUEST);
+            msg.set_fields_batch(fields.iter().cloned()).unwrap();
+            black_box(msg)
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_build);
+criterion_main!(benches);
@@ -0,0 +1,239 @@
+//! Additional Amounts (field 54)
+//!
+//! Field 54 carries up to six balances or amounts, each as a 20-character
+//! block:
+//!
+//! ```text
+//! 10 02 840 C 000000012345
+//! |  |  |   | `- amount in minor units (12 digits)
+//! |  |  |   `--- C = credit, D = debit
+//! |  |  `------- currency code (3 digits)
+//! |  `---------- amount type (2 digits)
+//! `------------- account type (2 digits)
+//! ```
+
+use crate::error::{ISO8583Error, Result};
+use crate::processing_code::AccountType;
+use std::fmt;
+
+/// Length of one amount block
+pub const AMOUNT_BLOCK_LEN: usize = 20;
+
+/// Amount type (positions 3-4 of each block)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AmountType {
+    /// Account available balance (01)
+    AvailableBalance,
+    /// Account ledger balance (02)
+    LedgerBalance,
+    /// Amount owing (03)
+    AmountOwing,
+    /// Amount due (04)
+    AmountDue,
+    /// Amount remaining this cycle (20)
+    RemainingThisCycle,
+    /// Amount cash (40)
+    Cash,
+    /// Amount fees (56)
+    Fees,
+    /// Original amount requested, e.g. on a partial approval (57)
+    OriginalAmount,
+    /// Any other code, preserved as-is
+    Other(u8),
+}
+
+impl AmountType {
+    /// Create from the numeric code
+    pub fn from_code(code: u8) -> Self {
+        match code {
+            1 => Self::AvailableBalance,
+            2 => Self::LedgerBalance,
+            3 => Self::AmountOwing,
+            4 => Self::AmountDue,
+            20 => Self::RemainingThisCycle,
+            40 => Self::Cash,
+            56 => Self::Fees,
+            57 => Self::OriginalAmount,
+            other => Self::Other(other),
+        }
+    }
+
+    /// Get the numeric code
+    pub fn to_code(&self) -> u8 {
+        match self {
+            Self::AvailableBalance => 1,
+            Self::LedgerBalance => 2,
+            Self::AmountOwing => 3,
+            Self::AmountDue => 4,
+            Self::RemainingThisCycle => 20,
+            Self::Cash => 40,
+            Self::Fees => 56,
+            Self::OriginalAmount => 57,
+            Self::Other(code) => *code,
+        }
+    }
+
+    /// Check if the amount is an account balance (available or ledger)
+    pub fn is_balance(&self) -> bool {
+        matches!(self, Self::AvailableBalance | Self::LedgerBalance)
+    }
+
+    /// Check if the amount is the available (rather than ledger) balance
+    pub fn is_available(&self) -> bool {
+        matches!(self, Self::AvailableBalance)
+    }
+}
+
+impl fmt::Display for AmountType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}", self.to_code())
+    }
+}
+
+/// One 20-character block of field 54
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdditionalAmount {
+    /// Account the amount applies to
+    pub account_type: AccountType,
+    /// What the amount represents
+    pub amount_type: AmountType,
+    /// ISO 4217 numeric currency code
+    pub currency_code: String,
+    /// Amount in minor units; negative for debit (`D`) amounts
+    pub amount: i64,
+}
+
+impl AdditionalAmount {
+    /// Parse every block of a field 54 value
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::additional_amounts::{AdditionalAmount, AmountType};
+    ///
+    /// let amounts = AdditionalAmount::parse_all("1001840C0000000150001002840D000000002500")?;
+    /// assert_eq!(amounts[0].amount_type, AmountType::AvailableBalance);
+    /// assert_eq!(amounts[0].amount, 15000);
+    /// assert_eq!(amounts[1].amount, -2500);
+    /// # Ok::<(), iso8583_core::ISO8583Error>(())
+    /// ```
+    pub fn parse_all(data: &str) -> Result<Vec<Self>> {
+        if data.len() % AMOUNT_BLOCK_LEN != 0 || !data.is_ascii() {
+            return Err(ISO8583Error::invalid_field_value(
+                54,
+                format!(
+                    "Additional amounts must be {}-character blocks",
+                    AMOUNT_BLOCK_LEN
+                ),
+            ));
+        }
+
+        data.as_bytes()
+            .chunks(AMOUNT_BLOCK_LEN)
+            .map(|block| std::str::from_utf8(block).unwrap_or_default().parse())
+            .collect()
+    }
+
+    /// Encode amounts as a field 54 value
+    pub fn encode_all(amounts: &[Self]) -> String {
+        amounts.iter().map(|amount| amount.to_string()).collect()
+    }
+
+    /// Find the first amount of the given type
+    pub fn find(amounts: &[Self], amount_type: AmountType) -> Option<&Self> {
+        amounts.iter().find(|a| a.amount_type == amount_type)
+    }
+}
+
+impl std::str::FromStr for AdditionalAmount {
+    type Err = ISO8583Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = |reason: &str| ISO8583Error::invalid_field_value(54, reason);
+
+        if s.len() != AMOUNT_BLOCK_LEN || !s.is_ascii() {
+            return Err(invalid("Additional amount block must be 20 characters"));
+        }
+
+        let digits = |range: std::ops::Range<usize>| {
+            let part = &s[range];
+            if part.bytes().all(|b| b.is_ascii_digit()) {
+                Ok(part)
+            } else {
+                Err(invalid("Additional amount block has non-numeric data"))
+            }
+        };
+
+        let account_code: u8 = digits(0..2)?.parse().unwrap_or_default();
+        let amount_code: u8 = digits(2..4)?.parse().unwrap_or_default();
+        let currency_code = digits(4..7)?.to_string();
+        let magnitude: i64 = digits(8..20)?.parse().unwrap_or_default();
+        let amount = match &s[7..8] {
+            "C" => magnitude,
+            "D" => -magnitude,
+            _ => return Err(invalid("Additional amount sign must be 'C' or 'D'")),
+        };
+
+        Ok(Self {
+            account_type: AccountType::from_raw(account_code)
+                .ok_or_else(|| invalid("Additional amount account type must be 00-99"))?,
+            amount_type: AmountType::from_code(amount_code),
+            currency_code,
+            amount,
+        })
+    }
+}
+
+impl fmt::Display for AdditionalAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:02}{}{:>3}{}{:012}",
+            self.account_type.to_code(),
+            self.amount_type,
+            self.currency_code,
+            if self.amount < 0 { 'D' } else { 'C' },
+            self.amount.unsigned_abs()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_amount_types() {
+        assert!(AmountType::AvailableBalance.is_balance());
+        assert!(AmountType::AvailableBalance.is_available());
+        assert!(AmountType::LedgerBalance.is_balance());
+        assert!(!AmountType::LedgerBalance.is_available());
+        assert!(!AmountType::Cash.is_balance());
+        assert_eq!(AmountType::from_code(56), AmountType::Fees);
+        assert_eq!(AmountType::from_code(99), AmountType::Other(99));
+        assert_eq!(AmountType::Other(99).to_string(), "99");
+    }
+
+    #[test]
+    fn test_parse_and_encode() {
+        let data = "2001840C0000000150002002840C0000000200001040840D000000002500";
+        let amounts = AdditionalAmount::parse_all(data).unwrap();
+
+        assert_eq!(amounts.len(), 3);
+        assert_eq!(amounts[0].account_type, AccountType::Checking);
+        assert_eq!(amounts[0].currency_code, "840");
+        assert_eq!(amounts[1].amount_type, AmountType::LedgerBalance);
+        assert_eq!(amounts[2].amount, -2500);
+
+        let available = AdditionalAmount::find(&amounts, AmountType::AvailableBalance).unwrap();
+        assert_eq!(available.amount, 15000);
+        assert_eq!(AdditionalAmount::encode_all(&amounts), data);
+        assert!(AdditionalAmount::parse_all("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_malformed() {
+        assert!(AdditionalAmount::parse_all("2001840C00000001500").is_err()); // 19 chars
+        assert!(AdditionalAmount::parse_all("2001840X000000015000").is_err()); // Bad sign
+        assert!(AdditionalAmount::parse_all("2001USDC000000015000").is_err()); // Alpha currency
+    }
+}
@@ -33,6 +33,66 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+/// Extra bitmap block allocated by [`Bitmap::set_reporting`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitmapGrowth {
+    /// No new bitmap block was needed
+    None,
+    /// The secondary bitmap (fields 65-128) was added
+    Secondary,
+    /// The tertiary bitmap (fields 129-192) was added
+    Tertiary,
+}
+
+/// Error from the counted bitmap decoders ([`Bitmap::decode_counted`])
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitmapDecodeError {
+    /// Field 1 announces a secondary bitmap the input lacks
+    MissingSecondary,
+    /// Any other malformed bitmap
+    Invalid(&'static str),
+}
+
+impl core::fmt::Display for BitmapDecodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            BitmapDecodeError::MissingSecondary => {
+                f.write_str("Secondary bitmap indicated but not present")
+            }
+            BitmapDecodeError::Invalid(reason) => f.write_str(reason),
+        }
+    }
+}
+
+/// Wire representation of the bitmap region
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BitmapEncoding {
+    /// 8 raw bytes per bitmap block
+    #[default]
+    Binary,
+    /// 16 ASCII hex characters per bitmap block (uppercase when written)
+    AsciiHex,
+    /// 64 ASCII `'0'`/`'1'` characters per bitmap block, one per bit
+    ///
+    /// Used by some test harnesses and legacy simulators.
+    AsciiBits,
+}
+
+/// When to write the secondary bitmap block
+///
+/// Some hosts reject a secondary bitmap that carries no fields, others
+/// expect it whenever field 1 is set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SecondaryBitmapPolicy {
+    /// Write the blocks the bitmap tracks, as [`Bitmap::to_bytes`] does
+    #[default]
+    Auto,
+    /// Write the secondary block exactly when field 1 is set, even if empty
+    AlwaysWhenIndicated,
+    /// Drop an all-zero secondary block and clear field 1
+    NeverIfEmpty,
+}
+
 /// Bitmap for tracking present fields (supports up to 192 fields)
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Bitmap {
@@ -144,6 +204,57 @@ impl Bitmap {
         Ok(())
     }
 
+    /// Set a field, reporting whether a secondary or tertiary bitmap was added
+    ///
+    /// Behaves exactly like [`Bitmap::set`]. If one call adds both blocks,
+    /// [`BitmapGrowth::Tertiary`] is reported.
+    pub fn set_reporting(&mut self, field: u8) -> Result<BitmapGrowth, &'static str> {
+        let had_secondary = self.secondary.is_some();
+        let had_tertiary = self.tertiary.is_some();
+
+        self.set(field)?;
+
+        Ok(if !had_tertiary && self.tertiary.is_some() {
+            BitmapGrowth::Tertiary
+        } else if !had_secondary && self.secondary.is_some() {
+            BitmapGrowth::Secondary
+        } else {
+            BitmapGrowth::None
+        })
+    }
+
+    /// Set every data field in `start..=end`
+    ///
+    /// Fields 1 and 65 inside the range are bitmap indicators, not data, so
+    /// they are only enabled when a field beyond them needs the secondary or
+    /// tertiary bitmap, exactly as with [`Bitmap::set`]. The range is checked
+    /// before anything is set, so an invalid range leaves the bitmap
+    /// unchanged.
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::Bitmap;
+    ///
+    /// let mut bitmap = Bitmap::new();
+    /// bitmap.set_range(2, 20).unwrap();
+    /// assert!(bitmap.is_set(2) && bitmap.is_set(20));
+    /// assert!(!bitmap.is_set(1) && !bitmap.is_set(21));
+    /// ```
+    pub fn set_range(&mut self, start: u8, end: u8) -> Result<(), &'static str> {
+        if start == 0 || end > 192 {
+            return Err("Field number out of range (1-192)");
+        }
+        if start > end {
+            return Err("Field range start is after its end");
+        }
+
+        for field in (start..=end).filter(|&field| field != 1 && field != 65) {
+            self.set(field)?;
+        }
+
+        Ok(())
+    }
+
     /// Clear a field in the bitmap
     #[inline]
     pub fn clear(&mut self, field: u8) -> Result<(), &'static str> {
@@ -179,6 +290,104 @@ impl Bitmap {
             && !self.tertiary.as_ref().is_some_and(|t| self.has_any_set(t))
     }
 
+    /// Check whether two bitmaps carry the same data fields
+    ///
+    /// Indicator bits (field 1 for the secondary bitmap, field 65 for the
+    /// tertiary bitmap) are ignored, so a bitmap with an empty secondary
+    /// bitmap compares equal to one without a secondary bitmap at all.
+    pub fn same_data_fields(&self, other: &Self) -> bool {
+        (2..=192u8)
+            .filter(|&field| field != 65)
+            .all(|field| self.is_set(field) == other.is_set(field))
+    }
+
+    /// Data fields set in `self` but not in `other`
+    ///
+    /// Indicator bits are not compared; the result sets them only as needed
+    /// for the fields it carries.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut diff = Self::new();
+        for field in (2..=192u8).filter(|&field| field != 65) {
+            if self.is_set(field) && !other.is_set(field) {
+                // Field numbers are in range, so set cannot fail
+                let _ = diff.set(field);
+            }
+        }
+        diff
+    }
+
+    /// Copy of this bitmap with `policy` applied to the secondary block
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::{Bitmap, SecondaryBitmapPolicy};
+    ///
+    /// let mut bitmap = Bitmap::new();
+    /// bitmap.set(1).unwrap(); // Indicator set, but no fields 65-128
+    /// bitmap.set(3).unwrap();
+    ///
+    /// assert_eq!(bitmap.to_bytes().1, 16);
+    /// let trimmed = bitmap.with_secondary_policy(SecondaryBitmapPolicy::NeverIfEmpty);
+    /// assert_eq!(trimmed.to_bytes().1, 8);
+    /// assert!(!trimmed.is_set(1) && trimmed.is_set(3));
+    /// ```
+    pub fn with_secondary_policy(&self, policy: SecondaryBitmapPolicy) -> Self {
+        let mut bitmap = self.clone();
+        match policy {
+            SecondaryBitmapPolicy::Auto => {}
+            SecondaryBitmapPolicy::AlwaysWhenIndicated => {
+                if Self::is_set_in_bitmap(&self.primary, 1) {
+                    bitmap.secondary.get_or_insert([0u8; 8]);
+                } else {
+                    bitmap.secondary = None;
+                    bitmap.tertiary = None;
+                }
+            }
+            SecondaryBitmapPolicy::NeverIfEmpty => {
+                if self.secondary == Some([0u8; 8]) {
+                    bitmap.secondary = None;
+                    bitmap.tertiary = None;
+                    Self::clear_in_bitmap(&mut bitmap.primary, 1);
+                }
+            }
+        }
+        bitmap
+    }
+
+    /// Highest data field set, or `None` if no data field is set
+    ///
+    /// Indicator bits (fields 1 and 65) are ignored, so a result of 64 or
+    /// less means the secondary bitmap carries nothing. Each block is
+    /// checked with a single bit scan, tertiary first.
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::Bitmap;
+    ///
+    /// let mut bitmap = Bitmap::new();
+    /// assert_eq!(bitmap.highest_set_field(), None);
+    /// bitmap.set(2).unwrap();
+    /// bitmap.set(128).unwrap();
+    /// assert_eq!(bitmap.highest_set_field(), Some(128));
+    /// ```
+    pub fn highest_set_field(&self) -> Option<u8> {
+        // Field 1 of each block is its most significant bit
+        const INDICATOR: u64 = 1 << 63;
+        let blocks = [
+            (128u8, self.tertiary.map(u64::from_be_bytes)),
+            (
+                64,
+                self.secondary.map(|b| u64::from_be_bytes(b) & !INDICATOR),
+            ),
+            (0, Some(u64::from_be_bytes(self.primary) & !INDICATOR)),
+        ];
+
+        blocks.into_iter().find_map(|(base, bits)| {
+            let bits = bits.filter(|&bits| bits != 0)?;
+            Some(base + 64 - bits.trailing_zeros() as u8)
+        })
+    }
+
     /// Get all set field numbers (returns array and count)
     /// Returns (fields_array, count) where count indicates how many fields are actually set
     pub fn get_set_fields(&self) -> ([u8; 192], usize) {
@@ -273,12 +482,188 @@ impl Bitmap {
         Ok(bitmap)
     }
 
+    /// Parse from bytes, returning the bitmap and how many bytes it occupied
+    ///
+    /// The count is 8, 16 or 24 depending on whether the secondary (field 1)
+    /// and tertiary (field 65) indicators are set. Unlike [`Bitmap::from_bytes`],
+    /// an indicated bitmap that is missing from the input is an error.
+    pub fn from_bytes_counted(bytes: &[u8]) -> Result<(Self, usize), BitmapDecodeError> {
+        if bytes.len() < 8 {
+            return Err(BitmapDecodeError::Invalid(
+                "Bitmap must be at least 8 bytes",
+            ));
+        }
+
+        let mut primary = [0u8; 8];
+        primary.copy_from_slice(&bytes[0..8]);
+
+        let mut bitmap = Self {
+            primary,
+            secondary: None,
+            tertiary: None,
+        };
+        let mut consumed = 8;
+
+        if Self::is_set_in_bitmap(&bitmap.primary, 1) {
+            if bytes.len() < 16 {
+                return Err(BitmapDecodeError::MissingSecondary);
+            }
+            let mut secondary = [0u8; 8];
+            secondary.copy_from_slice(&bytes[8..16]);
+            bitmap.secondary = Some(secondary);
+            consumed = 16;
+
+            if Self::is_set_in_bitmap(&secondary, 1) {
+                if bytes.len() < 24 {
+                    return Err(BitmapDecodeError::Invalid(
+                        "Tertiary bitmap indicated but not present",
+                    ));
+                }
+                let mut tertiary = [0u8; 8];
+                tertiary.copy_from_slice(&bytes[16..24]);
+                bitmap.tertiary = Some(tertiary);
+                consumed = 24;
+            }
+        }
+
+        Ok((bitmap, consumed))
+    }
+
+    /// Parse from ASCII hex, returning the bitmap and how many bytes it occupied
+    ///
+    /// Each 8-byte block is sent as 16 hex characters, so the count is 16,
+    /// 32 or 48. Secondary and tertiary blocks are read only when their
+    /// indicator bit is set, as in [`Bitmap::from_bytes_counted`].
+    pub fn from_ascii_hex_counted(bytes: &[u8]) -> Result<(Self, usize), BitmapDecodeError> {
+        let mut raw = [0u8; 24];
+        let mut blocks = 0;
+
+        loop {
+            let chunk = bytes
+                .get(blocks * 16..(blocks + 1) * 16)
+                .ok_or(match blocks {
+                    0 => BitmapDecodeError::Invalid(
+                        "ASCII hex bitmap must be at least 16 characters",
+                    ),
+                    1 => BitmapDecodeError::MissingSecondary,
+                    _ => BitmapDecodeError::Invalid("Tertiary bitmap indicated but not present"),
+                })?;
+            hex::decode_to_slice(chunk, &mut raw[blocks * 8..(blocks + 1) * 8])
+                .map_err(|_| BitmapDecodeError::Invalid("Invalid ASCII hex bitmap"))?;
+            blocks += 1;
+
+            // Bit 1 of each block announces the next one
+            if blocks == 3 || raw[(blocks - 1) * 8] & 0x80 == 0 {
+                break;
+            }
+        }
+
+        let (bitmap, _) = Self::from_bytes_counted(&raw[..blocks * 8])?;
+        Ok((bitmap, blocks * 16))
+    }
+
+    /// Parse from ASCII `'0'`/`'1'` characters, returning the bitmap and how
+    /// many bytes it occupied
+    ///
+    /// Each 8-byte block is sent as 64 characters, field 1 first, so the
+    /// count is 64, 128 or 192. Secondary and tertiary blocks are read only
+    /// when their indicator bit is set, as in [`Bitmap::from_bytes_counted`].
+    pub fn from_ascii_bits_counted(bytes: &[u8]) -> Result<(Self, usize), BitmapDecodeError> {
+        let mut raw = [0u8; 24];
+        let mut blocks = 0;
+
+        loop {
+            let chunk = bytes
+                .get(blocks * 64..(blocks + 1) * 64)
+                .ok_or(match blocks {
+                    0 => BitmapDecodeError::Invalid(
+                        "ASCII bits bitmap must be at least 64 characters",
+                    ),
+                    1 => BitmapDecodeError::MissingSecondary,
+                    _ => BitmapDecodeError::Invalid("Tertiary bitmap indicated but not present"),
+                })?;
+            for (i, &c) in chunk.iter().enumerate() {
+                match c {
+                    b'0' => {}
+                    b'1' => raw[blocks * 8 + i / 8] |= 0x80 >> (i % 8),
+                    _ => {
+                        return Err(BitmapDecodeError::Invalid(
+                            "ASCII bits bitmap must contain only '0' and '1'",
+                        ))
+                    }
+                }
+            }
+            blocks += 1;
+
+            // Bit 1 of each block announces the next one
+            if blocks == 3 || raw[(blocks - 1) * 8] & 0x80 == 0 {
+                break;
+            }
+        }
+
+        let (bitmap, _) = Self::from_bytes_counted(&raw[..blocks * 8])?;
+        Ok((bitmap, blocks * 64))
+    }
+
+    /// Parse in the given encoding, returning the bitmap and how many bytes it occupied
+    pub fn decode_counted(
+        bytes: &[u8],
+        encoding: BitmapEncoding,
+    ) -> Result<(Self, usize), BitmapDecodeError> {
+        match encoding {
+            BitmapEncoding::Binary => Self::from_bytes_counted(bytes),
+            BitmapEncoding::AsciiHex => Self::from_ascii_hex_counted(bytes),
+            BitmapEncoding::AsciiBits => Self::from_ascii_bits_counted(bytes),
+        }
+    }
+
+    /// Encode all present bitmaps in the given encoding
+    pub fn encode(&self, encoding: BitmapEncoding) -> alloc::vec::Vec<u8> {
+        match encoding {
+            BitmapEncoding::Binary => {
+                let (bytes, len) = self.to_bytes();
+                bytes[..len].to_vec()
+            }
+            BitmapEncoding::AsciiHex => self.to_hex_upper().into_bytes(),
+            BitmapEncoding::AsciiBits => {
+                let (bytes, len) = self.to_bytes();
+                bytes[..len]
+                    .iter()
+                    .flat_map(|byte| (0..8).map(move |bit| b'0' + ((byte >> (7 - bit)) & 1)))
+                    .collect()
+            }
+        }
+    }
+
     /// Parse from hex string
+    ///
+    /// Upper- and lowercase digits are accepted, so `"FF..."` and `"ff..."`
+    /// parse to the same bitmap.
     pub fn from_hex(hex_str: &str) -> Result<Self, &'static str> {
         let bytes = hex::decode(hex_str).map_err(|_| "Invalid hex string")?;
         Self::from_bytes(&bytes)
     }
 
+    /// Encode all present bitmaps as a lowercase hex string
+    pub fn to_hex(&self) -> alloc::string::String {
+        let (bytes, len) = self.to_bytes();
+        hex::encode(&bytes[..len])
+    }
+
+    /// Encode all present bitmaps as an uppercase hex string
+    ///
+    /// Useful when comparing against reference logs that print bitmaps in
+    /// uppercase.
+    pub fn to_hex_upper(&self) -> alloc::string::String {
+        let (bytes, len) = self.to_bytes();
+        hex::encode_upper(&bytes[..len])
+    }
+
+    /// Encode the primary bitmap alone as a lowercase hex string
+    pub fn primary_hex(&self) -> alloc::string::String {
+        hex::encode(self.primary)
+    }
+
     // ===== Internal Helper Methods =====
 
     /// Check if specific field is set in 8-byte bitmap
@@ -390,6 +775,196 @@ mod tests {
         assert!(bitmap.is_empty());
     }
 
+    #[test]
+    fn test_difference() {
+        let mut a = Bitmap::new();
+        let mut b = Bitmap::new();
+        for field in [2, 3, 4, 70] {
+            a.set(field).unwrap();
+        }
+        for field in [3, 4, 11] {
+            b.set(field).unwrap();
+        }
+
+        let (fields, count) = a.difference(&b).get_set_fields();
+        assert_eq!(fields[..count], [1, 2, 70]);
+        let (fields, count) = b.difference(&a).get_set_fields();
+        assert_eq!(fields[..count], [11]);
+        assert!(a.difference(&a).is_empty());
+    }
+
+    #[test]
+    fn test_set_reporting() {
+        let mut bitmap = Bitmap::new();
+        assert_eq!(bitmap.set_reporting(2), Ok(BitmapGrowth::None));
+        assert_eq!(bitmap.set_reporting(70), Ok(BitmapGrowth::Secondary));
+        assert_eq!(bitmap.set_reporting(100), Ok(BitmapGrowth::None));
+        assert_eq!(bitmap.set_reporting(130), Ok(BitmapGrowth::Tertiary));
+        assert!(bitmap.set_reporting(0).is_err());
+
+        let mut direct = Bitmap::new();
+        assert_eq!(direct.set_reporting(150), Ok(BitmapGrowth::Tertiary));
+        assert!(direct.is_set(1) && direct.is_set(65) && direct.is_set(150));
+    }
+
+    #[test]
+    fn test_highest_set_field() {
+        let mut bitmap = Bitmap::new();
+        assert_eq!(bitmap.highest_set_field(), None);
+
+        bitmap.set(2).unwrap();
+        assert_eq!(bitmap.highest_set_field(), Some(2));
+        bitmap.set(128).unwrap();
+        assert_eq!(bitmap.highest_set_field(), Some(128));
+        bitmap.set(192).unwrap();
+        assert_eq!(bitmap.highest_set_field(), Some(192));
+
+        // Indicators alone do not count
+        let mut indicators = Bitmap::new();
+        indicators.set(1).unwrap();
+        indicators.set(64).unwrap();
+        assert_eq!(indicators.highest_set_field(), Some(64));
+        indicators.clear(64).unwrap();
+        assert_eq!(indicators.highest_set_field(), None);
+
+        let mut full = Bitmap::new();
+        full.set_range(2, 192).unwrap();
+        assert_eq!(full.highest_set_field(), Some(192));
+        full.clear(192).unwrap();
+        assert_eq!(full.highest_set_field(), Some(191));
+    }
+
+    #[test]
+    fn test_set_range() {
+        let mut bitmap = Bitmap::new();
+        bitmap.set_range(2, 70).unwrap();
+
+        assert!(bitmap.is_set(1)); // Secondary indicator
+        assert!((2..=64).chain(66..=70).all(|field| bitmap.is_set(field)));
+        assert!(!bitmap.is_set(65)); // No tertiary bitmap needed
+        assert!(!bitmap.is_set(71));
+        assert_eq!(bitmap.to_bytes().1, 16);
+
+        let mut single = Bitmap::new();
+        single.set_range(3, 3).unwrap();
+        assert!(single.is_set(3) && !single.is_set(1));
+
+        let before = bitmap.clone();
+        assert!(bitmap.set_range(0, 10).is_err());
+        assert!(bitmap.set_range(190, 193).is_err());
+        assert!(bitmap.set_range(20, 10).is_err());
+        assert_eq!(bitmap, before);
+    }
+
+    #[test]
+    fn test_ascii_hex_counted() {
+        let mut bitmap = Bitmap::new();
+        bitmap.set(3).unwrap();
+        bitmap.set(70).unwrap();
+
+        let ascii = bitmap.encode(BitmapEncoding::AsciiHex);
+        assert_eq!(ascii, b"A0000000000000000400000000000000");
+        assert_eq!(
+            Bitmap::decode_counted(&ascii, BitmapEncoding::AsciiHex),
+            Ok((bitmap.clone(), 32))
+        );
+
+        let primary_only = b"2000000000000000FIELDS";
+        let (parsed, consumed) = Bitmap::from_ascii_hex_counted(primary_only).unwrap();
+        assert_eq!(consumed, 16);
+        assert!(parsed.is_set(3));
+
+        assert_eq!(
+            Bitmap::from_ascii_hex_counted(b"A000000000000000"),
+            Err(BitmapDecodeError::MissingSecondary)
+        );
+        assert!(Bitmap::from_ascii_hex_counted(b"G000000000000000").is_err());
+        assert!(Bitmap::from_ascii_hex_counted(b"2000").is_err());
+    }
+
+    #[test]
+    fn test_ascii_bits_counted() {
+        let mut bitmap = Bitmap::new();
+        bitmap.set(3).unwrap();
+        bitmap.set(70).unwrap();
+
+        let bits = bitmap.encode(BitmapEncoding::AsciiBits);
+        assert_eq!(bits.len(), 128);
+        assert_eq!(&bits[..8], b"10100000");
+        assert_eq!(&bits[64..72], b"00000100");
+        assert_eq!(
+            Bitmap::decode_counted(&bits, BitmapEncoding::AsciiBits),
+            Ok((bitmap.clone(), 128))
+        );
+
+        let mut primary_only = b"0010".to_vec();
+        primary_only.resize(64, b'0');
+        primary_only.extend_from_slice(b"FIELDS");
+        let (parsed, consumed) = Bitmap::from_ascii_bits_counted(&primary_only).unwrap();
+        assert_eq!(consumed, 64);
+        assert!(parsed.is_set(3));
+
+        assert!(Bitmap::from_ascii_bits_counted(&bits[..64]).is_err()); // Missing secondary
+        assert!(Bitmap::from_ascii_bits_counted(&[b'2'; 64]).is_err());
+        assert!(Bitmap::from_ascii_bits_counted(b"0010").is_err());
+    }
+
+    #[test]
+    fn test_hex_case() {
+        let upper = Bitmap::from_hex("F23C449108E18000").unwrap();
+        let lower = Bitmap::from_hex("f23c449108e18000").unwrap();
+        let mixed = Bitmap::from_hex("F23c449108E18000").unwrap();
+        assert_eq!(upper, lower);
+        assert_eq!(upper, mixed);
+
+        assert_eq!(upper.to_hex(), "f23c449108e18000");
+        assert_eq!(upper.to_hex_upper(), "F23C449108E18000");
+        assert_eq!(upper.primary_hex(), "f23c449108e18000");
+
+        let mut secondary = Bitmap::new();
+        secondary.set(2).unwrap();
+        secondary.set(70).unwrap();
+        assert_eq!(secondary.to_hex(), "c0000000000000000400000000000000");
+        assert_eq!(secondary.primary_hex(), "c000000000000000");
+        assert_eq!(
+            Bitmap::from_hex(&secondary.to_hex_upper()).unwrap(),
+            secondary
+        );
+    }
+
+    #[test]
+    fn test_same_data_fields() {
+        let mut plain = Bitmap::new();
+        plain.set(2).unwrap();
+        plain.set(4).unwrap();
+
+        // Secondary indicator set, but no fields 65-128 present
+        let mut indicated = plain.clone();
+        indicated.set(1).unwrap();
+        assert_ne!(plain, indicated);
+        assert!(plain.same_data_fields(&indicated));
+        assert!(indicated.same_data_fields(&plain));
+
+        // Secondary field added then removed leaves only the indicator
+        let mut cleared = plain.clone();
+        cleared.set(70).unwrap();
+        cleared.clear(70).unwrap();
+        assert!(plain.same_data_fields(&cleared));
+
+        // Tertiary indicator only
+        let mut tertiary = plain.clone();
+        tertiary.set(65).unwrap();
+        assert!(plain.same_data_fields(&tertiary));
+
+        // Genuine data differences are still detected
+        let mut extra = plain.clone();
+        extra.set(70).unwrap();
+        assert!(!plain.same_data_fields(&extra));
+        let mut missing = plain.clone();
+        missing.clear(4).unwrap();
+        assert!(!plain.same_data_fields(&missing));
+    }
+
     #[test]
     fn test_set_and_check() {
         let mut bitmap = Bitmap::new();
@@ -428,6 +1003,63 @@ mod tests {
         assert_eq!(bitmap, restored);
     }
 
+    #[test]
+    fn test_from_bytes_counted() {
+        let mut bitmap = Bitmap::new();
+        bitmap.set(2).unwrap();
+        let (bytes, len) = bitmap.to_bytes();
+        assert_eq!(Bitmap::from_bytes_counted(&bytes[..len]).unwrap().1, 8);
+
+        bitmap.set(70).unwrap();
+        let (bytes, len) = bitmap.to_bytes();
+        let (restored, consumed) = Bitmap::from_bytes_counted(&bytes[..len]).unwrap();
+        assert_eq!(consumed, 16);
+        assert_eq!(restored, bitmap);
+
+        bitmap.set(150).unwrap();
+        let (bytes, len) = bitmap.to_bytes();
+        let (restored, consumed) = Bitmap::from_bytes_counted(&bytes).unwrap();
+        assert_eq!(consumed, 24);
+        assert_eq!(len, 24);
+        assert_eq!(restored, bitmap);
+
+        // Secondary indicated but truncated
+        assert!(Bitmap::from_bytes_counted(&bytes[..12]).is_err());
+    }
+
+    #[test]
+    fn test_tertiary_only_roundtrip() {
+        // Only the tertiary indicator (field 65) and one field from 129-192
+        let mut bitmap = Bitmap::new();
+        bitmap.set(65).unwrap();
+        bitmap.set(150).unwrap();
+
+        let (bytes, len) = bitmap.to_bytes();
+        assert_eq!(len, 24);
+
+        // Primary: only bit 1 (secondary present)
+        assert_eq!(&bytes[0..8], &[0x80, 0, 0, 0, 0, 0, 0, 0]);
+        // Secondary: only bit 1 (field 65, tertiary present)
+        assert_eq!(&bytes[8..16], &[0x80, 0, 0, 0, 0, 0, 0, 0]);
+        // Tertiary: field 150 is bit 22 of the third block
+        assert_eq!(&bytes[16..24], &[0, 0, 0x04, 0, 0, 0, 0, 0]);
+
+        let (restored, consumed) = Bitmap::from_bytes_counted(&bytes[..len]).unwrap();
+        assert_eq!(consumed, 24);
+        assert_eq!(restored, bitmap);
+        let (fields, count) = restored.get_set_fields();
+        assert_eq!(&fields[..count], &[1, 65, 150]);
+
+        // Setting only the high field yields the same indicator chain
+        let mut implicit = Bitmap::new();
+        implicit.set(150).unwrap();
+        assert_eq!(implicit, bitmap);
+
+        // A secondary block that indicates tertiary must be followed by it
+        assert!(Bitmap::from_bytes_counted(&bytes[..16]).is_err());
+        assert_eq!(Bitmap::from_bytes(&bytes[..len]).unwrap(), bitmap);
+    }
+
     #[test]
     fn test_get_set_fields() {
         let mut bitmap = Bitmap::new();
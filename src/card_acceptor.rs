@@ -0,0 +1,181 @@
+//! Card Acceptor Name/Location (field 43)
+//!
+//! Field 43 is 40 characters split into fixed-position subfields in the
+//! common layout:
+//!
+//! ```text
+//! positions  1-22  merchant name
+//! positions 23-35  city
+//! positions 36-37  state or province
+//! positions 38-40  country code
+//! ```
+//!
+//! The padding spaces are part of each subfield, so values are kept
+//! untrimmed and the field always re-encodes to the exact input.
+
+use crate::error::{ISO8583Error, Result};
+use std::fmt;
+
+/// Length of field 43
+pub const CARD_ACCEPTOR_LEN: usize = 40;
+
+/// Width of the name subfield
+pub const NAME_LEN: usize = 22;
+
+/// Width of the city subfield
+pub const CITY_LEN: usize = 13;
+
+/// Width of the state subfield
+pub const STATE_LEN: usize = 2;
+
+/// Width of the country subfield
+pub const COUNTRY_LEN: usize = 3;
+
+/// Field 43 split into its fixed-position subfields
+///
+/// Each subfield holds exactly its width in characters, including any
+/// padding spaces.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CardAcceptorNameLocation {
+    /// Merchant name (positions 1-22)
+    pub name: String,
+    /// City (positions 23-35)
+    pub city: String,
+    /// State or province (positions 36-37)
+    pub state: String,
+    /// Country code (positions 38-40)
+    pub country: String,
+}
+
+impl CardAcceptorNameLocation {
+    /// Build from subfield values, padding each with trailing spaces
+    ///
+    /// Values longer than their subfield, or non-ASCII values, are rejected
+    /// rather than truncated.
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::card_acceptor::CardAcceptorNameLocation;
+    ///
+    /// let location = CardAcceptorNameLocation::new("ACME STORE", "SPRINGFIELD", "IL", "USA")?;
+    /// assert_eq!(
+    ///     location.to_string(),
+    ///     "ACME STORE            SPRINGFIELD  ILUSA"
+    /// );
+    /// # Ok::<(), iso8583_core::ISO8583Error>(())
+    /// ```
+    pub fn new(name: &str, city: &str, state: &str, country: &str) -> Result<Self> {
+        let pad = |value: &str, width: usize, subfield: &str| {
+            if value.len() > width || !value.is_ascii() {
+                return Err(ISO8583Error::invalid_field_value(
+                    43,
+                    format!(
+                        "Card acceptor {} must be at most {} ASCII characters",
+                        subfield, width
+                    ),
+                ));
+            }
+            Ok(format!("{:<width$}", value, width = width))
+        };
+
+        Ok(Self {
+            name: pad(name, NAME_LEN, "name")?,
+            city: pad(city, CITY_LEN, "city")?,
+            state: pad(state, STATE_LEN, "state")?,
+            country: pad(country, COUNTRY_LEN, "country")?,
+        })
+    }
+
+    /// Split a 40-character field 43 value into its subfields
+    ///
+    /// Nothing is trimmed, so `parse(s)?.to_string() == s`.
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::card_acceptor::CardAcceptorNameLocation;
+    ///
+    /// let location =
+    ///     CardAcceptorNameLocation::parse("ACME STORE            SPRINGFIELD  ILUSA")?;
+    /// assert_eq!(location.name, "ACME STORE            ");
+    /// assert_eq!(location.city.trim_end(), "SPRINGFIELD");
+    /// assert_eq!(location.country, "USA");
+    /// # Ok::<(), iso8583_core::ISO8583Error>(())
+    /// ```
+    pub fn parse(field43: &str) -> Result<Self> {
+        if field43.len() != CARD_ACCEPTOR_LEN || !field43.is_ascii() {
+            return Err(ISO8583Error::invalid_field_value(
+                43,
+                format!(
+                    "Card acceptor name/location must be {} ASCII characters",
+                    CARD_ACCEPTOR_LEN
+                ),
+            ));
+        }
+
+        let (name, rest) = field43.split_at(NAME_LEN);
+        let (city, rest) = rest.split_at(CITY_LEN);
+        let (state, country) = rest.split_at(STATE_LEN);
+
+        Ok(Self {
+            name: name.to_string(),
+            city: city.to_string(),
+            state: state.to_string(),
+            country: country.to_string(),
+        })
+    }
+}
+
+impl std::str::FromStr for CardAcceptorNameLocation {
+    type Err = ISO8583Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for CardAcceptorNameLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:<nw$.nw$}{:<cw$.cw$}{:<sw$.sw$}{:<kw$.kw$}",
+            self.name,
+            self.city,
+            self.state,
+            self.country,
+            nw = NAME_LEN,
+            cw = CITY_LEN,
+            sw = STATE_LEN,
+            kw = COUNTRY_LEN
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip_keeps_spaces() {
+        // Leading space in the name, trailing spaces everywhere
+        let raw = " CORNER CAFE          LONDON       GB826";
+        let location = CardAcceptorNameLocation::parse(raw).unwrap();
+
+        assert_eq!(location.name, " CORNER CAFE          ");
+        assert_eq!(location.city, "LONDON       ");
+        assert_eq!(location.state, "GB");
+        assert_eq!(location.country, "826");
+        assert_eq!(location.to_string(), raw);
+
+        let built = CardAcceptorNameLocation::new(" CORNER CAFE", "LONDON", "GB", "826").unwrap();
+        assert_eq!(built, location);
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(CardAcceptorNameLocation::parse("TOO SHORT").is_err());
+        assert!(CardAcceptorNameLocation::parse(&"X".repeat(41)).is_err());
+        assert!(CardAcceptorNameLocation::new(&"X".repeat(23), "", "", "").is_err());
+        assert!(CardAcceptorNameLocation::new("", "", "ILL", "").is_err());
+        assert!(CardAcceptorNameLocation::new("CAFÉ", "", "", "").is_err());
+    }
+}
@@ -0,0 +1,167 @@
+//! Card-present derivation
+//!
+//! Whether a transaction counts as card-present for interchange is not
+//! carried in a single field; acquirers derive it from the POS entry mode
+//! (field 22), the POS condition code (field 25) and the presence of track
+//! data (fields 35 and 45). The rules differ slightly between networks, so
+//! they sit behind the [`CardPresenceRule`] trait.
+
+use crate::field::Field;
+use crate::message::ISO8583Message;
+use crate::pos_entry_mode::PanEntryMode;
+
+/// Decides whether a message describes a card-present transaction
+pub trait CardPresenceRule {
+    /// Check if the card was physically presented
+    fn is_card_present(&self, message: &ISO8583Message) -> bool;
+}
+
+/// The common card-present rule set
+///
+/// Applied in order, the first match wins:
+///
+/// 1. A field 25 condition code that is not card-present (e.g. 08 mail or
+///    telephone order, 59 e-commerce) → not present.
+/// 2. Field 22 PAN entry mode 01 (manual key entry) → not present.
+/// 3. Field 22 PAN entry mode chip or contactless (05, 07, 95) with track
+///    data (field 35 or 45) in the message → present.
+/// 4. Otherwise → not present.
+///
+/// Not-present signals are checked first, so a contradictory message is
+/// never qualified as card-present. Unparseable fields 22 and 25 are
+/// treated as absent. Networks that also qualify swiped transactions can
+/// supply their own [`CardPresenceRule`].
+///
+/// # Example
+/// ```
+/// use iso8583_core::card_presence::{CardPresenceRule, DefaultCardPresence};
+/// use iso8583_core::field::FieldValue;
+/// use iso8583_core::*;
+///
+/// let mut msg = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+/// msg.set_field(Field::PointOfServiceEntryMode, FieldValue::from_string("071"))?;
+/// msg.set_field(Field::Track2Data, FieldValue::from_string("4111111111111111=2512101"))?;
+/// assert!(DefaultCardPresence.is_card_present(&msg));
+///
+/// msg.set_field(Field::PointOfServiceConditionCode, FieldValue::from_string("08"))?;
+/// assert!(!DefaultCardPresence.is_card_present(&msg));
+/// # Ok::<(), ISO8583Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultCardPresence;
+
+impl CardPresenceRule for DefaultCardPresence {
+    fn is_card_present(&self, message: &ISO8583Message) -> bool {
+        if let Ok(condition) = message.get_pos_condition() {
+            if !condition.is_card_present() {
+                return false;
+            }
+        }
+
+        if let Ok(entry_mode) = message.get_pos_entry_mode() {
+            if entry_mode.pan_entry == PanEntryMode::Manual {
+                return false;
+            }
+            if entry_mode.is_chip() {
+                return message.has_field(Field::Track2Data)
+                    || message.has_field(Field::Track1Data);
+            }
+        }
+
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::FieldValue;
+    use crate::mti::MessageType;
+
+    fn message(fields: &[(Field, &str)]) -> ISO8583Message {
+        let mut msg = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+        for (field, value) in fields {
+            msg.set_field(*field, FieldValue::from_string(*value))
+                .unwrap();
+        }
+        msg
+    }
+
+    #[test]
+    fn test_chip_present() {
+        let msg = message(&[
+            (Field::PointOfServiceEntryMode, "051"),
+            (Field::PointOfServiceConditionCode, "00"),
+            (Field::Track2Data, "4111111111111111=2512101"),
+        ]);
+        assert!(DefaultCardPresence.is_card_present(&msg));
+
+        // Contactless with track 1 only
+        let msg = message(&[
+            (Field::PointOfServiceEntryMode, "071"),
+            (Field::Track1Data, "B4111111111111111^DOE/JOHN^2512101"),
+        ]);
+        assert!(DefaultCardPresence.is_card_present(&msg));
+    }
+
+    #[test]
+    fn test_chip_without_track_data() {
+        // Chip entry mode alone is not enough without fields 35/45
+        let msg = message(&[
+            (Field::PointOfServiceEntryMode, "051"),
+            (Field::PointOfServiceConditionCode, "00"),
+        ]);
+        assert!(!DefaultCardPresence.is_card_present(&msg));
+
+        // Nor is track data without a chip entry mode
+        let msg = message(&[(Field::Track2Data, "4111111111111111=2512101")]);
+        assert!(!DefaultCardPresence.is_card_present(&msg));
+    }
+
+    #[test]
+    fn test_not_present() {
+        // E-commerce: keyed PAN, condition code 59
+        let ecommerce = message(&[
+            (Field::PointOfServiceEntryMode, "010"),
+            (Field::PointOfServiceConditionCode, "59"),
+        ]);
+        assert!(!DefaultCardPresence.is_card_present(&ecommerce));
+
+        // Mail order overrides a chip entry mode
+        let moto = message(&[
+            (Field::PointOfServiceEntryMode, "051"),
+            (Field::PointOfServiceConditionCode, "08"),
+        ]);
+        assert!(!DefaultCardPresence.is_card_present(&moto));
+
+        // Manual entry even with a normal condition code
+        let keyed = message(&[
+            (Field::PointOfServiceEntryMode, "011"),
+            (Field::PointOfServiceConditionCode, "00"),
+        ]);
+        assert!(!DefaultCardPresence.is_card_present(&keyed));
+
+        // No signals at all
+        assert!(!DefaultCardPresence.is_card_present(&message(&[])));
+    }
+
+    #[test]
+    fn test_custom_rule() {
+        // A network that treats keyed entry at an attended terminal as present
+        struct KeyedAttended;
+        impl CardPresenceRule for KeyedAttended {
+            fn is_card_present(&self, message: &ISO8583Message) -> bool {
+                message
+                    .get_pos_condition()
+                    .is_ok_and(|condition| condition.is_card_present())
+            }
+        }
+
+        let keyed = message(&[
+            (Field::PointOfServiceEntryMode, "011"),
+            (Field::PointOfServiceConditionCode, "00"),
+        ]);
+        assert!(!keyed.is_card_present());
+        assert!(keyed.is_card_present_with(&KeyedAttended));
+    }
+}
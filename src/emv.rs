@@ -0,0 +1,365 @@
+//! EMV chip data (field 55) handling
+//!
+//! Field 55 carries ICC data as a sequence of BER-TLV objects. Constructed
+//! tags (e.g. template 77) contain nested TLVs, which are parsed recursively.
+//!
+//! The built-in 1987 table keeps field 55 as ISO-reserved `ans` text, so hex
+//! ICC data round-trips as a string. To send it as raw bytes, attach a
+//! [`RuntimeSpec`](crate::spec::RuntimeSpec) that overrides field 55 with a
+//! binary definition:
+//!
+//! ```
+//! use iso8583_core::field::FieldValue;
+//! use iso8583_core::spec::{DataType, FieldDefinition, RuntimeSpec};
+//! use iso8583_core::*;
+//!
+//! let mut spec = RuntimeSpec::new();
+//! spec.override_field(55, FieldDefinition::lllvar(DataType::Binary, 999));
+//!
+//! let mut msg = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+//! msg.set_runtime_spec(Some(spec));
+//! msg.set_field(Field::ReservedISO1, FieldValue::from_binary(vec![0x9C, 0x01, 0x00]))?;
+//!
+//! let options = ParseOptions::new().runtime_spec(spec);
+//! let parsed = ISO8583Message::from_bytes_with_options(&msg.to_bytes(), &options)?;
+//! assert_eq!(
+//!     parsed.get_field(Field::ReservedISO1),
+//!     Some(&FieldValue::from_binary(vec![0x9C, 0x01, 0x00]))
+//! );
+//! # Ok::<(), ISO8583Error>(())
+//! ```
+
+use crate::error::{ISO8583Error, Result};
+
+/// A single BER-TLV data object
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tlv {
+    /// Tag bytes (1-3 bytes, e.g. `[0x9F, 0x26]`)
+    pub tag: Vec<u8>,
+    /// Value bytes (for constructed tags, the encoded children)
+    pub value: Vec<u8>,
+    /// Nested objects for constructed tags
+    pub children: Vec<Tlv>,
+}
+
+impl Tlv {
+    /// Check if this is a constructed (template) tag
+    pub fn is_constructed(&self) -> bool {
+        self.tag.first().is_some_and(|b| b & 0x20 != 0)
+    }
+}
+
+/// Tags required in field 55 for an online authorization
+///
+/// ARQC (9F26), cryptogram information data (9F27), issuer application
+/// data (9F10), unpredictable number (9F37), ATC (9F36), TVR (95),
+/// transaction date (9A), transaction type (9C), amount authorized (9F02),
+/// transaction currency code (5F2A), AIP (82) and terminal country code (9F1A).
+pub const ONLINE_AUTH_REQUIRED_TAGS: &[&[u8]] = &[
+    &[0x9F, 0x26],
+    &[0x9F, 0x27],
+    &[0x9F, 0x10],
+    &[0x9F, 0x37],
+    &[0x9F, 0x36],
+    &[0x95],
+    &[0x9A],
+    &[0x9C],
+    &[0x9F, 0x02],
+    &[0x5F, 0x2A],
+    &[0x82],
+    &[0x9F, 0x1A],
+];
+
+/// Cryptogram data an issuer needs to verify an ARQC
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmvCryptogramData {
+    /// Application cryptogram (9F26)
+    pub arqc: [u8; 8],
+    /// Application transaction counter (9F36)
+    pub atc: u16,
+    /// Unpredictable number (9F37)
+    pub unpredictable_number: [u8; 4],
+    /// Cryptogram information data (9F27)
+    pub cryptogram_info: u8,
+}
+
+impl EmvCryptogramData {
+    /// Tags read by [`EmvCryptogramData::from_tlvs`]
+    pub const TAGS: &'static [&'static [u8]] =
+        &[&[0x9F, 0x26], &[0x9F, 0x36], &[0x9F, 0x37], &[0x9F, 0x27]];
+
+    /// Extract the cryptogram tags from parsed TLVs
+    ///
+    /// Tags are found at any nesting depth, so data wrapped in template 77
+    /// works the same as top-level tags. Every tag must be present with its
+    /// EMV length.
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::emv::{parse_tlvs, EmvCryptogramData};
+    ///
+    /// let tlvs = parse_tlvs(&[
+    ///     0x77, 0x1B,
+    ///     0x9F, 0x26, 0x08, 1, 2, 3, 4, 5, 6, 7, 8,
+    ///     0x9F, 0x36, 0x02, 0x00, 0x2A,
+    ///     0x9F, 0x37, 0x04, 0xDE, 0xAD, 0xBE, 0xEF,
+    ///     0x9F, 0x27, 0x01, 0x80,
+    /// ])?;
+    /// let data = EmvCryptogramData::from_tlvs(&tlvs)?;
+    /// assert_eq!(data.atc, 42);
+    /// assert_eq!(data.cryptogram_info, 0x80);
+    /// # Ok::<(), iso8583_core::ISO8583Error>(())
+    /// ```
+    pub fn from_tlvs(tlvs: &[Tlv]) -> Result<Self> {
+        validate_required_tags(tlvs, Self::TAGS)?;
+
+        fn value<const N: usize>(tlvs: &[Tlv], tag: &[u8]) -> Result<[u8; N]> {
+            let tlv = find_tag(tlvs, tag).expect("presence checked above");
+            tlv.value.as_slice().try_into().map_err(|_| {
+                ISO8583Error::invalid_field_value(
+                    55,
+                    format!(
+                        "EMV tag {} must be {} bytes, got {}",
+                        hex::encode_upper(tag),
+                        N,
+                        tlv.value.len()
+                    ),
+                )
+            })
+        }
+
+        Ok(Self {
+            arqc: value(tlvs, &[0x9F, 0x26])?,
+            atc: u16::from_be_bytes(value(tlvs, &[0x9F, 0x36])?),
+            unpredictable_number: value(tlvs, &[0x9F, 0x37])?,
+            cryptogram_info: value::<1>(tlvs, &[0x9F, 0x27])?[0],
+        })
+    }
+}
+
+/// Parse a sequence of BER-TLV objects
+///
+/// Filler bytes (0x00 / 0xFF) between objects are skipped.
+///
+/// # Example
+/// ```
+/// use iso8583_core::emv::parse_tlvs;
+///
+/// let tlvs = parse_tlvs(&[0x9F, 0x27, 0x01, 0x80, 0x82, 0x02, 0x19, 0x80]).unwrap();
+/// assert_eq!(tlvs.len(), 2);
+/// assert_eq!(tlvs[0].tag, vec![0x9F, 0x27]);
+/// assert_eq!(tlvs[1].value, vec![0x19, 0x80]);
+/// ```
+pub fn parse_tlvs(data: &[u8]) -> Result<Vec<Tlv>> {
+    let mut tlvs = Vec::new();
+    let mut offset = 0;
+
+    while offset < data.len() {
+        if data[offset] == 0x00 || data[offset] == 0xFF {
+            offset += 1;
+            continue;
+        }
+
+        // Tag: multi-byte when the low 5 bits of the first byte are all set
+        let tag_start = offset;
+        let first = data[offset];
+        offset += 1;
+        if first & 0x1F == 0x1F {
+            loop {
+                let b = *data
+                    .get(offset)
+                    .ok_or_else(|| ISO8583Error::invalid_field_value(55, "Truncated EMV tag"))?;
+                offset += 1;
+                if b & 0x80 == 0 {
+                    break;
+                }
+            }
+        }
+        let tag = data[tag_start..offset].to_vec();
+
+        // Length: short form, or 0x81/0x82 followed by 1/2 length bytes
+        let len_byte = *data.get(offset).ok_or_else(|| {
+            ISO8583Error::invalid_field_value(
+                55,
+                format!("Missing length for EMV tag {}", hex::encode_upper(&tag)),
+            )
+        })?;
+        offset += 1;
+        let length = if len_byte & 0x80 == 0 {
+            len_byte as usize
+        } else {
+            let count = (len_byte & 0x7F) as usize;
+            if count == 0 || count > 2 || offset + count > data.len() {
+                return Err(ISO8583Error::invalid_field_value(
+                    55,
+                    format!("Invalid length for EMV tag {}", hex::encode_upper(&tag)),
+                ));
+            }
+            let length = data[offset..offset + count]
+                .iter()
+                .fold(0usize, |acc, &b| (acc << 8) | b as usize);
+            offset += count;
+            length
+        };
+
+        if offset + length > data.len() {
+            return Err(ISO8583Error::invalid_field_value(
+                55,
+                format!(
+                    "EMV tag {} length {} exceeds remaining {} bytes",
+                    hex::encode_upper(&tag),
+                    length,
+                    data.len() - offset
+                ),
+            ));
+        }
+
+        let value = data[offset..offset + length].to_vec();
+        offset += length;
+
+        let mut tlv = Tlv {
+            tag,
+            value,
+            children: Vec::new(),
+        };
+        if tlv.is_constructed() {
+            tlv.children = parse_tlvs(&tlv.value)?;
+        }
+        tlvs.push(tlv);
+    }
+
+    Ok(tlvs)
+}
+
+/// Find a tag anywhere in the TLV tree (including nested templates)
+pub fn find_tag<'a>(tlvs: &'a [Tlv], tag: &[u8]) -> Option<&'a Tlv> {
+    for tlv in tlvs {
+        if tlv.tag == tag {
+            return Some(tlv);
+        }
+        if let Some(found) = find_tag(&tlv.children, tag) {
+            return Some(found);
+        }
+    }
+    None
+}
+
+/// Check that every required tag is present (at any nesting depth)
+///
+/// # Example
+/// ```
+/// use iso8583_core::emv::{parse_tlvs, validate_required_tags};
+///
+/// // Tags nested under the format 2 response template (77)
+/// let tlvs = parse_tlvs(&[0x77, 0x04, 0x9F, 0x27, 0x01, 0x80]).unwrap();
+/// assert!(validate_required_tags(&tlvs, &[&[0x9F, 0x27]]).is_ok());
+/// assert!(validate_required_tags(&tlvs, &[&[0x9F, 0x26]]).is_err());
+/// ```
+pub fn validate_required_tags(tlvs: &[Tlv], required: &[&[u8]]) -> Result<()> {
+    let missing: Vec<String> = required
+        .iter()
+        .filter(|tag| find_tag(tlvs, tag).is_none())
+        .map(hex::encode_upper)
+        .collect();
+
+    if !missing.is_empty() {
+        return Err(ISO8583Error::ValidationError(format!(
+            "Missing required EMV tags: {}",
+            missing.join(", ")
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn online_auth_data() -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&[0x9F, 0x26, 0x08, 1, 2, 3, 4, 5, 6, 7, 8]);
+        data.extend_from_slice(&[0x9F, 0x27, 0x01, 0x80]);
+        data.extend_from_slice(&[0x9F, 0x10, 0x03, 0x06, 0x01, 0x0A]);
+        data.extend_from_slice(&[0x9F, 0x37, 0x04, 0xDE, 0xAD, 0xBE, 0xEF]);
+        data.extend_from_slice(&[0x9F, 0x36, 0x02, 0x00, 0x01]);
+        data.extend_from_slice(&[0x95, 0x05, 0, 0, 0, 0, 0]);
+        data.extend_from_slice(&[0x9A, 0x03, 0x26, 0x01, 0x15]);
+        data.extend_from_slice(&[0x9C, 0x01, 0x00]);
+        data.extend_from_slice(&[0x9F, 0x02, 0x06, 0, 0, 0, 0, 0x10, 0x00]);
+        data.extend_from_slice(&[0x5F, 0x2A, 0x02, 0x08, 0x40]);
+        data.extend_from_slice(&[0x82, 0x02, 0x19, 0x80]);
+        data.extend_from_slice(&[0x9F, 0x1A, 0x02, 0x08, 0x40]);
+        data
+    }
+
+    #[test]
+    fn test_parse_tlvs() {
+        let tlvs = parse_tlvs(&online_auth_data()).unwrap();
+        assert_eq!(tlvs.len(), ONLINE_AUTH_REQUIRED_TAGS.len());
+        assert_eq!(find_tag(&tlvs, &[0x9F, 0x26]).unwrap().value.len(), 8);
+        assert!(validate_required_tags(&tlvs, ONLINE_AUTH_REQUIRED_TAGS).is_ok());
+    }
+
+    #[test]
+    fn test_nested_template() {
+        let inner = online_auth_data();
+        let mut data = vec![0x77, 0x81, inner.len() as u8];
+        data.extend_from_slice(&inner);
+
+        let tlvs = parse_tlvs(&data).unwrap();
+        assert_eq!(tlvs.len(), 1);
+        assert!(tlvs[0].is_constructed());
+        assert!(validate_required_tags(&tlvs, ONLINE_AUTH_REQUIRED_TAGS).is_ok());
+    }
+
+    #[test]
+    fn test_missing_tags() {
+        let tlvs = parse_tlvs(&[0x9F, 0x27, 0x01, 0x80]).unwrap();
+        let err = validate_required_tags(&tlvs, &[&[0x9F, 0x26], &[0x82]]).unwrap_err();
+        assert_eq!(
+            err,
+            ISO8583Error::ValidationError("Missing required EMV tags: 9F26, 82".to_string())
+        );
+    }
+
+    #[test]
+    fn test_cryptogram_data() {
+        let expected = EmvCryptogramData {
+            arqc: [1, 2, 3, 4, 5, 6, 7, 8],
+            atc: 1,
+            unpredictable_number: [0xDE, 0xAD, 0xBE, 0xEF],
+            cryptogram_info: 0x80,
+        };
+
+        let tlvs = parse_tlvs(&online_auth_data()).unwrap();
+        assert_eq!(EmvCryptogramData::from_tlvs(&tlvs).unwrap(), expected);
+
+        // Nested under template 77
+        let inner = online_auth_data();
+        let mut nested = vec![0x77, 0x81, inner.len() as u8];
+        nested.extend_from_slice(&inner);
+        let tlvs = parse_tlvs(&nested).unwrap();
+        assert_eq!(EmvCryptogramData::from_tlvs(&tlvs).unwrap(), expected);
+
+        // Missing ATC
+        let tlvs = parse_tlvs(&[0x9F, 0x27, 0x01, 0x80]).unwrap();
+        assert!(matches!(
+            EmvCryptogramData::from_tlvs(&tlvs),
+            Err(ISO8583Error::ValidationError(reason)) if reason.contains("9F36")
+        ));
+
+        // Short ARQC
+        let mut short = online_auth_data();
+        short.splice(0..11, [0x9F, 0x26, 0x02, 1, 2]);
+        let tlvs = parse_tlvs(&short).unwrap();
+        assert!(EmvCryptogramData::from_tlvs(&tlvs).is_err());
+    }
+
+    #[test]
+    fn test_truncated_tlv() {
+        assert!(parse_tlvs(&[0x9F, 0x26, 0x08, 0x01]).is_err());
+        assert!(parse_tlvs(&[0x9F]).is_err());
+        assert!(parse_tlvs(&[0x82]).is_err());
+    }
+}
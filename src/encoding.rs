@@ -18,6 +18,20 @@ pub enum Encoding {
     EBCDIC,
 }
 
+/// Wire representation of fixed-length binary fields
+///
+/// ASCII-oriented deployments often send binary fields such as the PIN
+/// block (field 52) or MACs (fields 64 and 128) as hex text, so 8 bytes
+/// travel as 16 characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BinaryEncoding {
+    /// Raw bytes
+    #[default]
+    Raw,
+    /// Two hex characters per byte (uppercase when written)
+    Hex,
+}
+
 /// Encode numeric string to BCD
 ///
 /// Each pair of digits is encoded into one byte.
@@ -37,6 +37,10 @@ pub enum ISO8583Error {
     #[error("Invalid bitmap: {0}")]
     InvalidBitmap(String),
 
+    /// Field 1 is set but the message ends before the secondary bitmap
+    #[error("Secondary bitmap indicated by field 1 but not present")]
+    MissingSecondaryBitmap,
+
     /// Invalid encoding
     #[error("Invalid encoding: {0}")]
     InvalidEncoding(String),
@@ -45,6 +49,22 @@ pub enum ISO8583Error {
     #[error("Message too short: expected at least {expected} bytes, got {actual}")]
     MessageTooShort { expected: usize, actual: usize },
 
+    /// Bytes left over after the last field the bitmap declares
+    #[error("{0} trailing bytes after the last field")]
+    TrailingBytes(usize),
+
+    /// A length header (or an unterminated frame) exceeds the reader's limit
+    #[error("Frame length {len} exceeds the maximum of {max} bytes")]
+    FrameTooLarge { len: usize, max: usize },
+
+    /// Fields stopped lining up with the buffer, starting at `field`
+    #[error("Message desynchronized at field {field} (offset {offset}): {reason}")]
+    Desync {
+        field: u8,
+        offset: usize,
+        reason: String,
+    },
+
     /// Invalid PAN (Primary Account Number)
     #[error("Invalid PAN: {0}")]
     InvalidPAN(String),
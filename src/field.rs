@@ -7,11 +7,15 @@
 //! - Validation rules
 
 use crate::error::{ISO8583Error, Result};
+use crate::spec::{self, DataType, LengthType};
+use std::borrow::Cow;
 use std::fmt;
 
 /// ISO 8583 Field enumeration
+///
+/// Fields order by field number, so a `BTreeMap<Field, _>` iterates in wire order.
 #[allow(missing_docs)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(u8)]
 pub enum Field {
     // Field 1 is the secondary bitmap (handled specially)
@@ -149,7 +153,7 @@ pub enum Field {
 }
 
 /// Field type enumeration
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum FieldType {
     /// Numeric (n)
     Numeric,
@@ -165,6 +169,41 @@ pub enum FieldType {
     Track2,
     /// Track 3 format (x+n)
     Track3,
+    /// Signed amount (x+n): 'C' (credit) or 'D' (debit) followed by digits
+    SignedAmount,
+}
+
+/// What to do with a value longer than its fixed field length
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Cut the value to the defined length when generating
+    Truncate,
+    /// Fail checked generation (`try_to_bytes`) with a length error
+    Error,
+    /// Fail any generation that takes the policy (`try_to_bytes_with_options`
+    /// and `to_bytes_with_options`) with a length error
+    Reject,
+}
+
+impl FieldType {
+    /// Default overflow policy for fixed-length fields of this type
+    ///
+    /// Values whose meaning changes when cut (numeric, binary, track 2,
+    /// signed amounts)
+    /// default to [`OverflowPolicy::Error`]; free text defaults to
+    /// [`OverflowPolicy::Truncate`].
+    pub fn default_overflow_policy(&self) -> OverflowPolicy {
+        match self {
+            FieldType::Numeric
+            | FieldType::Binary
+            | FieldType::Track2
+            | FieldType::SignedAmount => OverflowPolicy::Error,
+            FieldType::Alpha
+            | FieldType::AlphaNumeric
+            | FieldType::AlphaNumericSpecial
+            | FieldType::Track3 => OverflowPolicy::Truncate,
+        }
+    }
 }
 
 /// Field length specification
@@ -189,6 +228,72 @@ pub struct FieldDefinition {
     pub description: &'static str,
 }
 
+// Conversions to and from the `spec` type system.
+//
+// `spec` (`DataType`, `LengthType`, `spec::FieldDefinition`) is the canonical
+// representation; these conversions let code built on this legacy module
+// interoperate with it while the field metadata is consolidated.
+
+impl From<FieldType> for DataType {
+    fn from(field_type: FieldType) -> Self {
+        match field_type {
+            FieldType::Numeric => DataType::Numeric,
+            FieldType::Alpha => DataType::Alpha,
+            FieldType::AlphaNumeric => DataType::Alphanumeric,
+            FieldType::AlphaNumericSpecial => DataType::AlphanumericSpecial,
+            FieldType::Binary => DataType::Binary,
+            FieldType::Track2 => DataType::Track2,
+            FieldType::Track3 => DataType::Track3,
+            FieldType::SignedAmount => DataType::SignedAmount,
+        }
+    }
+}
+
+impl From<DataType> for FieldType {
+    fn from(data_type: DataType) -> Self {
+        match data_type {
+            DataType::Numeric => FieldType::Numeric,
+            DataType::Alpha => FieldType::Alpha,
+            DataType::Alphanumeric => FieldType::AlphaNumeric,
+            DataType::AlphanumericSpecial => FieldType::AlphaNumericSpecial,
+            DataType::Binary => FieldType::Binary,
+            DataType::Track2 => FieldType::Track2,
+            DataType::Track3 => FieldType::Track3,
+            DataType::SignedAmount => FieldType::SignedAmount,
+        }
+    }
+}
+
+/// Lengths above `u16::MAX` saturate (no ISO 8583 field comes close)
+impl From<FieldLength> for (LengthType, u16) {
+    fn from(length: FieldLength) -> Self {
+        let (length_type, len) = match length {
+            FieldLength::Fixed(len) => (LengthType::Fixed, len),
+            FieldLength::LLVar(max) => (LengthType::Llvar, max),
+            FieldLength::LLLVar(max) => (LengthType::Lllvar, max),
+        };
+        (length_type, u16::try_from(len).unwrap_or(u16::MAX))
+    }
+}
+
+impl From<(LengthType, u16)> for FieldLength {
+    fn from((length_type, len): (LengthType, u16)) -> Self {
+        let len = len as usize;
+        match length_type {
+            LengthType::Fixed => FieldLength::Fixed(len),
+            LengthType::Llvar => FieldLength::LLVar(len),
+            LengthType::Lllvar => FieldLength::LLLVar(len),
+        }
+    }
+}
+
+impl From<FieldDefinition> for spec::FieldDefinition {
+    fn from(def: FieldDefinition) -> Self {
+        let (length_type, max_len) = def.length.into();
+        spec::FieldDefinition::new(def.field_type.into(), length_type, max_len)
+    }
+}
+
 /// Field value (parsed data)
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FieldValue {
@@ -198,6 +303,33 @@ pub enum FieldValue {
     Binary(Vec<u8>),
 }
 
+/// Field value that borrows from the input buffer where possible
+///
+/// Produced by [`MessageView`](crate::message::MessageView) so read-mostly
+/// paths avoid allocating per field. Owned data is still accepted, and
+/// [`into_owned`](Self::into_owned) / [`FieldValue::as_ref`] bridge the two.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldValueRef<'a> {
+    /// String value
+    String(Cow<'a, str>),
+    /// Binary value
+    Binary(Cow<'a, [u8]>),
+}
+
+/// Serializes as the string value, or lowercase hex for binary values
+#[cfg(feature = "serde")]
+impl serde::Serialize for FieldValue {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            FieldValue::String(s) => serializer.serialize_str(s),
+            FieldValue::Binary(b) => serializer.serialize_str(&hex::encode(b)),
+        }
+    }
+}
+
 impl Field {
     /// Get field number
     pub fn number(&self) -> u8 {
@@ -207,8 +339,7 @@ impl Field {
     /// Get field definition
     pub fn definition(&self) -> FieldDefinition {
         let num = self.number();
-        let defs = get_field_definitions();
-        defs.get(num as usize).copied().unwrap_or(FieldDefinition {
+        FieldDefinition::get(num).unwrap_or(FieldDefinition {
             number: num,
             name: "Unknown",
             field_type: FieldType::AlphaNumericSpecial,
@@ -217,6 +348,34 @@ impl Field {
         })
     }
 
+    /// Check if the field carries cardholder secrets (PAN, track data, PIN block)
+    pub fn is_sensitive(&self) -> bool {
+        matches!(
+            self,
+            Field::PrimaryAccountNumber
+                | Field::Track2Data
+                | Field::Track1Data
+                | Field::PersonalIdentificationNumberData
+        )
+    }
+
+    /// Check if the field may carry cardholder data
+    ///
+    /// The classification used by tokenization and detokenization proxies:
+    /// the [sensitive](Self::is_sensitive) fields (PAN, track 1 and 2 data,
+    /// PIN block) plus the expiration date (14), PAN extended country code
+    /// (20), extended PAN (34) and track 3 data (36).
+    pub fn is_cardholder_data(&self) -> bool {
+        self.is_sensitive()
+            || matches!(
+                self,
+                Field::ExpirationDate
+                    | Field::PANExtendedCountryCode
+                    | Field::ExtendedPrimaryAccountNumber
+                    | Field::Track3Data
+            )
+    }
+
     /// Create field from number
     pub fn from_number(num: u8) -> Result<Self> {
         match num {
@@ -388,6 +547,19 @@ impl FieldValue {
         }
     }
 
+    /// Length in bytes (characters for ASCII string values)
+    pub fn len(&self) -> usize {
+        match self {
+            Self::String(s) => s.len(),
+            Self::Binary(b) => b.len(),
+        }
+    }
+
+    /// Check if the value is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     /// Convert to string (lossy for binary)
     pub fn to_string_lossy(&self) -> String {
         match self {
@@ -395,6 +567,128 @@ impl FieldValue {
             Self::Binary(b) => String::from_utf8_lossy(b).to_string(),
         }
     }
+
+    /// Borrow as a [`FieldValueRef`] without copying
+    pub fn as_ref(&self) -> FieldValueRef<'_> {
+        match self {
+            Self::String(s) => FieldValueRef::String(Cow::Borrowed(s)),
+            Self::Binary(b) => FieldValueRef::Binary(Cow::Borrowed(b)),
+        }
+    }
+
+    /// Borrow with the padding `def` implies on the wire removed
+    ///
+    /// Fixed numeric fields lose leading zeros (keeping at least one digit)
+    /// and other fixed string fields lose trailing spaces, mirroring how
+    /// short values are padded when encoded. Leading and embedded spaces,
+    /// variable-length values and binary values are left as-is.
+    pub fn trimmed(&self, def: &FieldDefinition) -> FieldValueRef<'_> {
+        let (Self::String(s), FieldLength::Fixed(_)) = (self, def.length) else {
+            return self.as_ref();
+        };
+
+        let trimmed = match def.field_type {
+            FieldType::Numeric => trim_leading_zeros(s),
+            _ => s.trim_end_matches(' '),
+        };
+        FieldValueRef::String(Cow::Borrowed(trimmed))
+    }
+}
+
+/// Strip leading zeros, keeping the last digit of an all-zero value
+pub(crate) fn trim_leading_zeros(s: &str) -> &str {
+    let trimmed = s.trim_start_matches('0');
+    if trimmed.is_empty() && !s.is_empty() {
+        &s[s.len() - 1..]
+    } else {
+        trimmed
+    }
+}
+
+impl<'a> FieldValueRef<'a> {
+    /// Get as string reference
+    pub fn as_string(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s),
+            Self::Binary(_) => None,
+        }
+    }
+
+    /// Get as binary reference
+    pub fn as_binary(&self) -> Option<&[u8]> {
+        match self {
+            Self::String(_) => None,
+            Self::Binary(b) => Some(b),
+        }
+    }
+
+    /// Length in bytes (characters for ASCII string values)
+    pub fn len(&self) -> usize {
+        match self {
+            Self::String(s) => s.len(),
+            Self::Binary(b) => b.len(),
+        }
+    }
+
+    /// Check if the value is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Check if the value borrows from its source rather than owning it
+    pub fn is_borrowed(&self) -> bool {
+        matches!(
+            self,
+            Self::String(Cow::Borrowed(_)) | Self::Binary(Cow::Borrowed(_))
+        )
+    }
+
+    /// Convert into an owned [`FieldValue`], reusing owned buffers
+    pub fn into_owned(self) -> FieldValue {
+        match self {
+            Self::String(s) => FieldValue::String(s.into_owned()),
+            Self::Binary(b) => FieldValue::Binary(b.into_owned()),
+        }
+    }
+}
+
+impl From<FieldValue> for FieldValueRef<'static> {
+    fn from(value: FieldValue) -> Self {
+        match value {
+            FieldValue::String(s) => Self::String(Cow::Owned(s)),
+            FieldValue::Binary(b) => Self::Binary(Cow::Owned(b)),
+        }
+    }
+}
+
+impl<'a> From<FieldValueRef<'a>> for FieldValue {
+    fn from(value: FieldValueRef<'a>) -> Self {
+        value.into_owned()
+    }
+}
+
+impl From<&FieldValueRef<'_>> for FieldValue {
+    fn from(value: &FieldValueRef<'_>) -> Self {
+        match value {
+            FieldValueRef::String(s) => FieldValue::String(s.to_string()),
+            FieldValueRef::Binary(b) => FieldValue::Binary(b.to_vec()),
+        }
+    }
+}
+
+impl PartialEq<FieldValue> for FieldValueRef<'_> {
+    fn eq(&self, other: &FieldValue) -> bool {
+        *self == other.as_ref()
+    }
+}
+
+impl fmt::Display for FieldValueRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::String(s) => write!(f, "{}", s),
+            Self::Binary(b) => write!(f, "{}", hex::encode(b)),
+        }
+    }
 }
 
 impl fmt::Display for Field {
@@ -403,6 +697,17 @@ impl fmt::Display for Field {
     }
 }
 
+/// Overwrites the backing buffer with zeros and empties it
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for FieldValue {
+    fn zeroize(&mut self) {
+        match self {
+            Self::String(s) => s.zeroize(),
+            Self::Binary(b) => b.zeroize(),
+        }
+    }
+}
+
 impl fmt::Display for FieldValue {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -657,33 +962,33 @@ fn get_field_definitions() -> Vec<FieldDefinition> {
         FieldDefinition {
             number: 28,
             name: "Transaction Fee Amount",
-            field_type: FieldType::Numeric,
-            length: FieldLength::Fixed(8),
-            description: "Transaction fee",
+            field_type: FieldType::SignedAmount,
+            length: FieldLength::Fixed(9),
+            description: "Transaction fee (C/D sign + 8 digits)",
         },
         // Field 29 - Settlement Fee Amount
         FieldDefinition {
             number: 29,
             name: "Settlement Fee Amount",
-            field_type: FieldType::Numeric,
-            length: FieldLength::Fixed(8),
-            description: "Settlement fee",
+            field_type: FieldType::SignedAmount,
+            length: FieldLength::Fixed(9),
+            description: "Settlement fee (C/D sign + 8 digits)",
         },
         // Field 30 - Transaction Processing Fee Amount
         FieldDefinition {
             number: 30,
             name: "Transaction Processing Fee Amount",
-            field_type: FieldType::Numeric,
-            length: FieldLength::Fixed(8),
-            description: "Processing fee",
+            field_type: FieldType::SignedAmount,
+            length: FieldLength::Fixed(9),
+            description: "Processing fee (C/D sign + 8 digits)",
         },
         // Field 31 - Settlement Processing Fee Amount
         FieldDefinition {
             number: 31,
             name: "Settlement Processing Fee Amount",
-            field_type: FieldType::Numeric,
-            length: FieldLength::Fixed(8),
-            description: "Settlement processing fee",
+            field_type: FieldType::SignedAmount,
+            length: FieldLength::Fixed(9),
+            description: "Settlement processing fee (C/D sign + 8 digits)",
         },
         // Field 32 - Acquiring Institution Identification Code
         FieldDefinition {
@@ -1344,19 +1649,269 @@ fn get_field_definitions() -> Vec<FieldDefinition> {
 
 impl FieldDefinition {
     /// Get field definition by number
+    ///
+    /// Returns `None` for field numbers that have no entry in the table.
     pub fn get(number: u8) -> Option<Self> {
         if number > 128 {
             return None;
         }
-        let defs = get_field_definitions();
-        Some(defs[number as usize])
+        get_field_definitions()
+            .into_iter()
+            .find(|def| def.number == number)
+    }
+
+    /// Definition of `number` with its format taken from a spec definition
+    ///
+    /// Name and description come from the built-in table when available.
+    pub fn from_spec(number: u8, def: &spec::FieldDefinition) -> Self {
+        let base = Field::from_number(number)
+            .map(|field| field.definition())
+            .unwrap_or(FieldDefinition {
+                number,
+                name: "Unknown",
+                field_type: FieldType::AlphaNumericSpecial,
+                length: FieldLength::LLLVar(999),
+                description: "Unknown field",
+            });
+
+        Self {
+            field_type: def.data_type.into(),
+            length: (def.length_type, def.max_len).into(),
+            ..base
+        }
+    }
+
+    /// Validate a value against this definition's type and length
+    pub fn validate(&self, value: &FieldValue) -> Result<()> {
+        match value {
+            FieldValue::String(s) => {
+                // Check field type constraints
+                match self.field_type {
+                    FieldType::Numeric if !s.chars().all(|c: char| c.is_ascii_digit()) => {
+                        return Err(ISO8583Error::invalid_field_value(
+                            self.number,
+                            "Field must be numeric",
+                        ));
+                    }
+                    FieldType::Alpha
+                        if !s.chars().all(|c: char| c.is_ascii_alphabetic() || c == ' ') =>
+                    {
+                        return Err(ISO8583Error::invalid_field_value(
+                            self.number,
+                            "Field must be alphabetic",
+                        ));
+                    }
+                    FieldType::SignedAmount if parse_signed_amount(s).is_none() => {
+                        return Err(ISO8583Error::invalid_field_value(
+                            self.number,
+                            "Field must be 'C' or 'D' followed by digits",
+                        ));
+                    }
+                    _ => {} // Other types allow more characters
+                }
+            }
+            FieldValue::Binary(_) => {}
+        }
+        let actual = value.len();
+
+        // Check length
+        match self.length {
+            FieldLength::Fixed(len) => {
+                if actual != len {
+                    return Err(ISO8583Error::field_length_mismatch(
+                        self.number,
+                        len,
+                        actual,
+                    ));
+                }
+            }
+            FieldLength::LLVar(max_len) | FieldLength::LLLVar(max_len) => {
+                if actual > max_len {
+                    return Err(ISO8583Error::invalid_field_value(
+                        self.number,
+                        format!("Field exceeds maximum length of {}", max_len),
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check whether a value is longer than this fixed-length field
+    ///
+    /// Always `false` for variable-length fields.
+    pub fn overflows(&self, value: &FieldValue) -> bool {
+        matches!(self.length, FieldLength::Fixed(len) if value.len() > len)
+    }
+
+    /// Generate a placeholder value that passes [`validate`](Self::validate)
+    ///
+    /// Fixed numeric fields are zero-filled, signed amounts are a zero
+    /// credit (`C0...`), other fixed text fields are
+    /// space-filled, fixed binary fields are zero bytes and variable-length
+    /// fields are empty.
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::field::{Field, FieldValue};
+    ///
+    /// let def = Field::ProcessingCode.definition();
+    /// assert_eq!(def.default_value(), FieldValue::from_string("000000"));
+    /// assert!(def.validate(&def.default_value()).is_ok());
+    /// ```
+    pub fn default_value(&self) -> FieldValue {
+        match (self.field_type, self.length) {
+            (FieldType::Binary, FieldLength::Fixed(len)) => FieldValue::from_binary(vec![0; len]),
+            (FieldType::Binary, _) => FieldValue::from_binary(Vec::new()),
+            (FieldType::Numeric, FieldLength::Fixed(len)) => {
+                FieldValue::from_string("0".repeat(len))
+            }
+            (FieldType::SignedAmount, FieldLength::Fixed(len)) => {
+                FieldValue::from_string(format!("C{}", "0".repeat(len.saturating_sub(1))))
+            }
+            (_, FieldLength::Fixed(len)) => FieldValue::from_string(" ".repeat(len)),
+            _ => FieldValue::from_string(String::new()),
+        }
     }
 }
 
+/// Parse an x+n signed amount ("C00000100" / "D00000100") into minor units
+///
+/// 'C' (credit) is positive and 'D' (debit) negative. Returns `None` for a
+/// missing sign, non-digit characters, no digits, or overflow.
+pub fn parse_signed_amount(s: &str) -> Option<i64> {
+    let (sign, digits) = match s.as_bytes().first()? {
+        b'C' => (1, &s[1..]),
+        b'D' => (-1, &s[1..]),
+        _ => return None,
+    };
+
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    digits.parse::<i64>().ok().map(|amount| sign * amount)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_trimmed() {
+        let name_def = Field::CardAcceptorNameLocation.definition();
+        let name = FieldValue::from_string(format!("{:<40}", "  JOE'S DINER  NEW YORK"));
+        assert_eq!(
+            name.trimmed(&name_def).as_string(),
+            Some("  JOE'S DINER  NEW YORK")
+        );
+
+        let stan_def = Field::SystemTraceAuditNumber.definition();
+        assert_eq!(
+            FieldValue::from_string("000042")
+                .trimmed(&stan_def)
+                .as_string(),
+            Some("42")
+        );
+        assert_eq!(
+            FieldValue::from_string("000000")
+                .trimmed(&stan_def)
+                .as_string(),
+            Some("0")
+        );
+
+        // Variable-length and binary values are untouched
+        let pan_def = Field::PrimaryAccountNumber.definition();
+        assert_eq!(
+            FieldValue::from_string("0411 ")
+                .trimmed(&pan_def)
+                .as_string(),
+            Some("0411 ")
+        );
+        let pin_def = Field::PersonalIdentificationNumberData.definition();
+        let pin = FieldValue::from_binary(vec![0x12, 0, 0]);
+        assert_eq!(pin.trimmed(&pin_def), pin);
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_zeroize_field_value() {
+        use zeroize::Zeroize;
+
+        let mut value = FieldValue::from_string("4111111111111111");
+        value.zeroize();
+        assert!(value.is_empty());
+
+        let mut pin = FieldValue::from_binary(vec![0xAB; 8]);
+        pin.zeroize();
+        assert_eq!(pin, FieldValue::Binary(Vec::new()));
+
+        assert!(Field::PersonalIdentificationNumberData.is_sensitive());
+        assert!(!Field::ProcessingCode.is_sensitive());
+        assert!(Field::Track3Data.is_cardholder_data());
+        assert!(!Field::Track3Data.is_sensitive());
+        assert!(!Field::ProcessingCode.is_cardholder_data());
+    }
+
+    #[test]
+    fn test_spec_type_conversions() {
+        let types = [
+            FieldType::Numeric,
+            FieldType::Alpha,
+            FieldType::AlphaNumeric,
+            FieldType::AlphaNumericSpecial,
+            FieldType::Binary,
+            FieldType::Track2,
+            FieldType::Track3,
+            FieldType::SignedAmount,
+        ];
+        for field_type in types {
+            assert_eq!(FieldType::from(DataType::from(field_type)), field_type);
+        }
+
+        for length in [
+            FieldLength::Fixed(12),
+            FieldLength::LLVar(19),
+            FieldLength::LLLVar(999),
+        ] {
+            let spec_length: (LengthType, u16) = length.into();
+            assert_eq!(FieldLength::from(spec_length), length);
+        }
+        assert_eq!(
+            <(LengthType, u16)>::from(FieldLength::LLVar(19)),
+            (LengthType::Llvar, 19)
+        );
+    }
+
+    #[test]
+    fn test_definitions_match_spec_table() {
+        use crate::spec::{Iso1987, IsoSpec};
+
+        let def: spec::FieldDefinition = Field::PrimaryAccountNumber.definition().into();
+        assert_eq!(Some(&def), Iso1987::get_field(2));
+
+        let def: spec::FieldDefinition = Field::TransactionFeeAmount.definition().into();
+        assert_eq!(Some(&def), Iso1987::get_field(28));
+    }
+
+    #[test]
+    fn test_signed_amount() {
+        assert_eq!(parse_signed_amount("C00000100"), Some(100));
+        assert_eq!(parse_signed_amount("D00000100"), Some(-100));
+        assert_eq!(parse_signed_amount("00000100"), None);
+        assert_eq!(parse_signed_amount("X00000100"), None);
+        assert_eq!(parse_signed_amount("C"), None);
+        assert_eq!(parse_signed_amount("C0000-100"), None);
+
+        let def = Field::TransactionFeeAmount.definition();
+        assert_eq!(def.field_type, FieldType::SignedAmount);
+        assert!(def.validate(&FieldValue::from_string("C00000100")).is_ok());
+        assert!(def.validate(&FieldValue::from_string("D00000100")).is_ok());
+        assert!(def.validate(&FieldValue::from_string("000000100")).is_err());
+        assert!(def.validate(&FieldValue::from_string("C0000100")).is_err());
+    }
+
     #[test]
     fn test_field_from_number() {
         let field = Field::from_number(2).unwrap();
@@ -1380,6 +1935,75 @@ mod tests {
         assert_eq!(value.to_string_lossy(), "4111111111111111");
     }
 
+    #[test]
+    fn test_field_value_ref() {
+        let owned = FieldValue::from_string("123456");
+        let borrowed = owned.as_ref();
+        assert!(borrowed.is_borrowed());
+        assert_eq!(borrowed.as_string(), Some("123456"));
+        assert_eq!(borrowed, owned);
+        assert_eq!(FieldValue::from(&borrowed), owned);
+
+        let from_owned = FieldValueRef::from(FieldValue::from_binary(vec![0xAB, 0xCD]));
+        assert!(!from_owned.is_borrowed());
+        assert_eq!(from_owned.to_string(), "abcd");
+        assert_eq!(from_owned.len(), 2);
+        assert_eq!(
+            FieldValue::from(from_owned),
+            FieldValue::from_binary(vec![0xAB, 0xCD])
+        );
+    }
+
+    #[test]
+    fn test_definition_lookup_by_number() {
+        assert_eq!(Field::MessageAuthenticationCode.definition().number, 64);
+        assert_eq!(Field::MessageAuthenticationCode2.definition().number, 128);
+        assert!(FieldDefinition::get(60).is_none());
+        assert!(FieldDefinition::get(129).is_none());
+    }
+
+    #[test]
+    fn test_default_values_validate() {
+        for def in get_field_definitions() {
+            let value = def.default_value();
+            assert!(def.validate(&value).is_ok(), "field {}", def.number);
+        }
+
+        assert_eq!(
+            Field::PersonalIdentificationNumberData
+                .definition()
+                .default_value(),
+            FieldValue::from_binary(vec![0; 8])
+        );
+        assert_eq!(
+            Field::PrimaryAccountNumber.definition().default_value(),
+            FieldValue::from_string("")
+        );
+    }
+
+    #[test]
+    fn test_field_ordering() {
+        assert!(Field::PrimaryAccountNumber < Field::SystemTraceAuditNumber);
+        assert!(Field::SystemTraceAuditNumber < Field::TertiaryBitmap);
+        assert!(Field::TertiaryBitmap < Field::MessageAuthenticationCode2);
+
+        let mut fields = std::collections::BTreeMap::new();
+        fields.insert(Field::MessageAuthenticationCode2, 128);
+        fields.insert(Field::TertiaryBitmap, 65);
+        fields.insert(Field::SystemTraceAuditNumber, 11);
+        fields.insert(Field::PrimaryAccountNumber, 2);
+        assert_eq!(
+            fields.into_values().collect::<Vec<_>>(),
+            vec![2, 11, 65, 128]
+        );
+
+        // Declaration order must match field numbers for the derive to hold
+        let all = Field::all();
+        assert!(all
+            .windows(2)
+            .all(|w| w[0] < w[1] && w[0].number() < w[1].number()));
+    }
+
     #[test]
     fn test_invalid_field_number() {
         assert!(Field::from_number(0).is_err());
@@ -75,6 +75,8 @@ macro_rules! define_field {
 ///     4,
 ///     12
 /// );
+///
+/// assert_eq!(Field4Amount::from_u64(10000).unwrap().as_bytes(), b"000000010000");
 /// ```
 #[macro_export]
 macro_rules! define_numeric_field {
@@ -121,6 +123,23 @@ macro_rules! define_numeric_field {
                 &self.0
             }
 
+            /// Create from an integer, zero-padded to the field width
+            ///
+            /// Fails if the value needs more digits than the field holds.
+            #[inline]
+            pub fn from_u64(value: u64) -> Result<Self, &'static str> {
+                let mut data = [b'0'; $len];
+                let mut rest = value;
+                for digit in data.iter_mut().rev() {
+                    *digit = b'0' + (rest % 10) as u8;
+                    rest /= 10;
+                }
+                if rest != 0 {
+                    return Err("Value has too many digits for field");
+                }
+                Ok(Self(data))
+            }
+
             /// Convert to u64 (if fits)
             #[inline]
             pub fn to_u64(&self) -> Result<u64, &'static str> {
@@ -284,6 +303,27 @@ mod tests {
         assert_eq!(amount.to_u64().unwrap(), 10000);
     }
 
+    #[test]
+    fn test_numeric_field_from_u64() {
+        let amount = Field4Amount::from_u64(10000).unwrap();
+        assert_eq!(amount.as_bytes(), b"000000010000");
+        assert_eq!(amount.to_u64().unwrap(), 10000);
+        assert_eq!(
+            Field4Amount::from_u64(0).unwrap().as_bytes(),
+            b"000000000000"
+        );
+
+        // Largest value that fits, and the first that needs N+1 digits
+        assert_eq!(
+            Field4Amount::from_u64(999_999_999_999).unwrap().as_bytes(),
+            b"999999999999"
+        );
+        assert!(Field4Amount::from_u64(1_000_000_000_000).is_err());
+        assert!(Field11Stan::from_u64(999_999).is_ok());
+        assert!(Field11Stan::from_u64(1_000_000).is_err());
+        assert!(Field4Amount::from_u64(u64::MAX).is_err());
+    }
+
     #[test]
     fn test_numeric_field_validation() {
         // Wrong length
@@ -0,0 +1,254 @@
+//! Message length indicator (MLI) and trailer framing
+//!
+//! On TCP links each ISO 8583 message is preceded by a length header so the
+//! receiver knows where one message ends and the next begins. The header
+//! carries the length of the message body only, not of the header itself.
+//! Legacy serial links instead end each message with a [`Trailer`].
+
+use crate::error::{ISO8583Error, Result};
+
+/// Largest frame the stream readers accept by default (1 MiB)
+///
+/// Length headers come from the peer, so readers refuse to buffer more than
+/// this rather than trusting e.g. a 4 GiB [`MliType::Binary4`] header.
+pub const DEFAULT_MAX_FRAME_LEN: usize = 1 << 20;
+
+/// Message length indicator format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MliType {
+    /// No length header; the message occupies the whole buffer or stream
+    None,
+    /// 2-byte big-endian binary length (most common)
+    Binary2,
+    /// 4-byte big-endian binary length
+    Binary4,
+    /// 4 ASCII decimal digits, e.g. `"0123"`
+    Ascii4,
+}
+
+impl MliType {
+    /// Size of the length header in bytes
+    pub fn header_len(&self) -> usize {
+        match self {
+            MliType::None => 0,
+            MliType::Binary2 => 2,
+            MliType::Binary4 | MliType::Ascii4 => 4,
+        }
+    }
+
+    /// Largest body length the header can express
+    pub fn max_len(&self) -> usize {
+        match self {
+            MliType::None => usize::MAX,
+            MliType::Binary2 => u16::MAX as usize,
+            MliType::Binary4 => u32::MAX as usize,
+            MliType::Ascii4 => 9999,
+        }
+    }
+
+    /// Encode a body length as a header
+    ///
+    /// Returns an empty header for [`MliType::None`].
+    pub fn encode(&self, len: usize) -> Result<Vec<u8>> {
+        if len > self.max_len() {
+            return Err(ISO8583Error::EncodingError(format!(
+                "Message length {} exceeds {:?} maximum {}",
+                len,
+                self,
+                self.max_len()
+            )));
+        }
+
+        Ok(match self {
+            MliType::None => Vec::new(),
+            MliType::Binary2 => (len as u16).to_be_bytes().to_vec(),
+            MliType::Binary4 => (len as u32).to_be_bytes().to_vec(),
+            MliType::Ascii4 => format!("{:04}", len).into_bytes(),
+        })
+    }
+
+    /// Decode a body length from the start of `bytes`
+    ///
+    /// Only the first [`header_len`](Self::header_len) bytes are read.
+    /// [`MliType::None`] has no header and is an error here.
+    pub fn decode(&self, bytes: &[u8]) -> Result<usize> {
+        let header_len = self.header_len();
+        if bytes.len() < header_len {
+            return Err(ISO8583Error::message_too_short(header_len, bytes.len()));
+        }
+        let header = &bytes[..header_len];
+
+        match self {
+            MliType::None => Err(ISO8583Error::ParseError(
+                "MliType::None has no length header".to_string(),
+            )),
+            MliType::Binary2 => Ok(u16::from_be_bytes([header[0], header[1]]) as usize),
+            MliType::Binary4 => {
+                Ok(u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize)
+            }
+            MliType::Ascii4 => {
+                if !header.iter().all(u8::is_ascii_digit) {
+                    return Err(ISO8583Error::EncodingError(format!(
+                        "Invalid ASCII length header: {:02X?}",
+                        header
+                    )));
+                }
+                Ok(header
+                    .iter()
+                    .fold(0, |len, &b| len * 10 + (b - b'0') as usize))
+            }
+        }
+    }
+}
+
+/// End-of-text control character
+pub const ETX: u8 = 0x03;
+
+/// Trailer delimiting messages on links without a length header
+///
+/// Older serial and async terminal links mark the end of each message
+/// instead of announcing its length. The delimiter must not occur inside
+/// the body, so these links carry text messages (e.g. with an ASCII hex
+/// bitmap).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Trailer {
+    /// No trailer; the message occupies the whole buffer or stream
+    None,
+    /// A single ETX (0x03) byte
+    Etx,
+    /// ETX followed by an XOR longitudinal redundancy check over the body
+    EtxLrc,
+    /// Carriage return and line feed
+    CrLf,
+}
+
+impl Trailer {
+    /// Bytes marking the end of the body
+    pub fn delimiter(&self) -> &'static [u8] {
+        match self {
+            Trailer::None => b"",
+            Trailer::Etx | Trailer::EtxLrc => &[ETX],
+            Trailer::CrLf => b"\r\n",
+        }
+    }
+
+    /// Size of the trailer in bytes
+    pub fn len(&self) -> usize {
+        match self {
+            Trailer::EtxLrc => 2,
+            other => other.delimiter().len(),
+        }
+    }
+
+    /// Check if the trailer is empty ([`Trailer::None`])
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Encode the trailer for `body`
+    ///
+    /// Fails if the body contains the delimiter, since the receiver would
+    /// cut the message short there.
+    pub fn encode(&self, body: &[u8]) -> Result<Vec<u8>> {
+        let delimiter = self.delimiter();
+        if !delimiter.is_empty() && body.windows(delimiter.len()).any(|w| w == delimiter) {
+            return Err(ISO8583Error::EncodingError(format!(
+                "Message body contains the {:?} delimiter",
+                self
+            )));
+        }
+
+        let mut trailer = delimiter.to_vec();
+        if *self == Trailer::EtxLrc {
+            trailer.push(lrc(body));
+        }
+        Ok(trailer)
+    }
+
+    /// Check the trailer at the end of `frame` and return the body before it
+    ///
+    /// For [`Trailer::EtxLrc`] the LRC must match the body.
+    pub fn strip<'a>(&self, frame: &'a [u8]) -> Result<&'a [u8]> {
+        let trailer_len = self.len();
+        if frame.len() < trailer_len {
+            return Err(ISO8583Error::message_too_short(trailer_len, frame.len()));
+        }
+
+        let (body, trailer) = frame.split_at(frame.len() - trailer_len);
+        let delimiter = self.delimiter();
+        if &trailer[..delimiter.len()] != delimiter {
+            return Err(ISO8583Error::ParseError(format!(
+                "Message does not end with the {:?} trailer",
+                self
+            )));
+        }
+        if *self == Trailer::EtxLrc && trailer[1] != lrc(body) {
+            return Err(ISO8583Error::ParseError(format!(
+                "LRC mismatch: expected {:02X}, got {:02X}",
+                lrc(body),
+                trailer[1]
+            )));
+        }
+
+        Ok(body)
+    }
+}
+
+/// XOR longitudinal redundancy check over `data`
+pub fn lrc(data: &[u8]) -> u8 {
+    data.iter().fold(0, |lrc, &b| lrc ^ b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode() {
+        for (mli, len) in [
+            (MliType::Binary2, 300),
+            (MliType::Binary4, 70_000),
+            (MliType::Ascii4, 123),
+        ] {
+            let header = mli.encode(len).unwrap();
+            assert_eq!(header.len(), mli.header_len());
+            assert_eq!(mli.decode(&header).unwrap(), len);
+        }
+
+        assert_eq!(MliType::Binary2.encode(300).unwrap(), vec![0x01, 0x2C]);
+        assert_eq!(MliType::Ascii4.encode(123).unwrap(), b"0123");
+        assert!(MliType::None.encode(10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_limits() {
+        assert!(MliType::Ascii4.encode(10_000).is_err());
+        assert!(MliType::Binary2.encode(65_536).is_err());
+        assert!(MliType::Binary2.decode(&[0x01]).is_err());
+        assert!(MliType::Ascii4.decode(b"01A3").is_err());
+        assert!(MliType::None.decode(b"").is_err());
+    }
+
+    #[test]
+    fn test_trailers() {
+        let body = b"0800ABC";
+        assert_eq!(lrc(body), b'0' ^ b'8' ^ b'0' ^ b'0' ^ b'A' ^ b'B' ^ b'C');
+
+        for trailer in [Trailer::None, Trailer::Etx, Trailer::EtxLrc, Trailer::CrLf] {
+            let mut frame = body.to_vec();
+            frame.extend(trailer.encode(body).unwrap());
+            assert_eq!(frame.len(), body.len() + trailer.len());
+            assert_eq!(trailer.strip(&frame).unwrap(), body);
+        }
+
+        assert_eq!(Trailer::EtxLrc.encode(body).unwrap(), vec![ETX, lrc(body)]);
+        assert_eq!(Trailer::CrLf.encode(body).unwrap(), b"\r\n");
+
+        // Corrupted LRC, missing trailer, delimiter inside the body
+        assert!(Trailer::EtxLrc.strip(&[b'A', ETX, b'B']).is_err());
+        assert!(Trailer::Etx.strip(b"0800").is_err());
+        assert!(Trailer::CrLf.strip(b"\n").is_err());
+        assert!(Trailer::Etx.encode(&[b'A', ETX]).is_err());
+        assert!(Trailer::CrLf.encode(b"A\r\nB").is_err());
+    }
+}
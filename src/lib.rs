@@ -51,6 +51,10 @@
 //! - `alloc`: Heap allocation (Vec, String)
 //! - `simd`: SIMD-accelerated bitmap operations
 //! - `serde`: JSON serialization support
+//! - `zeroize`: Zero PAN, track data and PIN block buffers when messages are dropped
+//! - `tokio`: Async reading from `tokio::io::AsyncRead`
+//! - `testing`: Test helpers such as `ISO8583Message::test_builder`
+//! - `test-vectors`: Canonical example messages for downstream tests
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
@@ -58,6 +62,9 @@
 #![warn(rust_2018_idioms)]
 #![cfg_attr(not(feature = "simd"), forbid(unsafe_code))]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 // Core modules
 pub mod fields;
 pub mod spec;
@@ -82,27 +89,68 @@ pub mod mti;
 #[cfg(feature = "std")]
 pub mod encoding;
 
+#[cfg(feature = "std")]
+pub mod framing;
+
+#[cfg(feature = "std")]
+pub mod emv;
+
+#[cfg(feature = "std")]
+pub mod subfield;
+
 #[cfg(feature = "std")]
 pub mod validation;
 
+#[cfg(feature = "serde")]
+pub mod schema;
+
 #[cfg(feature = "std")]
 pub mod response_code;
 
 #[cfg(feature = "std")]
 pub mod processing_code;
 
+#[cfg(feature = "std")]
+pub mod additional_amounts;
+
+#[cfg(feature = "std")]
+pub mod pos_entry_mode;
+
+#[cfg(feature = "std")]
+pub mod pos_condition_code;
+
+#[cfg(feature = "std")]
+pub mod card_acceptor;
+
 #[cfg(feature = "std")]
 pub mod utils;
 
+#[cfg(feature = "std")]
+pub mod money;
+
+#[cfg(feature = "std")]
+pub mod stan;
+
 #[cfg(feature = "std")]
 pub mod message;
 
+#[cfg(feature = "std")]
+pub mod network;
+
+#[cfg(feature = "std")]
+pub mod card_presence;
+
+#[cfg(feature = "test-vectors")]
+pub mod test_vectors;
+
 // Re-exports for convenience
 pub use fields::IsoField;
-pub use spec::{DataType, FieldDefinition, Iso1987, IsoSpec, LengthType};
+pub use spec::{
+    DataType, DefinedFields, FieldDefinition, Iso1987, IsoSpec, LengthType, RuntimeSpec,
+};
 
 #[cfg(feature = "alloc")]
-pub use bitmap::Bitmap;
+pub use bitmap::{Bitmap, BitmapDecodeError, BitmapEncoding, BitmapGrowth, SecondaryBitmapPolicy};
 
 #[cfg(feature = "std")]
 pub use error::{ISO8583Error, Result};
@@ -111,16 +159,45 @@ pub use error::{ISO8583Error, Result};
 pub use mti::{MessageClass, MessageFunction, MessageOrigin, MessageType};
 
 #[cfg(feature = "std")]
-pub use message::{ISO8583Message, MessageBuilder};
+pub use framing::{MliType, Trailer};
+
+#[cfg(feature = "std")]
+pub use message::{FieldLookup, ISO8583Message, MessageBuilder, MessageView, ParseOptions};
+
+#[cfg(feature = "std")]
+pub use response_code::{ResponseCategory, ResponseCode, ResponseCodeRegistry};
+
+#[cfg(feature = "std")]
+pub use processing_code::{
+    AccountType, OtherCode, ProcessingCode, TransactionCategory, TransactionType,
+};
+
+#[cfg(feature = "std")]
+pub use additional_amounts::{AdditionalAmount, AmountType};
+
+#[cfg(feature = "std")]
+pub use pos_entry_mode::{PanEntryMode, PinCapability, PosEntryMode};
+
+#[cfg(feature = "std")]
+pub use pos_condition_code::PosConditionCode;
+
+#[cfg(feature = "std")]
+pub use card_acceptor::CardAcceptorNameLocation;
+
+#[cfg(feature = "std")]
+pub use validation::{CrossFieldRule, MtiProfile, Validator};
+
+#[cfg(feature = "serde")]
+pub use schema::SpecSchema;
 
 #[cfg(feature = "std")]
-pub use response_code::{ResponseCategory, ResponseCode};
+pub use stan::{SequentialStan, StanProvider, StanStatus, StanTracker};
 
 #[cfg(feature = "std")]
-pub use processing_code::{AccountType, ProcessingCode, TransactionType};
+pub use network::NetworkMessage;
 
 #[cfg(feature = "std")]
-pub use validation::Validator;
+pub use card_presence::{CardPresenceRule, DefaultCardPresence};
 
 // Legacy field enum (std only for compatibility)
 #[cfg(feature = "std")]
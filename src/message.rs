@@ -3,11 +3,272 @@
 //! This module provides the main message type and operations for
 //! parsing and generating ISO 8583 messages.
 
-use crate::bitmap::Bitmap;
+use crate::additional_amounts::{AdditionalAmount, AmountType};
+use crate::bitmap::{Bitmap, BitmapDecodeError, BitmapEncoding, SecondaryBitmapPolicy};
+use crate::card_acceptor::CardAcceptorNameLocation;
+use crate::card_presence::{CardPresenceRule, DefaultCardPresence};
+use crate::emv::{self, EmvCryptogramData};
+use crate::encoding::{self, BinaryEncoding, Encoding};
 use crate::error::{ISO8583Error, Result};
-use crate::field::{Field, FieldDefinition, FieldLength, FieldType, FieldValue};
+use crate::field::{
+    Field, FieldDefinition, FieldLength, FieldType, FieldValue, FieldValueRef, OverflowPolicy,
+};
+use crate::framing::{MliType, Trailer, DEFAULT_MAX_FRAME_LEN};
 use crate::mti::MessageType;
-use std::collections::HashMap;
+use crate::pos_condition_code::PosConditionCode;
+use crate::pos_entry_mode::PosEntryMode;
+use crate::processing_code::{AccountType, ProcessingCode};
+use crate::response_code::ResponseCode;
+use crate::spec::RuntimeSpec;
+use crate::stan::{StanProvider, MAX_STAN};
+use crate::subfield;
+use crate::validation::MtiProfile;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::io::{self, Read as _};
+
+/// Magic prefix and format version of the journal encoding
+const JOURNAL_MAGIC: &[u8; 3] = b"I8J";
+const JOURNAL_VERSION: u8 = 1;
+
+/// Journal record kinds
+const JOURNAL_STRING: u8 = 0;
+const JOURNAL_BINARY: u8 = 1;
+const JOURNAL_UNKNOWN: u8 = 2;
+
+/// Request fields echoed into a partial approval response
+const PARTIAL_APPROVAL_ECHO: &[Field] = &[
+    Field::PrimaryAccountNumber,
+    Field::ProcessingCode,
+    Field::TransmissionDateTime,
+    Field::SystemTraceAuditNumber,
+    Field::LocalTransactionTime,
+    Field::LocalTransactionDate,
+    Field::MerchantType,
+    Field::AcquiringInstitutionIdentificationCode,
+    Field::ForwardingInstitutionIdentificationCode,
+    Field::RetrievalReferenceNumber,
+    Field::CardAcceptorTerminalIdentification,
+    Field::CardAcceptorIdentificationCode,
+    Field::CurrencyCodeTransaction,
+];
+
+/// Fail with [`ISO8583Error::FrameTooLarge`] if `len` exceeds `max_len`
+fn check_frame_len(len: usize, max_len: usize) -> Result<()> {
+    if len > max_len {
+        return Err(ISO8583Error::FrameTooLarge { len, max: max_len });
+    }
+    Ok(())
+}
+
+/// Field definition lookup for a custom specification
+///
+/// Returning `None` falls back to the built-in 1987 table.
+pub type FieldLookup = fn(u8) -> Option<FieldDefinition>;
+
+/// Options controlling how messages are parsed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Capture fields without a definition as opaque bytes instead of failing
+    pub lenient: bool,
+    /// Length format assumed for undefined fields in lenient mode
+    pub unknown_field_length: FieldLength,
+    /// Custom field lookups indexed by MTI version digit
+    pub version_specs: [Option<FieldLookup>; 10],
+    /// Runtime field table replacing the built-in 1987 table
+    pub spec: Option<RuntimeSpec>,
+    /// Wire representation of the bitmap region
+    pub bitmap_encoding: BitmapEncoding,
+    /// Wire representation of the MTI (4 ASCII/EBCDIC bytes or 2 BCD bytes)
+    pub mti_encoding: Encoding,
+    /// Take field 38's length from field 27 instead of the fixed 6
+    pub auth_id_length_from_field_27: bool,
+    /// When the secondary bitmap block is written (encoding only)
+    pub secondary_bitmap: SecondaryBitmapPolicy,
+    /// Require the fields to end exactly at the end of the buffer
+    pub check_desync: bool,
+    /// Uppercase alpha (`a`) fields when encoding
+    pub uppercase_alpha: bool,
+    /// Overflow policy overrides, one slot per [`FieldType`] variant
+    overflow_policies: [Option<OverflowPolicy>; 8],
+}
+
+impl ParseOptions {
+    /// Strict parsing (what [`ISO8583Message::from_bytes`] uses)
+    pub fn new() -> Self {
+        Self {
+            lenient: false,
+            unknown_field_length: FieldLength::LLLVar(999),
+            version_specs: [None; 10],
+            spec: None,
+            bitmap_encoding: BitmapEncoding::Binary,
+            mti_encoding: Encoding::ASCII,
+            auth_id_length_from_field_27: false,
+            secondary_bitmap: SecondaryBitmapPolicy::Auto,
+            check_desync: false,
+            uppercase_alpha: false,
+            overflow_policies: [None; 8],
+        }
+    }
+
+    /// Lenient parsing, assuming LLLVAR(999) for undefined fields
+    pub fn lenient() -> Self {
+        Self {
+            lenient: true,
+            ..Self::new()
+        }
+    }
+
+    /// Set the length format assumed for undefined fields
+    pub fn unknown_field_length(mut self, length: FieldLength) -> Self {
+        self.unknown_field_length = length;
+        self
+    }
+
+    /// Register a custom field lookup for messages with the given MTI version digit
+    ///
+    /// Useful for private/national formats (versions 8 and 9). Fields the
+    /// lookup does not define are resolved against the built-in 1987 table.
+    /// Versions above 9 are ignored.
+    ///
+    /// Messages parsed under a lookup carry its definitions as their
+    /// [`runtime_spec`](ISO8583Message::runtime_spec), so they re-encode in
+    /// the same format.
+    pub fn version_spec(mut self, version: u8, lookup: FieldLookup) -> Self {
+        if let Some(slot) = self.version_specs.get_mut(version as usize) {
+            *slot = Some(lookup);
+        }
+        self
+    }
+
+    /// Parse against a [`RuntimeSpec`] instead of the built-in 1987 table
+    ///
+    /// Parsed messages keep the spec, so structured fields can be read
+    /// with [`ISO8583Message::get_subfield`]. Version lookups registered
+    /// with [`version_spec`](Self::version_spec) still take precedence.
+    pub fn runtime_spec(mut self, spec: RuntimeSpec) -> Self {
+        self.spec = Some(spec);
+        self
+    }
+
+    /// Set how the bitmap region is encoded on the wire
+    pub fn bitmap_encoding(mut self, encoding: BitmapEncoding) -> Self {
+        self.bitmap_encoding = encoding;
+        self
+    }
+
+    /// Set how the MTI is encoded on the wire
+    pub fn mti_encoding(mut self, encoding: Encoding) -> Self {
+        self.mti_encoding = encoding;
+        self
+    }
+
+    /// Treat field 38 (auth ID) as variable length, sized by field 27
+    ///
+    /// When parsing, field 38 is read with the length given in field 27.
+    /// When encoding with
+    /// [`to_bytes_with_options`](ISO8583Message::to_bytes_with_options),
+    /// field 38 is written unpadded and field 27 is set to its length.
+    pub fn auth_id_length_from_field_27(mut self, enabled: bool) -> Self {
+        self.auth_id_length_from_field_27 = enabled;
+        self
+    }
+
+    /// Set when the secondary bitmap block is written by
+    /// [`to_bytes_with_options`](ISO8583Message::to_bytes_with_options)
+    pub fn secondary_bitmap(mut self, policy: SecondaryBitmapPolicy) -> Self {
+        self.secondary_bitmap = policy;
+        self
+    }
+
+    /// Check that the parsed fields line up with the buffer
+    ///
+    /// A field parsed with a valid but wrong length shifts every following
+    /// field, which then parse as garbage or fail far from the real cause.
+    /// With this check, leftover bytes after the last field, or a field that
+    /// fails to parse, produce an [`ISO8583Error::Desync`] naming the first
+    /// field whose content does not match its definition (the likely point
+    /// of desynchronization). The buffer must hold exactly one message, with
+    /// any length header already removed.
+    pub fn check_desync(mut self, enabled: bool) -> Self {
+        self.check_desync = enabled;
+        self
+    }
+
+    /// Uppercase alpha (`a`) fields written by
+    /// [`to_bytes_with_options`](ISO8583Message::to_bytes_with_options)
+    ///
+    /// Some specs only allow capital letters in alpha fields. The stored
+    /// values are left untouched.
+    pub fn uppercase_alpha(mut self, enabled: bool) -> Self {
+        self.uppercase_alpha = enabled;
+        self
+    }
+
+    /// Override the overflow policy for fixed-length fields of a type
+    ///
+    /// Applies when encoding with these options; see [`OverflowPolicy`].
+    pub fn overflow_policy(mut self, field_type: FieldType, policy: OverflowPolicy) -> Self {
+        self.overflow_policies[field_type as usize] = Some(policy);
+        self
+    }
+
+    /// Effective overflow policy for fixed-length fields of a type
+    ///
+    /// The override set with [`overflow_policy`](Self::overflow_policy), or
+    /// the type's [default](FieldType::default_overflow_policy).
+    pub fn effective_overflow_policy(&self, field_type: FieldType) -> OverflowPolicy {
+        self.overflow_policies[field_type as usize]
+            .unwrap_or_else(|| field_type.default_overflow_policy())
+    }
+
+    /// Number of bytes the MTI occupies
+    fn mti_len(&self) -> usize {
+        match self.mti_encoding {
+            Encoding::BCD => 2,
+            Encoding::ASCII | Encoding::EBCDIC => 4,
+        }
+    }
+
+    /// Runtime spec a message parsed with the given MTI version keeps
+    ///
+    /// The version lookup's definitions are folded into the spec (or the
+    /// 1987 table), so encoding matches what was parsed.
+    fn resolved_spec(&self, version: u8) -> Option<RuntimeSpec> {
+        let Some(lookup) = self.version_specs.get(version as usize).copied().flatten() else {
+            return self.spec;
+        };
+
+        let mut spec = self.spec.unwrap_or_default();
+        for field_num in 2..=128 {
+            if let Some(def) = lookup(field_num) {
+                spec.override_field(field_num, def.into());
+            }
+        }
+        Some(spec)
+    }
+
+    /// Resolve a field definition for the given MTI version
+    fn definition_for(&self, version: u8, field_num: u8) -> Option<FieldDefinition> {
+        self.version_specs
+            .get(version as usize)
+            .copied()
+            .flatten()
+            .and_then(|lookup| lookup(field_num))
+            .or_else(|| match &self.spec {
+                Some(spec) => spec
+                    .get_field(field_num)
+                    .map(|def| FieldDefinition::from_spec(field_num, def)),
+                None => FieldDefinition::get(field_num),
+            })
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 /// ISO 8583 Message
 #[derive(Debug, Clone, PartialEq)]
@@ -16,8 +277,19 @@ pub struct ISO8583Message {
     pub mti: MessageType,
     /// Field values (keyed by field number)
     fields: HashMap<u8, FieldValue>,
+    /// Raw wire bytes of undefined fields captured in lenient mode
+    unknown_fields: HashMap<u8, Vec<u8>>,
     /// Bitmap indicating present fields
     bitmap: Bitmap,
+    /// Runtime field table the message was parsed with, if any
+    spec: Option<Box<RuntimeSpec>>,
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for ISO8583Message {
+    fn drop(&mut self) {
+        self.zeroize_sensitive();
+    }
 }
 
 impl ISO8583Message {
@@ -26,7 +298,9 @@ impl ISO8583Message {
         Self {
             mti,
             fields: HashMap::new(),
+            unknown_fields: HashMap::new(),
             bitmap: Bitmap::new(),
+            spec: None,
         }
     }
 
@@ -37,67 +311,421 @@ impl ISO8583Message {
     /// [MTI (4 bytes)][Bitmap (8/16/24 bytes)][Fields...]
     /// ```
     pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
-        if bytes.len() < 12 {
-            // Minimum: 4 (MTI) + 8 (bitmap)
-            return Err(ISO8583Error::message_too_short(12, bytes.len()));
+        Self::from_bytes_with_options(bytes, &ParseOptions::default())
+    }
+
+    /// Parse message from bytes with explicit [`ParseOptions`]
+    ///
+    /// In lenient mode, fields the crate has no definition for are read using
+    /// `options.unknown_field_length` and kept verbatim (length indicator
+    /// included) in [`ISO8583Message::unknown_fields`], so the rest of the
+    /// message still parses and `to_bytes` reproduces them unchanged.
+    pub fn from_bytes_with_options(bytes: &[u8], options: &ParseOptions) -> Result<Self> {
+        MessageView::parse_with_options(bytes, options).map(MessageView::into_message)
+    }
+
+    /// Parse only the MTI and bitmap, e.g. for a routing decision
+    ///
+    /// Returns the MTI, the bitmap (with any secondary and tertiary blocks)
+    /// and the offset of the first field. No field is read, so this is much
+    /// cheaper than [`from_bytes`](Self::from_bytes).
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::*;
+    ///
+    /// let bytes = ISO8583Message::new(MessageType::NETWORK_MANAGEMENT_REQUEST).to_bytes();
+    /// let (mti, bitmap, offset) = ISO8583Message::parse_header(&bytes)?;
+    /// assert_eq!(mti, MessageType::NETWORK_MANAGEMENT_REQUEST);
+    /// assert!(bitmap.is_empty());
+    /// assert_eq!(offset, 12);
+    /// # Ok::<(), ISO8583Error>(())
+    /// ```
+    pub fn parse_header(bytes: &[u8]) -> Result<(MessageType, Bitmap, usize)> {
+        Self::parse_header_with_options(bytes, &ParseOptions::new())
+    }
+
+    /// Parse only the MTI and bitmap, using the MTI and bitmap encodings
+    /// from `options`
+    pub fn parse_header_with_options(
+        bytes: &[u8],
+        options: &ParseOptions,
+    ) -> Result<(MessageType, Bitmap, usize)> {
+        let mti_len = options.mti_len();
+        if bytes.len() < mti_len + 8 {
+            // Minimum: MTI + 8 (bitmap)
+            return Err(ISO8583Error::message_too_short(mti_len + 8, bytes.len()));
         }
 
-        let mut offset = 0;
+        // MTI (4 ASCII/EBCDIC bytes or 2 BCD bytes)
+        let mti = match options.mti_encoding {
+            Encoding::ASCII => MessageType::from_bytes(&bytes[..4])?,
+            Encoding::BCD => MessageType::from_bcd_bytes(&[bytes[0], bytes[1]])?,
+            Encoding::EBCDIC => encoding::decode_ebcdic(&bytes[..4])
+                .map_err(|e| ISO8583Error::InvalidMTI(e.to_string()))?
+                .parse()?,
+        };
 
-        // 1. Parse MTI (first 4 bytes)
-        let mti = MessageType::from_bytes(&bytes[offset..offset + 4])?;
-        offset += 4;
+        // Bitmap(s); the bitmap reports how many bytes it occupies
+        let (bitmap, bitmap_len) = Bitmap::decode_counted(
+            &bytes[mti_len..],
+            options.bitmap_encoding,
+        )
+        .map_err(|e| match e {
+            BitmapDecodeError::MissingSecondary => ISO8583Error::MissingSecondaryBitmap,
+            BitmapDecodeError::Invalid(reason) => ISO8583Error::InvalidBitmap(reason.to_string()),
+        })?;
 
-        // 2. Parse primary bitmap (8 bytes = 16 hex chars)
-        let bitmap_hex = hex::encode(&bytes[offset..offset + 8]);
-        let mut bitmap = Bitmap::from_hex(&bitmap_hex)?;
-        offset += 8;
+        Ok((mti, bitmap, mti_len + bitmap_len))
+    }
 
-        // 3. Check for secondary bitmap (if field 1 is set)
-        if bitmap.is_set(1) {
-            if bytes.len() < offset + 8 {
-                return Err(ISO8583Error::message_too_short(offset + 8, bytes.len()));
-            }
-            let secondary_hex = hex::encode(&bytes[offset..offset + 8]);
-            let secondary_bitmap = Bitmap::from_hex(&secondary_hex)?;
+    /// Parse message from bytes, reporting bytes left after the last field
+    ///
+    /// [`from_bytes`](Self::from_bytes) stops once every field in the bitmap
+    /// has been read and ignores anything after it. Leftover bytes usually
+    /// mean the length header and bitmap disagree. This returns the message
+    /// with the number of trailing bytes. With `strict` set, any trailing
+    /// bytes are an [`ISO8583Error::TrailingBytes`] error instead.
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::*;
+    ///
+    /// let mut bytes = ISO8583Message::new(MessageType::NETWORK_MANAGEMENT_REQUEST).to_bytes();
+    /// bytes.extend_from_slice(b"XY");
+    ///
+    /// let (_, trailing) = ISO8583Message::from_bytes_checked(&bytes, false)?;
+    /// assert_eq!(trailing, 2);
+    /// assert_eq!(
+    ///     ISO8583Message::from_bytes_checked(&bytes, true),
+    ///     Err(ISO8583Error::TrailingBytes(2))
+    /// );
+    /// # Ok::<(), ISO8583Error>(())
+    /// ```
+    pub fn from_bytes_checked(bytes: &[u8], strict: bool) -> Result<(Self, usize)> {
+        let (view, consumed) = MessageView::parse_counted(bytes, &ParseOptions::default())?;
+        let trailing = bytes.len() - consumed;
 
-            // Merge secondary bitmap into main bitmap
-            for field_num in 65..=128 {
-                if secondary_bitmap.is_set(field_num) {
-                    bitmap.set(field_num)?;
-                }
-            }
-            offset += 8;
+        if strict && trailing > 0 {
+            return Err(ISO8583Error::TrailingBytes(trailing));
+        }
+        Ok((view.into_message(), trailing))
+    }
+
+    /// Parse a message whose exact length the transport already knows
+    ///
+    /// For payloads framed by something other than an MLI, such as one
+    /// message per queue entry. The fields must end exactly at the end of
+    /// `bytes`: leftover bytes are an [`ISO8583Error::TrailingBytes`] error
+    /// and a buffer that ends inside a field fails with
+    /// [`ISO8583Error::MessageTooShort`] (or
+    /// [`ISO8583Error::FieldLengthMismatch`] for a cut fixed-length field),
+    /// so a field desync surfaces here rather than as garbage values.
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::*;
+    ///
+    /// let bytes = ISO8583Message::new(MessageType::NETWORK_MANAGEMENT_REQUEST).to_bytes();
+    /// assert!(ISO8583Message::from_bytes_exact(&bytes).is_ok());
+    ///
+    /// let mut padded = bytes.clone();
+    /// padded.push(b'0');
+    /// assert_eq!(
+    ///     ISO8583Message::from_bytes_exact(&padded),
+    ///     Err(ISO8583Error::TrailingBytes(1))
+    /// );
+    /// # Ok::<(), ISO8583Error>(())
+    /// ```
+    pub fn from_bytes_exact(bytes: &[u8]) -> Result<Self> {
+        Self::from_bytes_checked(bytes, true).map(|(msg, _)| msg)
+    }
+
+    /// Parse message from a hex string (e.g. from logs)
+    ///
+    /// Whitespace anywhere in the string is ignored, so pretty-printed
+    /// dumps can be replayed directly.
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::*;
+    ///
+    /// let hex = "30383030 8220000000000000 0400000000000000 30313135313230303030303030303031333031";
+    /// let message = ISO8583Message::from_hex(hex)?;
+    /// assert_eq!(message.mti, MessageType::NETWORK_MANAGEMENT_REQUEST);
+    /// assert_eq!(message.to_hex().len(), hex.split_whitespace().map(str::len).sum::<usize>());
+    /// # Ok::<(), ISO8583Error>(())
+    /// ```
+    pub fn from_hex(s: &str) -> Result<Self> {
+        let compact: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+
+        if compact.len() % 2 != 0 {
+            return Err(ISO8583Error::InvalidEncoding(format!(
+                "Hex string has odd length ({} digits)",
+                compact.len()
+            )));
+        }
+
+        let bytes = hex::decode(&compact)
+            .map_err(|e| ISO8583Error::InvalidEncoding(format!("Invalid hex: {}", e)))?;
+
+        Self::from_bytes(&bytes)
+    }
+
+    /// Generate message bytes as a lowercase hex string
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.to_bytes())
+    }
+
+    /// Encode the message in the compact journal format
+    ///
+    /// This is an internal archival format, not ISO 8583 wire format. It
+    /// keeps the MTI (including its version), the bitmap as held, field
+    /// value kinds and undefined fields captured in lenient mode, so
+    /// [`from_journal_bytes`](Self::from_journal_bytes) reloads the message
+    /// exactly without consulting any field definitions.
+    ///
+    /// # Format
+    /// ```text
+    /// ["I8J"][version: u8][MTI (4 bytes)][bitmap len: u8][bitmap]
+    /// then per field, ascending: [field: u8][kind: u8][len: u16 BE][bytes]
+    /// ```
+    /// `kind` is 0 for string values, 1 for binary values and 2 for raw
+    /// undefined fields. A value longer than 65535 bytes does not fit the
+    /// length and fails with [`ISO8583Error::InvalidFieldValue`].
+    pub fn to_journal_bytes(&self) -> Result<Vec<u8>> {
+        let (bitmap, bitmap_len) = self.bitmap.to_bytes();
+
+        let mut out = Vec::new();
+        out.extend_from_slice(JOURNAL_MAGIC);
+        out.push(JOURNAL_VERSION);
+        out.extend_from_slice(&self.mti.to_bytes());
+        out.push(bitmap_len as u8);
+        out.extend_from_slice(&bitmap[..bitmap_len]);
+
+        let mut numbers: Vec<u8> = self
+            .fields
+            .keys()
+            .chain(self.unknown_fields.keys())
+            .copied()
+            .collect();
+        numbers.sort_unstable();
+
+        for num in numbers {
+            let (kind, data) = match (self.fields.get(&num), self.unknown_fields.get(&num)) {
+                (Some(FieldValue::String(s)), _) => (JOURNAL_STRING, s.as_bytes()),
+                (Some(FieldValue::Binary(b)), _) => (JOURNAL_BINARY, b.as_slice()),
+                (None, Some(raw)) => (JOURNAL_UNKNOWN, raw.as_slice()),
+                (None, None) => continue,
+            };
+            let len = u16::try_from(data.len()).map_err(|_| {
+                ISO8583Error::invalid_field_value(
+                    num,
+                    format!("{} bytes do not fit the journal length", data.len()),
+                )
+            })?;
+            out.push(num);
+            out.push(kind);
+            out.extend_from_slice(&len.to_be_bytes());
+            out.extend_from_slice(data);
+        }
+
+        Ok(out)
+    }
+
+    /// Decode a message written by [`to_journal_bytes`](Self::to_journal_bytes)
+    pub fn from_journal_bytes(bytes: &[u8]) -> Result<Self> {
+        let header_len = JOURNAL_MAGIC.len() + 1 + 4 + 1;
+        if bytes.len() < header_len {
+            return Err(ISO8583Error::message_too_short(header_len, bytes.len()));
+        }
+        if &bytes[..3] != JOURNAL_MAGIC {
+            return Err(ISO8583Error::ParseError(
+                "Not a journal record (bad magic)".to_string(),
+            ));
+        }
+        if bytes[3] != JOURNAL_VERSION {
+            return Err(ISO8583Error::ParseError(format!(
+                "Unsupported journal format version {}",
+                bytes[3]
+            )));
         }
 
-        // 4. Parse fields based on bitmap
+        let mti = MessageType::from_bytes(&bytes[4..8])?;
+        let bitmap_len = bytes[8] as usize;
+        let mut offset = header_len;
+        if bytes.len() < offset + bitmap_len {
+            return Err(ISO8583Error::message_too_short(
+                offset + bitmap_len,
+                bytes.len(),
+            ));
+        }
+        let bitmap = Bitmap::from_bytes(&bytes[offset..offset + bitmap_len])
+            .map_err(|e| ISO8583Error::InvalidBitmap(e.to_string()))?;
+        offset += bitmap_len;
+
         let mut fields = HashMap::new();
-        let (field_array, field_count) = bitmap.get_set_fields();
+        let mut unknown_fields = HashMap::new();
 
-        for item in field_array.iter().take(field_count) {
-            let field_num = *item;
-            if field_num == 1 || field_num == 65 {
-                continue; // Skip bitmap indicators
+        while offset < bytes.len() {
+            if bytes.len() < offset + 4 {
+                return Err(ISO8583Error::message_too_short(offset + 4, bytes.len()));
             }
+            let num = bytes[offset];
+            let kind = bytes[offset + 1];
+            let len = u16::from_be_bytes([bytes[offset + 2], bytes[offset + 3]]) as usize;
+            offset += 4;
 
-            let field = Field::from_number(field_num)?;
-            let def = field.definition();
+            if bytes.len() < offset + len {
+                return Err(ISO8583Error::message_too_short(offset + len, bytes.len()));
+            }
+            let data = &bytes[offset..offset + len];
+            offset += len;
 
-            // Parse field based on its length specification
-            let (value, bytes_consumed) = Self::parse_field(&bytes[offset..], &def)?;
-            fields.insert(field_num, value);
-            offset += bytes_consumed;
+            if !(2..=128).contains(&num) || num == 65 {
+                return Err(ISO8583Error::ParseError(format!(
+                    "Journal record for invalid field number {}",
+                    num
+                )));
+            }
+            if !bitmap.is_set(num) {
+                return Err(ISO8583Error::ParseError(format!(
+                    "Journal record for field {} not set in the bitmap",
+                    num
+                )));
+            }
+
+            match kind {
+                JOURNAL_STRING => {
+                    let s = std::str::from_utf8(data).map_err(|e| {
+                        ISO8583Error::EncodingError(format!(
+                            "Invalid UTF-8 in field {}: {}",
+                            num, e
+                        ))
+                    })?;
+                    fields.insert(num, FieldValue::from_string(s));
+                }
+                JOURNAL_BINARY => {
+                    fields.insert(num, FieldValue::from_binary(data.to_vec()));
+                }
+                JOURNAL_UNKNOWN => {
+                    unknown_fields.insert(num, data.to_vec());
+                }
+                _ => {
+                    return Err(ISO8583Error::ParseError(format!(
+                        "Unknown journal record kind {} for field {}",
+                        kind, num
+                    )));
+                }
+            }
         }
 
         Ok(Self {
             mti,
             fields,
+            unknown_fields,
             bitmap,
+            spec: None,
         })
     }
 
+    /// Parse a field whose data uses its own wire encoding
+    ///
+    /// The length prefix of variable fields stays ASCII and counts
+    /// characters (digits for BCD). Values are always decoded to strings.
+    fn parse_encoded_field(
+        bytes: &[u8],
+        def: &FieldDefinition,
+        encoding: Encoding,
+    ) -> Result<(FieldValueRef<'static>, usize)> {
+        let (prefix_len, count) = match def.length {
+            FieldLength::Fixed(len) => (0, len),
+            FieldLength::LLVar(max_len) => (2, Self::read_length_prefix(bytes, 2, max_len, def)?),
+            FieldLength::LLLVar(max_len) => (3, Self::read_length_prefix(bytes, 3, max_len, def)?),
+        };
+        let data_len = match encoding {
+            Encoding::BCD => (count + 1) / 2,
+            Encoding::ASCII | Encoding::EBCDIC => count,
+        };
+        let data = bytes
+            .get(prefix_len..prefix_len + data_len)
+            .ok_or_else(|| ISO8583Error::message_too_short(prefix_len + data_len, bytes.len()))?;
+
+        let value = match encoding {
+            Encoding::ASCII => encoding::decode_ascii(data)?,
+            Encoding::EBCDIC => encoding::decode_ebcdic(data)?,
+            Encoding::BCD => {
+                // Odd digit counts are left-padded with a zero nibble
+                let digits = encoding::decode_bcd(data, data_len * 2)?;
+                digits[digits.len() - count..].to_string()
+            }
+        };
+
+        Ok((
+            FieldValueRef::String(Cow::Owned(value)),
+            prefix_len + data_len,
+        ))
+    }
+
+    /// Parse a fixed-length binary field sent as hex text
+    fn parse_hex_field(
+        bytes: &[u8],
+        def: &FieldDefinition,
+    ) -> Result<(FieldValueRef<'static>, usize)> {
+        let len = match def.length {
+            FieldLength::Fixed(len) => len * 2,
+            _ => {
+                return Err(ISO8583Error::invalid_field_value(
+                    def.number,
+                    "Hex encoding requires a fixed-length field",
+                ))
+            }
+        };
+        let text = bytes
+            .get(..len)
+            .ok_or_else(|| ISO8583Error::field_length_mismatch(def.number, len, bytes.len()))?;
+        let value = hex::decode(text).map_err(|e| {
+            ISO8583Error::EncodingError(format!("Invalid hex in field {}: {}", def.number, e))
+        })?;
+
+        Ok((FieldValueRef::Binary(Cow::Owned(value)), len))
+    }
+
+    /// Read an ASCII length prefix of `digits` digits
+    fn read_length_prefix(
+        bytes: &[u8],
+        digits: usize,
+        max_len: usize,
+        def: &FieldDefinition,
+    ) -> Result<usize> {
+        let length = bytes
+            .get(..digits)
+            .and_then(|prefix| std::str::from_utf8(prefix).ok())
+            .and_then(|prefix| prefix.parse().ok())
+            .ok_or_else(|| {
+                ISO8583Error::EncodingError(format!(
+                    "Invalid length indicator for field {}",
+                    def.number
+                ))
+            })?;
+
+        if length > max_len {
+            return Err(ISO8583Error::invalid_field_value(
+                def.number,
+                format!(
+                    "Length {} exceeds maximum {} for field {}",
+                    length, max_len, def.number
+                ),
+            ));
+        }
+        Ok(length)
+    }
+
     /// Parse a single field from bytes
-    fn parse_field(bytes: &[u8], def: &FieldDefinition) -> Result<(FieldValue, usize)> {
+    fn parse_field<'a>(
+        bytes: &'a [u8],
+        def: &FieldDefinition,
+    ) -> Result<(FieldValueRef<'a>, usize)> {
         // Ensure we have at least some bytes to parse
         if bytes.is_empty() {
             return Err(ISO8583Error::message_too_short(1, 0));
@@ -115,7 +743,7 @@ impl ISO8583Message {
                 }
 
                 let value = match def.field_type {
-                    FieldType::Binary => FieldValue::from_binary(bytes[..len].to_vec()),
+                    FieldType::Binary => FieldValueRef::Binary(Cow::Borrowed(&bytes[..len])),
                     _ => {
                         let s = std::str::from_utf8(&bytes[..len]).map_err(|e| {
                             ISO8583Error::EncodingError(format!(
@@ -123,7 +751,7 @@ impl ISO8583Message {
                                 def.number, e
                             ))
                         })?;
-                        FieldValue::from_string(s.to_string())
+                        FieldValueRef::String(Cow::Borrowed(s))
                     }
                 };
 
@@ -164,7 +792,9 @@ impl ISO8583Message {
                 }
 
                 let value = match def.field_type {
-                    FieldType::Binary => FieldValue::from_binary(bytes[2..2 + length].to_vec()),
+                    FieldType::Binary => {
+                        FieldValueRef::Binary(Cow::Borrowed(&bytes[2..2 + length]))
+                    }
                     _ => {
                         let s = std::str::from_utf8(&bytes[2..2 + length]).map_err(|e| {
                             ISO8583Error::EncodingError(format!(
@@ -172,7 +802,7 @@ impl ISO8583Message {
                                 def.number, e
                             ))
                         })?;
-                        FieldValue::from_string(s.to_string())
+                        FieldValueRef::String(Cow::Borrowed(s))
                     }
                 };
 
@@ -213,7 +843,9 @@ impl ISO8583Message {
                 }
 
                 let value = match def.field_type {
-                    FieldType::Binary => FieldValue::from_binary(bytes[3..3 + length].to_vec()),
+                    FieldType::Binary => {
+                        FieldValueRef::Binary(Cow::Borrowed(&bytes[3..3 + length]))
+                    }
                     _ => {
                         let s = std::str::from_utf8(&bytes[3..3 + length]).map_err(|e| {
                             ISO8583Error::EncodingError(format!(
@@ -221,7 +853,7 @@ impl ISO8583Message {
                                 def.number, e
                             ))
                         })?;
-                        FieldValue::from_string(s.to_string())
+                        FieldValueRef::String(Cow::Borrowed(s))
                     }
                 };
 
@@ -231,250 +863,4548 @@ impl ISO8583Message {
     }
 
     /// Generate message bytes (ASCII encoding)
+    ///
+    /// Never fails: a value the field's wire encoding cannot carry, such as
+    /// a non-digit in a field the [`RuntimeSpec`] sends as BCD, is written
+    /// with the offending characters replaced (zeros for BCD, spaces for
+    /// EBCDIC). Use [`try_to_bytes`](Self::try_to_bytes) or
+    /// [`to_bytes_with_options`](Self::to_bytes_with_options) to get an
+    /// error instead.
     pub fn to_bytes(&self) -> Vec<u8> {
-        let mut bytes = Vec::new();
-
-        // 1. Add MTI
-        bytes.extend_from_slice(&self.mti.to_bytes());
-
-        // 2. Add bitmap(s)
-        let (bitmap_bytes, bitmap_len) = self.bitmap.to_bytes();
-        bytes.extend_from_slice(&bitmap_bytes[..bitmap_len]);
-
-        // 3. Add fields in numerical order
-        let mut field_numbers: Vec<u8> = self.fields.keys().copied().collect();
-        field_numbers.sort();
-
-        for field_num in field_numbers {
-            if field_num == 1 || field_num == 65 {
-                continue; // Skip bitmap indicators
-            }
+        self.to_bytes_lossy(&ParseOptions::new())
+    }
 
-            if let Some(value) = self.fields.get(&field_num) {
-                let field = Field::from_number(field_num).unwrap();
-                let field_bytes = Self::generate_field(&field, value);
-                bytes.extend_from_slice(&field_bytes);
-            }
+    /// Encode with unencodable values substituted
+    ///
+    /// Only for options without field 27/38 length handling, the one check
+    /// substitution cannot satisfy.
+    fn to_bytes_lossy(&self, options: &ParseOptions) -> Vec<u8> {
+        debug_assert!(!options.auth_id_length_from_field_27);
+        let mut bytes = Vec::with_capacity(self.encoded_len());
+        if let Err(e) = self.write_body_with(&mut bytes, options, true) {
+            unreachable!("lossy encoding into a Vec failed: {}", e);
         }
-
         bytes
     }
 
-    /// Generate bytes for a single field
-    fn generate_field(field: &Field, value: &FieldValue) -> Vec<u8> {
-        let def = field.definition();
-        let mut bytes = Vec::new();
+    /// Generate message bytes with the bitmap region in the given encoding
+    ///
+    /// [`BitmapEncoding::AsciiHex`] writes each bitmap block as 16 uppercase
+    /// hex characters; parse such messages with
+    /// [`ParseOptions::bitmap_encoding`].
+    ///
+    /// Like [`to_bytes`](Self::to_bytes), unencodable values are substituted
+    /// rather than reported.
+    pub fn to_bytes_with_bitmap(&self, encoding: BitmapEncoding) -> Vec<u8> {
+        self.to_bytes_lossy(&ParseOptions::new().bitmap_encoding(encoding))
+    }
 
-        match def.length {
-            FieldLength::Fixed(len) => {
-                // Fixed length field
-                match value {
-                    FieldValue::String(s) => {
-                        let mut field_str = s.clone();
-                        // Pad or truncate to exact length
-                        if field_str.len() < len {
-                            // Pad with spaces or zeros depending on field type
-                            match def.field_type {
-                                FieldType::Numeric => {
-                                    field_str = format!("{:0>width$}", field_str, width = len);
-                                }
-                                _ => {
-                                    field_str = format!("{:<width$}", field_str, width = len);
-                                }
-                            }
-                        } else if field_str.len() > len {
-                            field_str.truncate(len);
-                        }
-                        bytes.extend_from_slice(field_str.as_bytes());
-                    }
-                    FieldValue::Binary(b) => {
-                        let mut bin = b.clone();
-                        bin.resize(len, 0); // Pad with zeros if needed
-                        bytes.extend_from_slice(&bin);
-                    }
+    /// Generate message bytes in the wire format described by `options`
+    ///
+    /// Honors [`ParseOptions::mti_encoding`] and
+    /// [`ParseOptions::bitmap_encoding`], so the output parses back with
+    /// [`from_bytes_with_options`](Self::from_bytes_with_options) and the
+    /// same options.
+    ///
+    /// # Errors
+    /// Fails if a value cannot be represented in the requested format, e.g.
+    /// an auth ID longer than 9 characters with
+    /// [`ParseOptions::auth_id_length_from_field_27`].
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::encoding::Encoding;
+    /// use iso8583_core::*;
+    ///
+    /// let options = ParseOptions::new().mti_encoding(Encoding::BCD);
+    /// let msg = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+    ///
+    /// let bytes = msg.to_bytes_with_options(&options)?;
+    /// assert_eq!(&bytes[..2], &[0x01, 0x00]);
+    /// assert_eq!(ISO8583Message::from_bytes_with_options(&bytes, &options)?, msg);
+    /// # Ok::<(), ISO8583Error>(())
+    /// ```
+    pub fn to_bytes_with_options(&self, options: &ParseOptions) -> Result<Vec<u8>> {
+        self.check_overflow(options, OverflowPolicy::Reject)?;
+
+        let mut bytes = Vec::new();
+        self.write_body_with(&mut bytes, options, false)
+            .map_err(|e| {
+                // Writing to a Vec only fails on values the format cannot carry
+                match e.into_inner().map(|inner| inner.downcast::<ISO8583Error>()) {
+                    Some(Ok(err)) => *err,
+                    _ => ISO8583Error::EncodingError("Failed to encode message".to_string()),
                 }
-            }
-            FieldLength::LLVar(_max_len) => {
-                // Variable length with 2-digit length indicator
-                match value {
-                    FieldValue::String(s) => {
-                        let length = format!("{:02}", s.len());
-                        bytes.extend_from_slice(length.as_bytes());
-                        bytes.extend_from_slice(s.as_bytes());
-                    }
-                    FieldValue::Binary(b) => {
-                        let length = format!("{:02}", b.len());
-                        bytes.extend_from_slice(length.as_bytes());
-                        bytes.extend_from_slice(b);
-                    }
+            })?;
+        Ok(bytes)
+    }
+
+    /// Length of the encoded message in bytes, excluding any MLI header
+    pub fn encoded_len(&self) -> usize {
+        let (_, bitmap_len) = self.bitmap.to_bytes();
+        let fields_len: usize = self
+            .fields
+            .iter()
+            .filter(|(&num, _)| num != 1 && num != 65)
+            .map(|(&num, value)| {
+                let def = self.field_definition(Field::from_number(num).unwrap());
+                // Overlong values get a wider prefix, as in write_field
+                let digits = value.len().to_string().len();
+                let (prefix_len, data_len) = match def.length {
+                    FieldLength::Fixed(len) => (0, len),
+                    FieldLength::LLVar(_) => (digits.max(2), value.len()),
+                    FieldLength::LLLVar(_) => (digits.max(3), value.len()),
+                };
+                if self.is_hex_binary(&def) {
+                    return (prefix_len + data_len) * 2;
                 }
-            }
-            FieldLength::LLLVar(_max_len) => {
-                // Variable length with 3-digit length indicator
-                match value {
-                    FieldValue::String(s) => {
-                        let length = format!("{:03}", s.len());
-                        bytes.extend_from_slice(length.as_bytes());
-                        bytes.extend_from_slice(s.as_bytes());
-                    }
-                    FieldValue::Binary(b) => {
-                        let length = format!("{:03}", b.len());
-                        bytes.extend_from_slice(length.as_bytes());
-                        bytes.extend_from_slice(b);
+                match self.field_encoding(num) {
+                    Some(Encoding::BCD) if def.field_type != FieldType::Binary => {
+                        prefix_len + (data_len + 1) / 2
                     }
+                    _ => prefix_len + data_len,
                 }
-            }
-        }
+            })
+            .sum();
+        let unknown_len: usize = self
+            .unknown_fields
+            .iter()
+            .filter(|(num, _)| !self.fields.contains_key(num))
+            .map(|(_, raw)| raw.len())
+            .sum();
 
-        bytes
+        4 + bitmap_len + fields_len + unknown_len
+    }
+
+    /// Generate message bytes preceded by a message length indicator
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::framing::MliType;
+    /// use iso8583_core::*;
+    ///
+    /// let msg = ISO8583Message::new(MessageType::NETWORK_MANAGEMENT_REQUEST);
+    /// let framed = msg.to_framed_bytes(MliType::Binary2)?;
+    /// assert_eq!(&framed[..2], &[0x00, 0x0C]); // MTI + primary bitmap
+    /// # Ok::<(), ISO8583Error>(())
+    /// ```
+    pub fn to_framed_bytes(&self, mli: MliType) -> Result<Vec<u8>> {
+        let body = self.to_bytes_with_options(&ParseOptions::new())?;
+        let mut bytes = mli.encode(body.len())?;
+        bytes.extend_from_slice(&body);
+        Ok(bytes)
+    }
+
+    /// Parse a message preceded by a message length indicator
+    ///
+    /// The header must match the body length exactly; for
+    /// [`MliType::None`] the whole buffer is the message.
+    pub fn from_framed_bytes(bytes: &[u8], mli: MliType) -> Result<Self> {
+        if mli == MliType::None {
+            return Self::from_bytes(bytes);
+        }
+
+        let header_len = mli.header_len();
+        let body_len = mli.decode(bytes)?;
+        let body = &bytes[header_len..];
+        if body.len() < body_len {
+            return Err(ISO8583Error::message_too_short(
+                header_len + body_len,
+                bytes.len(),
+            ));
+        }
+        if body.len() > body_len {
+            return Err(ISO8583Error::ParseError(format!(
+                "{} bytes after framed message",
+                body.len() - body_len
+            )));
+        }
+
+        Self::from_bytes(body)
+    }
+
+    /// Write the message with its MLI header directly into a writer
+    ///
+    /// Produces the same bytes as [`to_framed_bytes`](Self::to_framed_bytes)
+    /// without building the whole message in memory first, so it pairs well
+    /// with [`std::io::BufWriter`]. Returns the number of bytes written,
+    /// header included.
+    pub fn write_to<W: io::Write>(&self, w: &mut W, mli: MliType) -> io::Result<usize> {
+        let body_len = self.encoded_len();
+        let header = mli
+            .encode(body_len)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        w.write_all(&header)?;
+        self.write_body(w)?;
+        Ok(header.len() + body_len)
+    }
+
+    /// Read one message with its MLI header from a reader
+    ///
+    /// Reads exactly the header and the body it announces, leaving any
+    /// following messages in the reader. With [`MliType::None`] the reader
+    /// is consumed to the end. Parse failures are reported as
+    /// [`io::ErrorKind::InvalidData`] wrapping the [`ISO8583Error`].
+    ///
+    /// Frames longer than [`DEFAULT_MAX_FRAME_LEN`] are rejected; see
+    /// [`read_from_with_limit`](Self::read_from_with_limit).
+    pub fn read_from<R: io::Read>(r: &mut R, mli: MliType) -> io::Result<Self> {
+        Self::read_from_with_limit(r, mli, DEFAULT_MAX_FRAME_LEN)
+    }
+
+    /// Read one message with its MLI header, accepting at most `max_len`
+    /// body bytes
+    ///
+    /// A header announcing more fails with [`io::ErrorKind::InvalidData`]
+    /// wrapping [`ISO8583Error::FrameTooLarge`] before any body byte is
+    /// read. The body buffer grows as data arrives, so a header is never
+    /// trusted for an allocation up front.
+    pub fn read_from_with_limit<R: io::Read>(
+        r: &mut R,
+        mli: MliType,
+        max_len: usize,
+    ) -> io::Result<Self> {
+        let invalid = |e: ISO8583Error| io::Error::new(io::ErrorKind::InvalidData, e);
+
+        let mut body = Vec::new();
+        if mli == MliType::None {
+            r.take(max_len as u64 + 1).read_to_end(&mut body)?;
+            check_frame_len(body.len(), max_len).map_err(invalid)?;
+        } else {
+            let mut header = [0u8; 4];
+            let header = &mut header[..mli.header_len()];
+            r.read_exact(header)?;
+            let len = mli.decode(header).map_err(invalid)?;
+            check_frame_len(len, max_len).map_err(invalid)?;
+            r.take(len as u64).read_to_end(&mut body)?;
+            if body.len() < len {
+                return Err(io::ErrorKind::UnexpectedEof.into());
+            }
+        }
+
+        Self::from_bytes(&body).map_err(invalid)
+    }
+
+    /// Read one message with its MLI header from an async reader
+    ///
+    /// The async counterpart of [`read_from`](Self::read_from): short reads
+    /// are retried until the whole frame has arrived. A stream that ends
+    /// mid-frame fails with [`io::ErrorKind::UnexpectedEof`], while a
+    /// complete frame that fails to parse is reported as
+    /// [`io::ErrorKind::InvalidData`] wrapping the [`ISO8583Error`].
+    ///
+    /// Frames longer than [`DEFAULT_MAX_FRAME_LEN`] are rejected; see
+    /// [`read_async_with_limit`](Self::read_async_with_limit).
+    #[cfg(feature = "tokio")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+    pub async fn read_async<R: tokio::io::AsyncRead + Unpin>(
+        r: &mut R,
+        mli: MliType,
+    ) -> io::Result<Self> {
+        Self::read_async_with_limit(r, mli, DEFAULT_MAX_FRAME_LEN).await
+    }
+
+    /// Read one message with its MLI header from an async reader,
+    /// accepting at most `max_len` body bytes
+    ///
+    /// The async counterpart of
+    /// [`read_from_with_limit`](Self::read_from_with_limit).
+    #[cfg(feature = "tokio")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio")))]
+    pub async fn read_async_with_limit<R: tokio::io::AsyncRead + Unpin>(
+        r: &mut R,
+        mli: MliType,
+        max_len: usize,
+    ) -> io::Result<Self> {
+        use tokio::io::AsyncReadExt;
+
+        let invalid = |e: ISO8583Error| io::Error::new(io::ErrorKind::InvalidData, e);
+
+        let mut body = Vec::new();
+        if mli == MliType::None {
+            r.take(max_len as u64 + 1).read_to_end(&mut body).await?;
+            check_frame_len(body.len(), max_len).map_err(invalid)?;
+        } else {
+            let mut header = [0u8; 4];
+            let header = &mut header[..mli.header_len()];
+            r.read_exact(header).await?;
+            let len = mli.decode(header).map_err(invalid)?;
+            check_frame_len(len, max_len).map_err(invalid)?;
+            r.take(len as u64).read_to_end(&mut body).await?;
+            if body.len() < len {
+                return Err(io::ErrorKind::UnexpectedEof.into());
+            }
+        }
+
+        Self::from_bytes(&body).map_err(invalid)
+    }
+
+    /// Generate message bytes followed by a trailer
+    ///
+    /// The body is encoded with `options`; use an ASCII bitmap encoding so
+    /// the delimiter cannot occur inside it. Fails if the body contains the
+    /// delimiter anyway.
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::framing::{lrc, Trailer, ETX};
+    /// use iso8583_core::*;
+    ///
+    /// let options = ParseOptions::new().bitmap_encoding(BitmapEncoding::AsciiHex);
+    /// let msg = ISO8583Message::new(MessageType::NETWORK_MANAGEMENT_REQUEST);
+    /// let framed = msg.to_delimited_bytes(Trailer::EtxLrc, &options)?;
+    ///
+    /// let (body, trailer) = framed.split_at(framed.len() - 2);
+    /// assert_eq!(trailer, &[ETX, lrc(body)]);
+    /// # Ok::<(), ISO8583Error>(())
+    /// ```
+    pub fn to_delimited_bytes(&self, trailer: Trailer, options: &ParseOptions) -> Result<Vec<u8>> {
+        let mut bytes = self.to_bytes_with_options(options)?;
+        let trailer = trailer.encode(&bytes)?;
+        bytes.extend_from_slice(&trailer);
+        Ok(bytes)
+    }
+
+    /// Parse a message followed by a trailer
+    ///
+    /// The buffer must end with the trailer, whose LRC (if any) must match.
+    pub fn from_delimited_bytes(
+        bytes: &[u8],
+        trailer: Trailer,
+        options: &ParseOptions,
+    ) -> Result<Self> {
+        Self::from_bytes_with_options(trailer.strip(bytes)?, options)
+    }
+
+    /// Read one message ending with a trailer from a buffered reader
+    ///
+    /// Scans up to the delimiter (plus the LRC byte for
+    /// [`Trailer::EtxLrc`]), leaving any following messages in the reader.
+    /// With [`Trailer::None`] the reader is consumed to the end. A stream
+    /// that ends before the trailer fails with
+    /// [`io::ErrorKind::UnexpectedEof`]; parse and LRC failures are reported
+    /// as [`io::ErrorKind::InvalidData`] wrapping the [`ISO8583Error`].
+    ///
+    /// Bodies longer than [`DEFAULT_MAX_FRAME_LEN`] are rejected; see
+    /// [`read_delimited_with_limit`](Self::read_delimited_with_limit).
+    pub fn read_delimited<R: io::BufRead>(
+        r: &mut R,
+        trailer: Trailer,
+        options: &ParseOptions,
+    ) -> io::Result<Self> {
+        Self::read_delimited_with_limit(r, trailer, options, DEFAULT_MAX_FRAME_LEN)
+    }
+
+    /// Read one message ending with a trailer, accepting at most `max_len`
+    /// body bytes
+    ///
+    /// A peer that sends more without a delimiter fails with
+    /// [`io::ErrorKind::InvalidData`] wrapping
+    /// [`ISO8583Error::FrameTooLarge`] once the limit is passed, instead of
+    /// being buffered indefinitely.
+    pub fn read_delimited_with_limit<R: io::BufRead>(
+        r: &mut R,
+        trailer: Trailer,
+        options: &ParseOptions,
+        max_len: usize,
+    ) -> io::Result<Self> {
+        let invalid = |e: ISO8583Error| io::Error::new(io::ErrorKind::InvalidData, e);
+
+        let mut frame = Vec::new();
+        match trailer {
+            Trailer::None => {
+                r.take(max_len as u64 + 1).read_to_end(&mut frame)?;
+                check_frame_len(frame.len(), max_len).map_err(invalid)?;
+            }
+            Trailer::Etx | Trailer::EtxLrc | Trailer::CrLf => {
+                let delimiter = trailer.delimiter();
+                let frame_limit = max_len + delimiter.len();
+                let mut limited = r.take(frame_limit as u64);
+                let last = delimiter[delimiter.len() - 1];
+                // A lone LF inside a CRLF-delimited body does not end it
+                while !frame.ends_with(delimiter) {
+                    if io::BufRead::read_until(&mut limited, last, &mut frame)? == 0 {
+                        if frame.len() >= frame_limit {
+                            return Err(invalid(ISO8583Error::FrameTooLarge {
+                                len: frame.len(),
+                                max: max_len,
+                            }));
+                        }
+                        return Err(io::ErrorKind::UnexpectedEof.into());
+                    }
+                }
+                if trailer == Trailer::EtxLrc {
+                    let mut check = [0u8; 1];
+                    limited.into_inner().read_exact(&mut check)?;
+                    frame.push(check[0]);
+                }
+            }
+        }
+
+        Self::from_delimited_bytes(&frame, trailer, options).map_err(invalid)
+    }
+
+    /// Write MTI, bitmap(s) and fields in numerical order
+    fn write_body<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
+        self.write_body_with(w, &ParseOptions::new(), false)
+    }
+
+    /// Write MTI, bitmap(s) and fields in the wire format of `options`
+    ///
+    /// With `lossy` set, values the field encoding cannot carry are
+    /// substituted instead of failing.
+    fn write_body_with<W: io::Write>(
+        &self,
+        w: &mut W,
+        options: &ParseOptions,
+        lossy: bool,
+    ) -> io::Result<()> {
+        if options.auth_id_length_from_field_27 {
+            if let Some(auth_id) = self.fields.get(&38) {
+                let length = FieldValue::String(auth_id.len().to_string());
+                if auth_id.len() > 9 {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        ISO8583Error::field_length_mismatch(38, 9, auth_id.len()),
+                    ));
+                }
+                if self.fields.get(&27) != Some(&length) {
+                    let mut with_length = self.clone();
+                    with_length.fields.insert(27, length);
+                    with_length.bitmap.set(27).expect("field 27 is in range");
+                    return with_length.write_body_with(w, options, lossy);
+                }
+            }
+        }
+
+        // 1. Add MTI
+        match options.mti_encoding {
+            Encoding::ASCII => w.write_all(&self.mti.to_bytes())?,
+            Encoding::BCD => {
+                let bcd = self
+                    .mti
+                    .to_bcd_bytes()
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                w.write_all(&bcd)?
+            }
+            Encoding::EBCDIC => {
+                let ebcdic = encoding::encode_ebcdic(&self.mti.to_string())
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                w.write_all(&ebcdic)?
+            }
+        }
+
+        // 2. Add bitmap(s)
+        w.write_all(
+            &self
+                .bitmap
+                .with_secondary_policy(options.secondary_bitmap)
+                .encode(options.bitmap_encoding),
+        )?;
+
+        // 3. Add fields in numerical order
+        for field_num in self.wire_field_numbers() {
+            self.write_wire_field(w, field_num, options, lossy)?;
+        }
+
+        Ok(())
+    }
+
+    /// Present data field numbers (defined and undefined) in wire order
+    fn wire_field_numbers(&self) -> Vec<u8> {
+        let mut field_numbers: Vec<u8> = self
+            .fields
+            .keys()
+            .chain(self.unknown_fields.keys())
+            .copied()
+            .filter(|&num| num != 1 && num != 65) // Skip bitmap indicators
+            .collect();
+        field_numbers.sort();
+        field_numbers.dedup();
+        field_numbers
+    }
+
+    /// Write one present field, defined or undefined
+    fn write_wire_field<W: io::Write>(
+        &self,
+        w: &mut W,
+        field_num: u8,
+        options: &ParseOptions,
+        lossy: bool,
+    ) -> io::Result<()> {
+        if let Some(value) = self.fields.get(&field_num) {
+            let mut def = self.field_definition(Field::from_number(field_num).unwrap());
+            if field_num == 38 && options.auth_id_length_from_field_27 {
+                def.length = FieldLength::Fixed(value.len());
+            }
+            let uppercased;
+            let value = match value {
+                FieldValue::String(s)
+                    if options.uppercase_alpha && def.field_type == FieldType::Alpha =>
+                {
+                    uppercased = FieldValue::String(s.to_ascii_uppercase());
+                    &uppercased
+                }
+                _ => value,
+            };
+            match self.field_encoding(field_num) {
+                _ if self.is_hex_binary(&def) => {
+                    let mut raw = Vec::new();
+                    Self::write_field(&mut raw, &def, value)?;
+                    w.write_all(hex::encode_upper(raw).as_bytes())?
+                }
+                Some(encoding) if def.field_type != FieldType::Binary => {
+                    Self::write_encoded_field(w, &def, value, encoding, lossy)?
+                }
+                _ => Self::write_field(w, &def, value)?,
+            }
+        } else if let Some(raw) = self.unknown_fields.get(&field_num) {
+            w.write_all(raw)?;
+        }
+        Ok(())
+    }
+
+    /// Generate message bytes with sensitive field content masked
+    ///
+    /// The PAN, track data and PIN block (fields 2, 35, 45 and 52) are
+    /// replaced byte-for-byte with `*`, keeping their length prefixes, so the
+    /// output is safe to log yet has the same length and field offsets as
+    /// [`to_bytes`](Self::to_bytes).
+    pub fn masked_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.encoded_len());
+        bytes.extend_from_slice(&self.mti.to_bytes());
+        let (bitmap_bytes, bitmap_len) = self.bitmap.to_bytes();
+        bytes.extend_from_slice(&bitmap_bytes[..bitmap_len]);
+
+        for field_num in self.wire_field_numbers() {
+            let start = bytes.len();
+            if let Err(e) = self.write_wire_field(&mut bytes, field_num, &ParseOptions::new(), true)
+            {
+                unreachable!("lossy encoding into a Vec failed: {}", e);
+            }
+
+            let field = match Field::from_number(field_num) {
+                Ok(field) if field.is_sensitive() && self.fields.contains_key(&field_num) => field,
+                _ => continue,
+            };
+            let prefix_len = match self.field_definition(field).length {
+                FieldLength::Fixed(_) => 0,
+                FieldLength::LLVar(_) => 2,
+                FieldLength::LLLVar(_) => 3,
+            };
+            bytes[start + prefix_len..].fill(b'*');
+        }
+
+        bytes
+    }
+
+    /// Generate message bytes, honoring the overflow policy of each field
+    ///
+    /// Unlike [`to_bytes`](Self::to_bytes), which always truncates
+    /// over-length fixed fields, this fails with
+    /// [`ISO8583Error::FieldLengthMismatch`] for any over-length value whose
+    /// type's [`OverflowPolicy`] is not `Truncate`. Alpha (`a`) fields
+    /// holding anything but letters and spaces are rejected too, rather
+    /// than padded and sent.
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::field::FieldValue;
+    /// use iso8583_core::*;
+    ///
+    /// let mut msg = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+    /// msg.set_field(Field::TransactionAmount, FieldValue::from_string("1000000000000"))?;
+    /// assert!(msg.try_to_bytes().is_err());
+    /// # Ok::<(), ISO8583Error>(())
+    /// ```
+    pub fn try_to_bytes(&self) -> Result<Vec<u8>> {
+        self.try_to_bytes_with_options(&ParseOptions::new())
+    }
+
+    /// Checked generation in the wire format and with the overflow policies
+    /// of `options`
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::field::{FieldType, FieldValue, OverflowPolicy};
+    /// use iso8583_core::*;
+    ///
+    /// let mut msg = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+    /// msg.set_field(Field::TransactionAmount, FieldValue::from_string("1000000000000"))?;
+    ///
+    /// let options = ParseOptions::new().overflow_policy(FieldType::Numeric, OverflowPolicy::Truncate);
+    /// assert!(msg.try_to_bytes_with_options(&options).is_ok());
+    /// # Ok::<(), ISO8583Error>(())
+    /// ```
+    pub fn try_to_bytes_with_options(&self, options: &ParseOptions) -> Result<Vec<u8>> {
+        for field_num in self.get_field_numbers() {
+            let def = self.field_definition(Field::from_number(field_num)?);
+            let value = &self.fields[&field_num];
+            if def.field_type == FieldType::Alpha
+                && !value
+                    .as_string()
+                    .is_some_and(|s| s.chars().all(|c| c.is_ascii_alphabetic() || c == ' '))
+            {
+                return Err(ISO8583Error::invalid_field_value(
+                    field_num,
+                    "Field must be alphabetic",
+                ));
+            }
+        }
+        self.check_overflow(options, OverflowPolicy::Error)?;
+
+        self.to_bytes_with_options(options)
+    }
+
+    /// Fail on the first over-length fixed field whose policy is `strictest`
+    /// or stricter
+    fn check_overflow(&self, options: &ParseOptions, strictest: OverflowPolicy) -> Result<()> {
+        for field_num in self.get_field_numbers() {
+            let def = self.field_definition(Field::from_number(field_num)?);
+            let value = &self.fields[&field_num];
+            let refused = match options.effective_overflow_policy(def.field_type) {
+                OverflowPolicy::Truncate => false,
+                OverflowPolicy::Error => strictest == OverflowPolicy::Error,
+                OverflowPolicy::Reject => true,
+            };
+            if let FieldLength::Fixed(len) = def.length {
+                if refused && def.overflows(value) {
+                    return Err(ISO8583Error::field_length_mismatch(
+                        field_num,
+                        len,
+                        value.len(),
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Wire encoding override for a field from the attached runtime spec
+    fn field_encoding(&self, field_num: u8) -> Option<Encoding> {
+        self.spec
+            .as_deref()
+            .and_then(|spec| spec.encoding(field_num))
+    }
+
+    /// Check if a field is fixed-length binary sent as hex text
+    fn is_hex_binary(&self, def: &FieldDefinition) -> bool {
+        def.field_type == FieldType::Binary
+            && matches!(def.length, FieldLength::Fixed(_))
+            && self
+                .spec
+                .as_deref()
+                .is_some_and(|spec| spec.binary_encoding(def.number) == BinaryEncoding::Hex)
+    }
+
+    /// Write a string field in its own wire encoding
+    ///
+    /// Padding and truncation match [`write_field`](Self::write_field); the
+    /// length prefix of variable fields stays ASCII.
+    fn write_encoded_field<W: io::Write>(
+        w: &mut W,
+        def: &FieldDefinition,
+        value: &FieldValue,
+        encoding: Encoding,
+        lossy: bool,
+    ) -> io::Result<()> {
+        let mut ascii = Vec::new();
+        Self::write_field(&mut ascii, def, value)?;
+
+        let prefix_len = match def.length {
+            FieldLength::Fixed(_) => 0,
+            FieldLength::LLVar(_) => 2,
+            FieldLength::LLLVar(_) => 3,
+        };
+        let (prefix, data) = ascii.split_at(prefix_len);
+        let substituted;
+        let data = if lossy {
+            // Replace what the encoding cannot carry, keeping the length
+            substituted = match encoding {
+                Encoding::BCD => data
+                    .iter()
+                    .map(|&b| if b.is_ascii_digit() { b as char } else { '0' })
+                    .collect::<String>(),
+                Encoding::ASCII | Encoding::EBCDIC => data
+                    .iter()
+                    .map(|&b| if b.is_ascii() { b as char } else { ' ' })
+                    .collect(),
+            };
+            substituted.as_str()
+        } else {
+            std::str::from_utf8(data).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+        };
+
+        let encoded = match encoding {
+            Encoding::ASCII => Ok(data.as_bytes().to_vec()),
+            Encoding::BCD => encoding::encode_bcd(data),
+            Encoding::EBCDIC => encoding::encode_ebcdic(data),
+        }
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        w.write_all(prefix)?;
+        w.write_all(&encoded)
+    }
+
+    /// Write the encoded bytes of a single field
+    fn write_field<W: io::Write>(
+        w: &mut W,
+        def: &FieldDefinition,
+        value: &FieldValue,
+    ) -> io::Result<()> {
+        match def.length {
+            FieldLength::Fixed(len) => {
+                // Fixed length field
+                match value {
+                    FieldValue::String(s) => {
+                        let mut field_str = s.clone();
+                        // Pad or truncate to exact length
+                        if field_str.len() < len {
+                            // Pad with spaces or zeros depending on field type
+                            match def.field_type {
+                                FieldType::Numeric => {
+                                    field_str = format!("{:0>width$}", field_str, width = len);
+                                }
+                                FieldType::SignedAmount if field_str.starts_with(['C', 'D']) => {
+                                    // Zero-pad the digits after the sign
+                                    let (sign, digits) = field_str.split_at(1);
+                                    field_str =
+                                        format!("{}{:0>width$}", sign, digits, width = len - 1);
+                                }
+                                _ => {
+                                    field_str = format!("{:<width$}", field_str, width = len);
+                                }
+                            }
+                        } else if field_str.len() > len {
+                            field_str.truncate(len);
+                        }
+                        w.write_all(field_str.as_bytes())?;
+                    }
+                    FieldValue::Binary(b) => {
+                        let data = &b[..b.len().min(len)];
+                        w.write_all(data)?;
+                        // Pad with zeros if needed
+                        io::copy(&mut io::repeat(0).take((len - data.len()) as u64), w)?;
+                    }
+                }
+            }
+            FieldLength::LLVar(_max_len) => {
+                // Variable length with 2-digit length indicator
+                match value {
+                    FieldValue::String(s) => {
+                        let length = format!("{:02}", s.len());
+                        w.write_all(length.as_bytes())?;
+                        w.write_all(s.as_bytes())?;
+                    }
+                    FieldValue::Binary(b) => {
+                        let length = format!("{:02}", b.len());
+                        w.write_all(length.as_bytes())?;
+                        w.write_all(b)?;
+                    }
+                }
+            }
+            FieldLength::LLLVar(_max_len) => {
+                // Variable length with 3-digit length indicator
+                match value {
+                    FieldValue::String(s) => {
+                        let length = format!("{:03}", s.len());
+                        w.write_all(length.as_bytes())?;
+                        w.write_all(s.as_bytes())?;
+                    }
+                    FieldValue::Binary(b) => {
+                        let length = format!("{:03}", b.len());
+                        w.write_all(length.as_bytes())?;
+                        w.write_all(b)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
     }
 
     /// Get field value
+    ///
+    /// This borrows and never clones; prefer it unless the value has to
+    /// outlive the message.
     pub fn get_field(&self, field: Field) -> Option<&FieldValue> {
         self.fields.get(&field.number())
     }
 
-    /// Set field value
-    pub fn set_field(&mut self, field: Field, value: FieldValue) -> Result<()> {
-        let field_num = field.number();
+    /// Get a string field as an owned `String`
+    ///
+    /// Clones the value, for boundaries where a borrow cannot escape. Binary
+    /// fields return `None`; use [`get_field`](Self::get_field) to borrow
+    /// without cloning.
+    pub fn take_str(&self, field: Field) -> Option<String> {
+        self.get_field(field)
+            .and_then(FieldValue::as_string)
+            .map(str::to_string)
+    }
+
+    /// Snapshot of all data fields, ordered by field number
+    ///
+    /// Every value is cloned. Undefined fields kept by lenient parsing are
+    /// not included.
+    pub fn to_owned_fields(&self) -> BTreeMap<u8, FieldValue> {
+        self.fields
+            .iter()
+            .map(|(&num, value)| (num, value.clone()))
+            .collect()
+    }
+
+    /// Set field value
+    ///
+    /// Fails without storing the value if the attached [`RuntimeSpec`]
+    /// sends the field as BCD and the value is not numeric.
+    pub fn set_field(&mut self, field: Field, value: FieldValue) -> Result<()> {
+        self.check_field_value(field, &value)?;
+
+        // Update bitmap
+        self.bitmap.set(field.number())?;
+
+        // Store value
+        self.fields.insert(field.number(), value);
+
+        Ok(())
+    }
+
+    /// Set several fields at once
+    ///
+    /// Every value is checked as in [`set_field`](Self::set_field) before
+    /// anything is stored, so on error the message is unchanged. Field
+    /// storage is reserved once up front, and the highest field is marked
+    /// first so any secondary or tertiary bitmap block is added in one step;
+    /// the remaining fields only flip bits in blocks that already exist.
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::field::FieldValue;
+    /// use iso8583_core::*;
+    ///
+    /// let mut msg = ISO8583Message::new(MessageType::FINANCIAL_REQUEST);
+    /// msg.set_fields_batch([
+    ///     (Field::ProcessingCode, FieldValue::from_string("000000")),
+    ///     (Field::SystemTraceAuditNumber, FieldValue::from_string("000001")),
+    ///     (Field::NetworkManagementInformationCode, FieldValue::from_string("301")),
+    /// ])?;
+    /// assert_eq!(msg.get_field_numbers(), vec![3, 11, 70]);
+    /// assert!(msg.bitmap().is_set(1));
+    /// # Ok::<(), ISO8583Error>(())
+    /// ```
+    pub fn set_fields_batch(
+        &mut self,
+        fields: impl IntoIterator<Item = (Field, FieldValue)>,
+    ) -> Result<()> {
+        let fields: Vec<(Field, FieldValue)> = fields.into_iter().collect();
+        for (field, value) in &fields {
+            self.check_field_value(*field, value)?;
+        }
+
+        self.fields.reserve(fields.len());
+        if let Some(highest) = fields.iter().map(|(field, _)| field.number()).max() {
+            self.bitmap.set(highest)?;
+        }
+        for (field, value) in fields {
+            self.bitmap.set(field.number())?;
+            self.fields.insert(field.number(), value);
+        }
+
+        Ok(())
+    }
+
+    /// Check that `value` can be stored in `field`
+    fn check_field_value(&self, field: Field, value: &FieldValue) -> Result<()> {
+        let field_num = field.number();
+
+        if self.field_encoding(field_num) == Some(Encoding::BCD)
+            && value
+                .as_string()
+                .is_some_and(|s| !s.bytes().all(|b| b.is_ascii_digit()))
+        {
+            return Err(ISO8583Error::invalid_field_value(
+                field_num,
+                "BCD-encoded field must be numeric",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Remove field
+    pub fn remove_field(&mut self, field: Field) -> Result<()> {
+        let field_num = field.number();
+
+        // Update bitmap
+        self.bitmap.clear(field_num)?;
+
+        // Remove value
+        self.fields.remove(&field_num);
+
+        Ok(())
+    }
+
+    /// Present fields that may carry cardholder data, in ascending order
+    ///
+    /// Fields are selected by [`Field::is_cardholder_data`], so a
+    /// tokenization pass can visit exactly these with
+    /// [`map_fields`](Self::map_fields).
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::field::FieldValue;
+    /// use iso8583_core::*;
+    ///
+    /// let mut msg = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+    /// msg.set_field(Field::ExpirationDate, FieldValue::from_string("2812"))?;
+    /// msg.set_field(Field::PrimaryAccountNumber, FieldValue::from_string("4111111111111111"))?;
+    /// msg.set_field(Field::SystemTraceAuditNumber, FieldValue::from_string("000001"))?;
+    ///
+    /// assert_eq!(
+    ///     msg.sensitive_fields(),
+    ///     vec![Field::PrimaryAccountNumber, Field::ExpirationDate]
+    /// );
+    /// # Ok::<(), ISO8583Error>(())
+    /// ```
+    pub fn sensitive_fields(&self) -> Vec<Field> {
+        self.get_field_numbers()
+            .into_iter()
+            .filter_map(|num| Field::from_number(num).ok())
+            .filter(|field| field.is_cardholder_data())
+            .collect()
+    }
+
+    /// Wipe and remove PAN, track data and PIN block fields
+    ///
+    /// Each value's buffer is zeroed before it is released. With the
+    /// `zeroize` feature this also happens automatically when the message is
+    /// dropped.
+    #[cfg(feature = "zeroize")]
+    pub fn zeroize_sensitive(&mut self) {
+        use zeroize::Zeroize;
+
+        let sensitive: Vec<u8> = self
+            .fields
+            .keys()
+            .copied()
+            .filter(|&num| Field::from_number(num).is_ok_and(|field| field.is_sensitive()))
+            .collect();
+
+        for field_num in sensitive {
+            if let Some(mut value) = self.fields.remove(&field_num) {
+                value.zeroize();
+            }
+            let _ = self.bitmap.clear(field_num);
+        }
+    }
+
+    /// Remove every field numbered above `max`
+    ///
+    /// For forwarding to hosts that only understand part of the field range.
+    /// The bitmap is rebuilt from the remaining fields, so with no field above
+    /// 64 left, the secondary bitmap (and the field 1 indicator) is dropped and
+    /// [`to_bytes`](Self::to_bytes) emits a single 8-byte bitmap.
+    pub fn truncate_to_field(&mut self, max: u8) {
+        self.fields.retain(|&num, _| num <= max);
+        self.unknown_fields.retain(|&num, _| num <= max);
+
+        self.bitmap = Bitmap::new();
+        for &num in self.fields.keys().chain(self.unknown_fields.keys()) {
+            if num != 1 && num != 65 {
+                // Present field numbers are in range, so set cannot fail
+                let _ = self.bitmap.set(num);
+            }
+        }
+    }
+
+    /// Rewrite or drop every field value in place
+    ///
+    /// `f` is called once per present field in field-number order. Returning
+    /// `Some(value)` replaces the field, returning `None` removes it and
+    /// clears its bitmap bit. Undefined fields captured in lenient mode are
+    /// left untouched.
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::field::FieldValue;
+    /// use iso8583_core::*;
+    ///
+    /// let mut msg = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+    /// msg.set_field(Field::PrimaryAccountNumber, FieldValue::from_string("4111111111111111"))?;
+    /// msg.set_field(Field::Track2Data, FieldValue::from_string("4111111111111111=2512"))?;
+    ///
+    /// msg.map_fields(|field, value| match field {
+    ///     Field::PrimaryAccountNumber => Some(FieldValue::from_string("TOKEN0000001")),
+    ///     Field::Track2Data => None,
+    ///     _ => Some(value.clone()),
+    /// });
+    ///
+    /// assert!(!msg.has_field(Field::Track2Data));
+    /// assert!(!msg.bitmap().is_set(35));
+    /// # Ok::<(), ISO8583Error>(())
+    /// ```
+    pub fn map_fields<F: FnMut(Field, &FieldValue) -> Option<FieldValue>>(&mut self, mut f: F) {
+        for field_num in self.get_field_numbers() {
+            let Ok(field) = Field::from_number(field_num) else {
+                continue;
+            };
+
+            let mapped = match self.fields.get(&field_num) {
+                Some(value) => f(field, value),
+                None => continue,
+            };
+
+            match mapped {
+                Some(value) => {
+                    self.fields.insert(field_num, value);
+                }
+                None => {
+                    let _ = self.remove_field(field);
+                }
+            }
+        }
+    }
+
+    /// Derive a new message with a different MTI, copying only `keep` fields
+    ///
+    /// Fields listed in `keep` but absent from `self` are skipped, so the
+    /// derived bitmap reflects exactly the fields that were copied.
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::field::FieldValue;
+    /// use iso8583_core::*;
+    ///
+    /// let mut auth = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+    /// auth.set_field(Field::PrimaryAccountNumber, FieldValue::from_string("4111111111111111"))?;
+    /// auth.set_field(Field::TransactionAmount, FieldValue::from_string("000000001000"))?;
+    /// auth.set_field(Field::Track2Data, FieldValue::from_string("4111111111111111=2512"))?;
+    ///
+    /// let capture = auth.derive(
+    ///     MessageType::FINANCIAL_ADVICE,
+    ///     &[Field::PrimaryAccountNumber, Field::TransactionAmount],
+    /// )?;
+    /// assert_eq!(capture.mti, MessageType::FINANCIAL_ADVICE);
+    /// assert_eq!(capture.get_field_numbers(), vec![2, 4]);
+    /// # Ok::<(), ISO8583Error>(())
+    /// ```
+    pub fn derive(&self, new_mti: MessageType, keep: &[Field]) -> Result<ISO8583Message> {
+        new_mti.check()?;
+
+        let mut derived = ISO8583Message::new(new_mti);
+        for &field in keep {
+            if let Some(value) = self.get_field(field) {
+                derived.set_field(field, value.clone())?;
+            }
+        }
+
+        Ok(derived)
+    }
+
+    /// Copy this message with a new MTI and the next STAN from `stan_provider`
+    ///
+    /// Every field is kept except field 11, which is overwritten with the
+    /// new STAN zero-padded to 6 digits. `self` is left untouched.
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::field::FieldValue;
+    /// use iso8583_core::stan::SequentialStan;
+    /// use iso8583_core::*;
+    ///
+    /// let mut auth = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+    /// auth.set_field(Field::SystemTraceAuditNumber, FieldValue::from_string("000041"))?;
+    ///
+    /// let advice = auth.resend_as(MessageType::AUTHORIZATION_ADVICE, &SequentialStan::new(42));
+    /// assert_eq!(advice.mti, MessageType::AUTHORIZATION_ADVICE);
+    /// assert_eq!(
+    ///     advice.get_field(Field::SystemTraceAuditNumber).unwrap().as_string(),
+    ///     Some("000042")
+    /// );
+    /// # Ok::<(), ISO8583Error>(())
+    /// ```
+    pub fn resend_as(&self, mti: MessageType, stan_provider: &dyn StanProvider) -> ISO8583Message {
+        let mut message = self.clone();
+        message.mti = mti;
+
+        let stan = stan_provider.next_stan() % (MAX_STAN + 1);
+        // A 6-digit numeric value always fits field 11
+        let _ = message.set_field(
+            Field::SystemTraceAuditNumber,
+            FieldValue::from_string(format!("{:06}", stan)),
+        );
+        message
+    }
+
+    /// Copy fields from `other` into this message, overwriting existing values
+    ///
+    /// With `Some(fields)`, only those fields are copied (fields absent from
+    /// `other` are skipped). With `None`, every field present in `other` is
+    /// copied, including undefined fields captured in lenient mode. The MTI
+    /// is left unchanged.
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::field::FieldValue;
+    /// use iso8583_core::*;
+    ///
+    /// let mut request = ISO8583Message::new(MessageType::FINANCIAL_ADVICE);
+    /// request.set_field(Field::TransactionAmount, FieldValue::from_string("000000001000"))?;
+    ///
+    /// let mut update = ISO8583Message::new(MessageType::FINANCIAL_ADVICE_RESPONSE);
+    /// update.set_field(Field::ResponseCode, FieldValue::from_string("00"))?;
+    ///
+    /// request.merge_from(&update, None)?;
+    /// assert_eq!(request.get_field_numbers(), vec![4, 39]);
+    /// # Ok::<(), ISO8583Error>(())
+    /// ```
+    pub fn merge_from(&mut self, other: &ISO8583Message, fields: Option<&[Field]>) -> Result<()> {
+        match fields {
+            Some(fields) => {
+                for &field in fields {
+                    if let Some(value) = other.get_field(field) {
+                        self.unknown_fields.remove(&field.number());
+                        self.set_field(field, value.clone())?;
+                    }
+                }
+            }
+            None => {
+                for (&field_num, value) in &other.fields {
+                    self.unknown_fields.remove(&field_num);
+                    self.bitmap.set(field_num)?;
+                    self.fields.insert(field_num, value.clone());
+                }
+                for (&field_num, raw) in &other.unknown_fields {
+                    self.fields.remove(&field_num);
+                    self.bitmap.set(field_num)?;
+                    self.unknown_fields.insert(field_num, raw.clone());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Fill every mandatory field of `profile` that is absent with a
+    /// placeholder value
+    ///
+    /// Placeholders come from [`FieldDefinition::default_value`] of this
+    /// message's own definitions, so an attached [`RuntimeSpec`] is honored.
+    /// Intended for stubbing responses; existing fields are left untouched.
+    /// The counterpart of [`missing_required`](Self::missing_required).
+    pub fn fill_missing_required(&mut self, profile: &MtiProfile) -> Result<()> {
+        for field in self.missing_required(profile) {
+            let value = self.field_definition(field).default_value();
+            self.set_field(field, value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Check if field is present
+    pub fn has_field(&self, field: Field) -> bool {
+        self.fields.contains_key(&field.number())
+    }
+
+    /// Presence of each field as a fixed-shape vector indexed by field number
+    ///
+    /// Index 0 is always `false`; `mask[n]` equals `has_field` for field `n`.
+    /// Undefined fields captured in lenient mode are not included.
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::field::FieldValue;
+    /// use iso8583_core::*;
+    ///
+    /// let mut msg = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+    /// msg.set_field(Field::ProcessingCode, FieldValue::from_string("000000"))?;
+    ///
+    /// let mask = msg.field_presence_mask();
+    /// assert!(mask[3]);
+    /// assert!(!mask[4]);
+    /// # Ok::<(), ISO8583Error>(())
+    /// ```
+    pub fn field_presence_mask(&self) -> [bool; 129] {
+        let mut mask = [false; 129];
+        for &field_num in self.fields.keys() {
+            if let Some(slot) = mask.get_mut(field_num as usize) {
+                *slot = true;
+            }
+        }
+        mask
+    }
+
+    /// Get Track 1 data (field 45) parsed into its components
+    pub fn get_track1(&self) -> Result<crate::utils::Track1> {
+        let data = self
+            .get_field(Field::Track1Data)
+            .ok_or(ISO8583Error::FieldNotPresent(45))?
+            .as_string()
+            .ok_or_else(|| {
+                ISO8583Error::invalid_field_value(45, "Track 1 must be a string value")
+            })?;
+
+        crate::utils::Track1::parse(data)
+    }
+
+    /// Get the POS entry mode (field 22)
+    pub fn get_pos_entry_mode(&self) -> Result<PosEntryMode> {
+        self.get_field(Field::PointOfServiceEntryMode)
+            .ok_or(ISO8583Error::FieldNotPresent(22))?
+            .as_string()
+            .ok_or_else(|| {
+                ISO8583Error::invalid_field_value(22, "POS entry mode must be a string value")
+            })?
+            .parse()
+    }
+
+    /// Runtime field table the message was parsed with, if any
+    pub fn runtime_spec(&self) -> Option<&RuntimeSpec> {
+        self.spec.as_deref()
+    }
+
+    /// Attach (or detach) a runtime field table
+    ///
+    /// The spec's definitions then govern how fields are validated in
+    /// [`set_field`](Self::set_field) and encoded by [`to_bytes`](Self::to_bytes).
+    /// Values already set are not re-checked: one the spec's wire encoding
+    /// cannot carry (e.g. a non-digit in a BCD field) is substituted by
+    /// `to_bytes` and reported by [`try_to_bytes`](Self::try_to_bytes).
+    pub fn set_runtime_spec(&mut self, spec: Option<RuntimeSpec>) {
+        self.spec = spec.map(Box::new);
+    }
+
+    /// Definition used to encode a field
+    ///
+    /// Comes from the attached [`RuntimeSpec`] when it defines the field,
+    /// otherwise from the built-in table.
+    pub fn field_definition(&self, field: Field) -> FieldDefinition {
+        let num = field.number();
+        self.spec
+            .as_deref()
+            .and_then(|spec| spec.get_field(num))
+            .map(|def| FieldDefinition::from_spec(num, def))
+            .unwrap_or_else(|| field.definition())
+    }
+
+    /// Get a subfield of a structured field (e.g. field 48)
+    ///
+    /// Only fields the message's [`RuntimeSpec`] marks as structured can be
+    /// split; for anything else this fails rather than guessing, so free
+    /// text is never mis-read as subfields. [`get_field`](Self::get_field)
+    /// returns the raw value in both cases.
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::field::FieldValue;
+    /// use iso8583_core::*;
+    ///
+    /// let mut spec = RuntimeSpec::new();
+    /// spec.set_structured(48, true);
+    ///
+    /// let mut msg = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+    /// msg.set_runtime_spec(Some(spec));
+    /// msg.set_field(Field::AdditionalDataPrivate, FieldValue::from_string("1005HELLO"))?;
+    ///
+    /// assert_eq!(msg.get_subfield(Field::AdditionalDataPrivate, 10)?, Some("HELLO"));
+    /// # Ok::<(), ISO8583Error>(())
+    /// ```
+    pub fn get_subfield(&self, field: Field, tag: u8) -> Result<Option<&str>> {
+        let num = field.number();
+        if !self
+            .spec
+            .as_ref()
+            .is_some_and(|spec| spec.is_structured(num))
+        {
+            return Err(ISO8583Error::invalid_field_value(
+                num,
+                "Field is not structured in the runtime spec",
+            ));
+        }
+
+        let data = self
+            .get_field(field)
+            .ok_or(ISO8583Error::FieldNotPresent(num))?
+            .as_string()
+            .ok_or_else(|| {
+                ISO8583Error::invalid_field_value(num, "Structured field must be text")
+            })?;
+
+        subfield::find(data, num, tag)
+    }
+
+    /// Get the POS condition code (field 25)
+    pub fn get_pos_condition(&self) -> Result<PosConditionCode> {
+        self.get_field(Field::PointOfServiceConditionCode)
+            .ok_or(ISO8583Error::FieldNotPresent(25))?
+            .as_string()
+            .ok_or_else(|| {
+                ISO8583Error::invalid_field_value(25, "POS condition code must be a string value")
+            })?
+            .parse()
+    }
+
+    /// Check if the transaction was card-present
+    ///
+    /// Derived from fields 22, 25, 35 and 45 using [`DefaultCardPresence`];
+    /// see there for the rules. Use
+    /// [`is_card_present_with`](Self::is_card_present_with) to apply a
+    /// network's own rules instead.
+    pub fn is_card_present(&self) -> bool {
+        self.is_card_present_with(&DefaultCardPresence)
+    }
+
+    /// Check if the transaction was card-present under a custom rule set
+    pub fn is_card_present_with(&self, rule: &dyn CardPresenceRule) -> bool {
+        rule.is_card_present(self)
+    }
+
+    /// Get the card acceptor name and location (field 43)
+    ///
+    /// The subfields keep their padding; see [`CardAcceptorNameLocation`].
+    pub fn get_card_acceptor(&self) -> Result<CardAcceptorNameLocation> {
+        self.get_field(Field::CardAcceptorNameLocation)
+            .ok_or(ISO8583Error::FieldNotPresent(43))?
+            .as_string()
+            .ok_or_else(|| {
+                ISO8583Error::invalid_field_value(43, "Card acceptor name/location must be text")
+            })?
+            .parse()
+    }
+
+    /// Get the terminal ID (field 41) without padding
+    ///
+    /// Hosts pad short IDs differently (trailing spaces or leading zeros),
+    /// so both are stripped: `"1234    "` and `"00001234"` both give
+    /// `"1234"`.
+    pub fn terminal_id_trimmed(&self) -> Option<&str> {
+        self.logical_id(Field::CardAcceptorTerminalIdentification)
+    }
+
+    /// Get the merchant ID (field 42) without padding
+    ///
+    /// See [`terminal_id_trimmed`](Self::terminal_id_trimmed).
+    pub fn merchant_id_trimmed(&self) -> Option<&str> {
+        self.logical_id(Field::CardAcceptorIdentificationCode)
+    }
+
+    /// String value with surrounding spaces and leading zeros stripped
+    fn logical_id(&self, field: Field) -> Option<&str> {
+        self.get_field(field)?
+            .as_string()
+            .map(|id| crate::field::trim_leading_zeros(id.trim_matches(' ')))
+    }
+
+    /// Build a partial approval response to this request
+    ///
+    /// The response echoes the request's identifying fields (PAN, processing
+    /// code, dates and times, STAN, institution and acceptor IDs, RRN and
+    /// currency) via [`derive`](Self::derive). Card data, security fields,
+    /// chip data and MACs are not carried over. It sets response code 10 (approved for a partial amount),
+    /// replaces field 4 with `approved_amount` and sets field 54 to a single
+    /// [`AmountType::OriginalAmount`] entry carrying the requested field 4
+    /// amount in the field 49 currency, for the field 3 from-account.
+    ///
+    /// `approved_amount` is in minor units and must be positive and below
+    /// the requested amount.
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::field::FieldValue;
+    /// use iso8583_core::*;
+    ///
+    /// let mut request = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+    /// request.set_field(Field::TransactionAmount, FieldValue::from_string("000000010000"))?;
+    /// request.set_field(Field::CurrencyCodeTransaction, FieldValue::from_string("840"))?;
+    ///
+    /// let response = request.to_partial_approval(6000)?;
+    /// assert_eq!(response.get_response_code()?, ResponseCode::APPROVED_PARTIAL_AMOUNT);
+    /// assert_eq!(
+    ///     response.get_field(Field::TransactionAmount).unwrap().as_string(),
+    ///     Some("000000006000")
+    /// );
+    /// assert_eq!(response.get_additional_amounts()?[0].amount, 10000);
+    /// # Ok::<(), ISO8583Error>(())
+    /// ```
+    pub fn to_partial_approval(&self, approved_amount: i64) -> Result<ISO8583Message> {
+        let mti = self.mti.to_response()?;
+
+        let requested: i64 = self
+            .get_field(Field::TransactionAmount)
+            .ok_or(ISO8583Error::FieldNotPresent(4))?
+            .as_string()
+            .and_then(|amount| amount.parse().ok())
+            .ok_or_else(|| ISO8583Error::invalid_field_value(4, "Amount must be numeric"))?;
+        if approved_amount <= 0 || approved_amount >= requested {
+            return Err(ISO8583Error::invalid_field_value(
+                4,
+                format!(
+                    "Partial amount {} must be between 0 and the requested {}",
+                    approved_amount, requested
+                ),
+            ));
+        }
+
+        let currency_code = self
+            .get_field(Field::CurrencyCodeTransaction)
+            .ok_or(ISO8583Error::FieldNotPresent(49))?
+            .as_string()
+            .ok_or_else(|| ISO8583Error::invalid_field_value(49, "Currency code must be text"))?
+            .to_string();
+        let account_type = self
+            .get_field(Field::ProcessingCode)
+            .and_then(|code| code.as_string())
+            .and_then(|code| code.parse::<ProcessingCode>().ok())
+            .map_or(AccountType::Default, |code| code.from_account);
+
+        let mut response = self.derive(mti, PARTIAL_APPROVAL_ECHO)?;
+        response.set_response_code(ResponseCode::APPROVED_PARTIAL_AMOUNT)?;
+        response.set_field(
+            Field::TransactionAmount,
+            FieldValue::from_string(format!("{:012}", approved_amount)),
+        )?;
+        let original = AdditionalAmount {
+            account_type,
+            amount_type: AmountType::OriginalAmount,
+            currency_code,
+            amount: requested,
+        };
+        response.set_field(
+            Field::AdditionalAmounts,
+            FieldValue::from_string(original.to_string()),
+        )?;
+
+        Ok(response)
+    }
+
+    /// Get the additional amounts (field 54), e.g. balances on an inquiry response
+    pub fn get_additional_amounts(&self) -> Result<Vec<AdditionalAmount>> {
+        let data = self
+            .get_field(Field::AdditionalAmounts)
+            .ok_or(ISO8583Error::FieldNotPresent(54))?
+            .as_string()
+            .ok_or_else(|| {
+                ISO8583Error::invalid_field_value(54, "Additional amounts must be a string value")
+            })?;
+        AdditionalAmount::parse_all(data)
+    }
+
+    /// Get the ARQC, ATC, unpredictable number and cryptogram information
+    /// data from field 55
+    ///
+    /// Field 55 may be binary or hex text. Tags nested in template 77 are
+    /// found as well; see [`EmvCryptogramData::from_tlvs`].
+    pub fn emv_cryptogram_data(&self) -> Result<EmvCryptogramData> {
+        let data = match self
+            .get_field(Field::ReservedISO1)
+            .ok_or(ISO8583Error::FieldNotPresent(55))?
+        {
+            FieldValue::Binary(b) => Cow::Borrowed(b.as_slice()),
+            FieldValue::String(s) => Cow::Owned(hex::decode(s).map_err(|e| {
+                ISO8583Error::invalid_field_value(55, format!("Invalid hex EMV data: {}", e))
+            })?),
+        };
+
+        EmvCryptogramData::from_tlvs(&emv::parse_tlvs(&data)?)
+    }
+
+    /// Mark the transaction as a chip fallback
+    ///
+    /// Rewrites field 22 to PAN entry mode 80 (magnetic stripe after a
+    /// failed chip read), keeping the terminal's PIN entry capability.
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::field::FieldValue;
+    /// use iso8583_core::*;
+    ///
+    /// let mut msg = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+    /// msg.set_field(Field::PointOfServiceEntryMode, FieldValue::from_string("051"))?;
+    /// msg.mark_fallback()?;
+    ///
+    /// assert_eq!(msg.get_field(Field::PointOfServiceEntryMode).unwrap().as_string(), Some("801"));
+    /// # Ok::<(), ISO8583Error>(())
+    /// ```
+    pub fn mark_fallback(&mut self) -> Result<()> {
+        let mode = self.get_pos_entry_mode()?.fallback();
+        self.set_field(
+            Field::PointOfServiceEntryMode,
+            FieldValue::from_string(mode.to_string()),
+        )
+    }
+
+    /// Get a signed fee amount (fields 28-31) in minor units
+    ///
+    /// The x+n value is a 'C' (credit, positive) or 'D' (debit, negative)
+    /// sign followed by digits, e.g. `"D00000150"` is -150.
+    pub fn get_fee_amount(&self, field: Field) -> Result<i64> {
+        if field.definition().field_type != FieldType::SignedAmount {
+            return Err(ISO8583Error::invalid_field_value(
+                field.number(),
+                "Not a signed amount field",
+            ));
+        }
+
+        let value = self
+            .get_field(field)
+            .ok_or(ISO8583Error::FieldNotPresent(field.number()))?
+            .as_string()
+            .ok_or_else(|| {
+                ISO8583Error::invalid_field_value(field.number(), "Amount must be a string value")
+            })?;
+
+        crate::field::parse_signed_amount(value).ok_or_else(|| {
+            ISO8583Error::invalid_field_value(
+                field.number(),
+                format!("Invalid signed amount: {}", value),
+            )
+        })
+    }
+
+    /// Get the card BIN: the leading `digits` digits of the PAN (field 2)
+    ///
+    /// Typically 6, or 8 for the extended BIN length. See
+    /// [`utils::extract_bin`](crate::utils::extract_bin).
+    pub fn pan_bin(&self, digits: usize) -> Result<String> {
+        let pan = self
+            .get_field(Field::PrimaryAccountNumber)
+            .ok_or(ISO8583Error::FieldNotPresent(2))?
+            .as_string()
+            .ok_or_else(|| ISO8583Error::invalid_field_value(2, "PAN must be a string value"))?;
+
+        crate::utils::extract_bin(pan, digits)
+    }
+
+    /// Get all present field numbers
+    pub fn get_field_numbers(&self) -> Vec<u8> {
+        let mut numbers: Vec<u8> = self.fields.keys().copied().collect();
+        numbers.sort();
+        numbers
+    }
+
+    /// Set the response code (field 39) from a typed [`ResponseCode`]
+    ///
+    /// The code is stored in its 2-character wire form (e.g. "05"), so the
+    /// leading zero is always preserved.
+    pub fn set_response_code(&mut self, rc: ResponseCode) -> Result<()> {
+        self.set_field(Field::ResponseCode, FieldValue::from_string(rc.to_string()))
+    }
+
+    /// Get the response code (field 39) as a typed [`ResponseCode`]
+    pub fn get_response_code(&self) -> Result<ResponseCode> {
+        let value = self
+            .get_field(Field::ResponseCode)
+            .ok_or(ISO8583Error::FieldNotPresent(39))?;
+
+        let code = value.as_string().ok_or_else(|| {
+            ISO8583Error::invalid_field_value(39, "Response code must be a string value")
+        })?;
+
+        code.parse().map_err(|_| {
+            ISO8583Error::invalid_field_value(39, format!("Invalid response code: {}", code))
+        })
+    }
+
+    /// Recompute fields whose values are derived from other fields
+    ///
+    /// This is opt-in; nothing is derived unless this is called (or the
+    /// builder's [`MessageBuilder::sync_derived_fields`] is used). Derived fields:
+    ///
+    /// - Field 27 (authorizing identification response length) is set to the
+    ///   length of field 38, or removed when field 38 is absent.
+    pub fn sync_derived_fields(&mut self) -> Result<()> {
+        let auth_id_len = self
+            .get_field(Field::AuthorizationIdentificationResponse)
+            .map(|value| match value {
+                FieldValue::String(s) => s.len(),
+                FieldValue::Binary(b) => b.len(),
+            });
+
+        match auth_id_len {
+            Some(len) if len > 9 => {
+                return Err(ISO8583Error::invalid_field_value(
+                    27,
+                    format!("Field 38 length {} does not fit in one digit", len),
+                ));
+            }
+            Some(len) => {
+                self.set_field(
+                    Field::AuthorizingIdentificationResponseLength,
+                    FieldValue::from_string(len.to_string()),
+                )?;
+            }
+            None => {
+                if self.has_field(Field::AuthorizingIdentificationResponseLength) {
+                    self.remove_field(Field::AuthorizingIdentificationResponseLength)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the raw bytes of an undefined field captured in lenient mode
+    pub fn get_unknown_field(&self, field_num: u8) -> Option<&[u8]> {
+        self.unknown_fields
+            .get(&field_num)
+            .map(|raw| raw.as_slice())
+    }
+
+    /// Undefined fields captured in lenient mode (raw wire bytes by field number)
+    pub fn unknown_fields(&self) -> &HashMap<u8, Vec<u8>> {
+        &self.unknown_fields
+    }
+
+    /// Every mandatory field of `profile` that is absent, in numeric order
+    ///
+    /// Unlike [`Validator::validate_required_fields`](crate::validation::Validator::validate_required_fields),
+    /// which stops at the first gap, this lists all of them.
+    pub fn missing_required(&self, profile: &MtiProfile) -> Vec<Field> {
+        let mut missing: Vec<Field> = profile
+            .mandatory
+            .iter()
+            .copied()
+            .filter(|&field| !self.has_field(field))
+            .collect();
+        missing.sort_unstable_by_key(|field| field.number());
+        missing.dedup();
+        missing
+    }
+
+    /// Check that the bitmap and the stored fields agree
+    ///
+    /// Every data bit set in the bitmap must have a stored value (defined
+    /// or undefined), and every stored value must have its bit set.
+    /// Indicator bits (1 and 65) are not checked. This is an internal
+    /// invariant; a failure means the message was built inconsistently.
+    pub fn validate_bitmap_consistency(&self) -> Result<()> {
+        for field in (2..=192u8).filter(|&field| field != 65) {
+            let in_bitmap = self.bitmap.is_set(field);
+            let stored =
+                self.fields.contains_key(&field) || self.unknown_fields.contains_key(&field);
+
+            if in_bitmap && !stored {
+                return Err(ISO8583Error::InvalidBitmap(format!(
+                    "Field {} is set in the bitmap but has no value",
+                    field
+                )));
+            }
+            if stored && !in_bitmap {
+                return Err(ISO8583Error::InvalidBitmap(format!(
+                    "Field {} has a value but is not set in the bitmap",
+                    field
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get bitmap reference
+    pub fn bitmap(&self) -> &Bitmap {
+        &self.bitmap
+    }
+
+    /// Compare the fields present in two messages
+    ///
+    /// Returns `(only_in_self, only_in_other)` as bitmaps of data fields.
+    /// Values are not compared, only presence.
+    pub fn bitmap_diff(&self, other: &Self) -> (Bitmap, Bitmap) {
+        (
+            self.bitmap.difference(&other.bitmap),
+            other.bitmap.difference(&self.bitmap),
+        )
+    }
+
+    /// Create a builder for constructing messages
+    pub fn builder() -> MessageBuilder {
+        MessageBuilder::new()
+    }
+
+    /// Create a builder for a POS purchase (0200) with the standard field set
+    ///
+    /// `amount` is in minor units and `currency` is the ISO 4217 numeric
+    /// code. Pre-populates fields 2, 3 (purchase), 4, 7, 11, 12, 13,
+    /// 18 (5999, miscellaneous retail), 22 (chip, PIN capable),
+    /// 25 (normal presentment), 41, 42 and 49. Fields 7, 12 and 13 are taken
+    /// from the current UTC time and 11 from [`generate_stan`](crate::utils::generate_stan).
+    /// Any field can be overridden on the returned builder.
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::*;
+    ///
+    /// let msg = ISO8583Message::pos_purchase("4111111111111111", 7550, "840", "POS12345", "MERCHANT0000001")
+    ///     .field(Field::MerchantType, "5812") // Restaurant
+    ///     .build()?;
+    ///
+    /// assert_eq!(msg.get_field(Field::TransactionAmount).unwrap().as_string(), Some("000000007550"));
+    /// # Ok::<(), ISO8583Error>(())
+    /// ```
+    pub fn pos_purchase(
+        pan: &str,
+        amount: u64,
+        currency: &str,
+        terminal_id: &str,
+        merchant_id: &str,
+    ) -> MessageBuilder {
+        Self::financial_preset("000000", pan, amount, currency, terminal_id, merchant_id)
+            .field(Field::MerchantType, "5999")
+            .field(Field::PointOfServiceConditionCode, "00")
+    }
+
+    /// Create a builder for an ATM cash withdrawal (0200) with the standard field set
+    ///
+    /// Same field set as [`pos_purchase`](Self::pos_purchase), with
+    /// processing code 010000 (withdrawal), merchant type 6011 (ATM) and
+    /// POS condition code 02 (unattended terminal).
+    pub fn atm_withdrawal(
+        pan: &str,
+        amount: u64,
+        currency: &str,
+        terminal_id: &str,
+        merchant_id: &str,
+    ) -> MessageBuilder {
+        Self::financial_preset("010000", pan, amount, currency, terminal_id, merchant_id)
+            .field(Field::MerchantType, "6011")
+            .field(Field::PointOfServiceConditionCode, "02")
+    }
+
+    /// Fields shared by the financial request presets
+    fn financial_preset(
+        processing_code: &str,
+        pan: &str,
+        amount: u64,
+        currency: &str,
+        terminal_id: &str,
+        merchant_id: &str,
+    ) -> MessageBuilder {
+        let now = chrono::Utc::now();
+
+        MessageBuilder::new()
+            .mti(MessageType::FINANCIAL_REQUEST)
+            .field(Field::PrimaryAccountNumber, pan)
+            .field(Field::ProcessingCode, processing_code)
+            .field(Field::TransactionAmount, format!("{:012}", amount))
+            .field(
+                Field::TransmissionDateTime,
+                now.format("%m%d%H%M%S").to_string(),
+            )
+            .field(Field::SystemTraceAuditNumber, crate::utils::generate_stan())
+            .field(
+                Field::LocalTransactionTime,
+                now.format("%H%M%S").to_string(),
+            )
+            .field(Field::LocalTransactionDate, now.format("%m%d").to_string())
+            .field(Field::PointOfServiceEntryMode, "051")
+            .field(Field::CardAcceptorTerminalIdentification, terminal_id)
+            .field(Field::CardAcceptorIdentificationCode, merchant_id)
+            .field(Field::CurrencyCodeTransaction, currency)
+    }
+
+    /// Create a builder pre-populated with valid mandatory fields
+    ///
+    /// Fills fields 3, 7, 11, 12 and 13; financial and authorization
+    /// requests also get a Luhn-valid test PAN (2) and an amount (4), and
+    /// responses get an approval response code (39). Override only the
+    /// fields a test cares about.
+    #[cfg(any(test, feature = "testing"))]
+    #[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+    pub fn test_builder(mti: MessageType) -> MessageBuilder {
+        use crate::mti::MessageClass;
+
+        let mut builder = MessageBuilder::new()
+            .mti(mti)
+            .field(Field::ProcessingCode, "000000")
+            .field(Field::TransmissionDateTime, "0101120000")
+            .field(Field::SystemTraceAuditNumber, "000001")
+            .field(Field::LocalTransactionTime, "120000")
+            .field(Field::LocalTransactionDate, "0101");
+
+        if mti.is_request()
+            && matches!(
+                mti.class,
+                MessageClass::Financial | MessageClass::Authorization
+            )
+        {
+            builder = builder
+                .field(Field::PrimaryAccountNumber, "4111111111111111")
+                .field(Field::TransactionAmount, "000000001000");
+        }
+
+        if mti.is_response() {
+            builder = builder.field(Field::ResponseCode, "00");
+        }
+
+        builder
+    }
+}
+
+/// Parsed message whose field values borrow from the input buffer
+///
+/// Parsing into a view allocates no per-field strings or buffers; call
+/// [`into_message`](Self::into_message) (or [`FieldValueRef::into_owned`] on
+/// individual values) only when owned data is needed.
+///
+/// # Example
+/// ```
+/// use iso8583_core::field::FieldValue;
+/// use iso8583_core::*;
+///
+/// let mut msg = ISO8583Message::new(MessageType::NETWORK_MANAGEMENT_REQUEST);
+/// msg.set_field(Field::SystemTraceAuditNumber, FieldValue::from_string("000001"))?;
+/// msg.set_field(Field::NetworkManagementInformationCode, FieldValue::from_string("301"))?;
+/// let bytes = msg.to_bytes();
+///
+/// let view = MessageView::parse(&bytes)?;
+/// let stan = view.get_field(Field::SystemTraceAuditNumber).unwrap();
+/// assert!(stan.is_borrowed());
+/// assert_eq!(stan.as_string(), Some("000001"));
+/// assert_eq!(view.into_message(), msg);
+/// # Ok::<(), ISO8583Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MessageView<'a> {
+    /// Message Type Indicator
+    pub mti: MessageType,
+    fields: HashMap<u8, FieldValueRef<'a>>,
+    unknown_fields: HashMap<u8, &'a [u8]>,
+    bitmap: Bitmap,
+    spec: Option<RuntimeSpec>,
+}
+
+impl<'a> MessageView<'a> {
+    /// Parse a view over message bytes (ASCII encoding)
+    pub fn parse(bytes: &'a [u8]) -> Result<Self> {
+        Self::parse_with_options(bytes, &ParseOptions::default())
+    }
+
+    /// Parse a view with explicit [`ParseOptions`]
+    ///
+    /// See [`ISO8583Message::from_bytes_with_options`].
+    pub fn parse_with_options(bytes: &'a [u8], options: &ParseOptions) -> Result<Self> {
+        Self::parse_counted(bytes, options).map(|(view, _)| view)
+    }
+
+    /// Parse a view, also returning the number of bytes consumed
+    pub(crate) fn parse_counted(bytes: &'a [u8], options: &ParseOptions) -> Result<(Self, usize)> {
+        // 1. Parse MTI and bitmap(s)
+        let (mti, bitmap, mut offset) = ISO8583Message::parse_header_with_options(bytes, options)?;
+
+        // 2. Parse fields based on bitmap
+        let mut fields = HashMap::new();
+        let mut unknown_fields = HashMap::new();
+        let (field_array, field_count) = bitmap.get_set_fields();
+        // Start offset and definition of each field, for the desync check
+        let mut parsed = Vec::new();
+
+        for item in field_array.iter().take(field_count) {
+            let field_num = *item;
+            if field_num == 1 || field_num == 65 {
+                continue; // Skip bitmap indicators
+            }
+
+            let spec_def = options.definition_for(mti.version, field_num);
+
+            if options.lenient && spec_def.is_none() {
+                let def = FieldDefinition {
+                    number: field_num,
+                    name: "Unknown",
+                    field_type: FieldType::Binary,
+                    length: options.unknown_field_length,
+                    description: "Unknown field",
+                };
+                let (_, bytes_consumed) = ISO8583Message::parse_field(&bytes[offset..], &def)?;
+                unknown_fields.insert(field_num, &bytes[offset..offset + bytes_consumed]);
+                offset += bytes_consumed;
+                continue;
+            }
+
+            let field = Field::from_number(field_num)?;
+            let mut def = spec_def.unwrap_or_else(|| field.definition());
+            if field_num == 38 && options.auth_id_length_from_field_27 {
+                // Field 27 precedes 38 on the wire, so it is already parsed
+                if let Some(len) = fields
+                    .get(&27)
+                    .and_then(|len: &FieldValueRef<'_>| len.as_string())
+                    .and_then(|len| len.parse().ok())
+                {
+                    def.length = FieldLength::Fixed(len);
+                }
+            }
+
+            // Parse field based on its length specification and encoding
+            let encoding = options.spec.and_then(|spec| spec.encoding(field_num));
+            let hex_binary = def.field_type == FieldType::Binary
+                && matches!(def.length, FieldLength::Fixed(_))
+                && options
+                    .spec
+                    .is_some_and(|spec| spec.binary_encoding(field_num) == BinaryEncoding::Hex);
+            let result = match encoding {
+                _ if hex_binary => ISO8583Message::parse_hex_field(&bytes[offset..], &def),
+                Some(encoding) if def.field_type != FieldType::Binary => {
+                    ISO8583Message::parse_encoded_field(&bytes[offset..], &def, encoding)
+                }
+                _ => ISO8583Message::parse_field(&bytes[offset..], &def),
+            };
+            let (value, bytes_consumed) = match result {
+                Ok(parsed_field) => parsed_field,
+                Err(e) if options.check_desync => {
+                    return Err(Self::desync_error(
+                        &parsed,
+                        &fields,
+                        (field_num, offset),
+                        e.to_string(),
+                    ))
+                }
+                Err(e) => return Err(e),
+            };
+            if options.check_desync {
+                parsed.push((field_num, offset, def));
+            }
+            if options
+                .spec
+                .is_some_and(|spec| spec.is_structured(field_num))
+            {
+                let data = value.as_string().ok_or_else(|| {
+                    ISO8583Error::invalid_field_value(field_num, "Structured field must be text")
+                })?;
+                subfield::parse(data, field_num)?;
+            }
+            fields.insert(field_num, value);
+            offset += bytes_consumed;
+        }
+
+        if options.check_desync && offset != bytes.len() {
+            let last = parsed
+                .last()
+                .map_or((0, offset), |&(num, start, _)| (num, start));
+            return Err(Self::desync_error(
+                &parsed,
+                &fields,
+                last,
+                format!("{} bytes left after the last field", bytes.len() - offset),
+            ));
+        }
+
+        let view = Self {
+            mti,
+            fields,
+            unknown_fields,
+            bitmap,
+            spec: options.resolved_spec(mti.version),
+        };
+        Ok((view, offset))
+    }
+
+    /// Build a desync error pointing at the first field whose content does
+    /// not match its definition, or at `fallback` if every field looks valid
+    fn desync_error(
+        parsed: &[(u8, usize, FieldDefinition)],
+        fields: &HashMap<u8, FieldValueRef<'a>>,
+        fallback: (u8, usize),
+        reason: String,
+    ) -> ISO8583Error {
+        let suspect = parsed.iter().find_map(|(num, start, def)| {
+            let value = FieldValue::from(fields.get(num)?);
+            def.validate(&value).err().map(|e| {
+                (
+                    *num,
+                    *start,
+                    format!("{}; first invalid field: {}", reason, e),
+                )
+            })
+        });
+        let (field, offset, reason) = suspect.unwrap_or((fallback.0, fallback.1, reason));
+
+        ISO8583Error::Desync {
+            field,
+            offset,
+            reason,
+        }
+    }
+
+    /// Get a borrowed field value
+    pub fn get_field(&self, field: Field) -> Option<&FieldValueRef<'a>> {
+        self.fields.get(&field.number())
+    }
+
+    /// Check if a field is present
+    pub fn has_field(&self, field: Field) -> bool {
+        self.fields.contains_key(&field.number())
+    }
+
+    /// Get all present field numbers
+    pub fn get_field_numbers(&self) -> Vec<u8> {
+        let mut numbers: Vec<u8> = self.fields.keys().copied().collect();
+        numbers.sort();
+        numbers
+    }
+
+    /// Get the raw bytes of an undefined field captured in lenient mode
+    pub fn get_unknown_field(&self, field_num: u8) -> Option<&'a [u8]> {
+        self.unknown_fields.get(&field_num).copied()
+    }
+
+    /// Get bitmap reference
+    pub fn bitmap(&self) -> &Bitmap {
+        &self.bitmap
+    }
+
+    /// Copy every value into an owned [`ISO8583Message`]
+    pub fn into_message(self) -> ISO8583Message {
+        ISO8583Message {
+            mti: self.mti,
+            fields: self
+                .fields
+                .into_iter()
+                .map(|(num, value)| (num, value.into_owned()))
+                .collect(),
+            unknown_fields: self
+                .unknown_fields
+                .into_iter()
+                .map(|(num, raw)| (num, raw.to_vec()))
+                .collect(),
+            bitmap: self.bitmap,
+            spec: self.spec.map(Box::new),
+        }
+    }
+}
+
+/// Serializes as `{"mti": "0200", "fields": {"2": ..., "3": ...}}`
+///
+/// Fields are emitted in ascending numeric order so the output is stable
+/// across runs and suitable for committed fixtures.
+#[cfg(feature = "serde")]
+impl serde::Serialize for ISO8583Message {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let fields: BTreeMap<u8, &FieldValue> = self.fields.iter().map(|(&k, v)| (k, v)).collect();
+
+        let mut state = serializer.serialize_struct("ISO8583Message", 2)?;
+        state.serialize_field("mti", &self.mti.to_string())?;
+        state.serialize_field("fields", &fields)?;
+        state.end()
+    }
+}
+
+/// Builder for ISO 8583 messages
+#[derive(Debug)]
+pub struct MessageBuilder {
+    message: ISO8583Message,
+    sync_derived: bool,
+    /// First error from a deferred setter, reported by `build`
+    error: Option<ISO8583Error>,
+}
+
+impl MessageBuilder {
+    /// Create a new builder
+    pub fn new() -> Self {
+        Self {
+            message: ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST),
+            sync_derived: false,
+            error: None,
+        }
+    }
+
+    /// Create a builder from `(field number, value)` pairs, e.g. to replay a
+    /// captured message
+    ///
+    /// Unlike [`field_num`](Self::field_num), an invalid field number or
+    /// value is reported immediately.
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::field::FieldValue;
+    /// use iso8583_core::*;
+    ///
+    /// let mti = MessageType::NETWORK_MANAGEMENT_REQUEST;
+    /// let builder = MessageBuilder::from_pairs(
+    ///     mti,
+    ///     [
+    ///         (11, FieldValue::from_string("000001")),
+    ///         (70, FieldValue::from_string("301")),
+    ///     ],
+    /// )?;
+    ///
+    /// assert!(MessageBuilder::from_pairs(mti, [(200, FieldValue::from_string("X"))]).is_err());
+    /// # Ok::<(), ISO8583Error>(())
+    /// ```
+    pub fn from_pairs(
+        mti: MessageType,
+        pairs: impl IntoIterator<Item = (u8, FieldValue)>,
+    ) -> Result<Self> {
+        let mut builder = Self::new().mti(mti);
+        for (num, value) in pairs {
+            builder.message.set_field(Field::from_number(num)?, value)?;
+        }
+        Ok(builder)
+    }
+
+    /// Set the MTI
+    pub fn mti(mut self, mti: MessageType) -> Self {
+        self.message.mti = mti;
+        self
+    }
+
+    /// Add a field
+    pub fn field<S: Into<String>>(mut self, field: Field, value: S) -> Self {
+        let _ = self
+            .message
+            .set_field(field, FieldValue::from_string(value.into()));
+        self
+    }
+
+    /// Add a binary field
+    pub fn binary_field(mut self, field: Field, value: Vec<u8>) -> Self {
+        let _ = self
+            .message
+            .set_field(field, FieldValue::from_binary(value));
+        self
+    }
+
+    /// Add a field by number
+    ///
+    /// An invalid field number is reported when [`build`](Self::build) is called.
+    pub fn field_num<S: Into<String>>(self, num: u8, value: S) -> Self {
+        self.value_num(num, FieldValue::from_string(value.into()))
+    }
+
+    /// Add a binary field by number
+    ///
+    /// An invalid field number is reported when [`build`](Self::build) is called.
+    pub fn binary_field_num(self, num: u8, value: Vec<u8>) -> Self {
+        self.value_num(num, FieldValue::from_binary(value))
+    }
+
+    fn value_num(mut self, num: u8, value: FieldValue) -> Self {
+        let result = Field::from_number(num).and_then(|field| self.message.set_field(field, value));
+        if let Err(e) = result {
+            self.error.get_or_insert(e);
+        }
+        self
+    }
+
+    /// Recompute derived fields when building
+    ///
+    /// See [`ISO8583Message::sync_derived_fields`] for the fields affected.
+    pub fn sync_derived_fields(mut self) -> Self {
+        self.sync_derived = true;
+        self
+    }
+
+    /// Build the message
+    pub fn build(mut self) -> Result<ISO8583Message> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+
+        self.message.mti.check()?;
+
+        if self.sync_derived {
+            self.message.sync_derived_fields()?;
+        }
+
+        // Validate the message
+        crate::validation::Validator::validate_required_fields(&self.message)?;
+
+        Ok(self.message)
+    }
+
+    /// Build the message without any validation
+    ///
+    /// The MTI is not checked, required fields are not enforced and errors
+    /// from deferred setters such as [`field_num`](Self::field_num) are
+    /// discarded (the offending field is simply absent). Derived fields are
+    /// still synced if requested, on a best-effort basis.
+    ///
+    /// The result may well be an invalid message. This is meant for negative
+    /// testing against a peer and for passthrough proxies; use
+    /// [`build`](Self::build) everywhere else.
+    pub fn build_unchecked(mut self) -> ISO8583Message {
+        if self.sync_derived {
+            let _ = self.message.sync_derived_fields();
+        }
+        self.message
+    }
+}
+
+impl Default for MessageBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_creation() {
+        let msg = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+        assert_eq!(msg.mti, MessageType::AUTHORIZATION_REQUEST);
+        assert_eq!(msg.get_field_numbers().len(), 0);
+    }
+
+    #[test]
+    fn test_set_and_get_field() {
+        let mut msg = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+
+        msg.set_field(
+            Field::PrimaryAccountNumber,
+            FieldValue::from_string("4111111111111111"),
+        )
+        .unwrap();
+
+        assert!(msg.has_field(Field::PrimaryAccountNumber));
+        let value = msg.get_field(Field::PrimaryAccountNumber).unwrap();
+        assert_eq!(value.as_string(), Some("4111111111111111"));
+    }
+
+    #[test]
+    fn test_remove_field() {
+        let mut msg = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+
+        msg.set_field(
+            Field::PrimaryAccountNumber,
+            FieldValue::from_string("4111111111111111"),
+        )
+        .unwrap();
+
+        assert!(msg.has_field(Field::PrimaryAccountNumber));
+
+        msg.remove_field(Field::PrimaryAccountNumber).unwrap();
+
+        assert!(!msg.has_field(Field::PrimaryAccountNumber));
+    }
+
+    #[test]
+    fn test_response_code_roundtrip() {
+        let mut msg = ISO8583Message::new(MessageType::AUTHORIZATION_RESPONSE);
+        assert_eq!(
+            msg.get_response_code(),
+            Err(ISO8583Error::FieldNotPresent(39))
+        );
+
+        msg.set_response_code(ResponseCode::DO_NOT_HONOR).unwrap();
+        assert_eq!(
+            msg.get_field(Field::ResponseCode).unwrap().as_string(),
+            Some("05")
+        );
+        assert_eq!(msg.get_response_code().unwrap(), ResponseCode::DO_NOT_HONOR);
+
+        msg.set_field(Field::ResponseCode, FieldValue::from_string("X1"))
+            .unwrap();
+        assert!(msg.get_response_code().is_err());
+    }
+
+    #[test]
+    fn test_lenient_parse_captures_undefined_fields() {
+        let mut msg = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+        msg.set_field(
+            Field::SystemTraceAuditNumber,
+            FieldValue::from_string("123456"),
+        )
+        .unwrap();
+        msg.set_field(Field::ReservedPrivate1, FieldValue::from_string("ABC"))
+            .unwrap();
+        let bytes = msg.to_bytes();
+
+        // Strict parsing keeps the field as a regular value
+        let strict = ISO8583Message::from_bytes(&bytes).unwrap();
+        assert!(strict.has_field(Field::ReservedPrivate1));
+        assert!(strict.unknown_fields().is_empty());
+
+        let lenient =
+            ISO8583Message::from_bytes_with_options(&bytes, &ParseOptions::lenient()).unwrap();
+        assert!(!lenient.has_field(Field::ReservedPrivate1));
+        assert_eq!(lenient.get_unknown_field(60), Some(&b"003ABC"[..]));
+        assert_eq!(
+            lenient
+                .get_field(Field::SystemTraceAuditNumber)
+                .unwrap()
+                .as_string(),
+            Some("123456")
+        );
+        assert_eq!(lenient.to_bytes(), bytes);
+    }
+
+    #[test]
+    fn test_lenient_parse_with_custom_length() {
+        let mut msg = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+        msg.set_field(Field::ReservedPrivate1, FieldValue::from_string("ABC"))
+            .unwrap();
+        msg.set_field(
+            Field::SystemTraceAuditNumber,
+            FieldValue::from_string("123456"),
+        )
+        .unwrap();
+        let bytes = msg.to_bytes();
+
+        // Pretend the partner sends field 60 as fixed 6 bytes ("003ABC")
+        let options = ParseOptions::lenient().unknown_field_length(FieldLength::Fixed(6));
+        let parsed = ISO8583Message::from_bytes_with_options(&bytes, &options).unwrap();
+        assert_eq!(parsed.get_unknown_field(60), Some(&b"003ABC"[..]));
+    }
+
+    #[test]
+    fn test_secondary_bitmap_roundtrip() {
+        let mut msg = ISO8583Message::new(MessageType::NETWORK_MANAGEMENT_REQUEST);
+        msg.set_field(
+            Field::SystemTraceAuditNumber,
+            FieldValue::from_string("000001"),
+        )
+        .unwrap();
+        msg.set_field(
+            Field::NetworkManagementInformationCode,
+            FieldValue::from_string("301"),
+        )
+        .unwrap();
+
+        let bytes = msg.to_bytes();
+        let parsed = ISO8583Message::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            parsed
+                .get_field(Field::NetworkManagementInformationCode)
+                .unwrap()
+                .as_string(),
+            Some("301")
+        );
+        assert_eq!(parsed, msg);
+
+        // Secondary bitmap indicated but cut off, inside or right after the primary
+        assert_eq!(
+            ISO8583Message::from_bytes(&bytes[..16]),
+            Err(ISO8583Error::MissingSecondaryBitmap)
+        );
+        assert_eq!(
+            ISO8583Message::from_bytes(&bytes[..12]),
+            Err(ISO8583Error::MissingSecondaryBitmap)
+        );
+        // Cut off in the field data instead
+        assert!(matches!(
+            ISO8583Message::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(ISO8583Error::MessageTooShort { .. } | ISO8583Error::FieldLengthMismatch { .. })
+        ));
+        let hex_options = ParseOptions::new().bitmap_encoding(BitmapEncoding::AsciiHex);
+        let hex_bytes = msg.to_bytes_with_options(&hex_options).unwrap();
+        assert_eq!(
+            ISO8583Message::from_bytes_with_options(&hex_bytes[..20], &hex_options),
+            Err(ISO8583Error::MissingSecondaryBitmap)
+        );
+    }
+
+    #[test]
+    fn test_tertiary_bitmap_roundtrip() {
+        // Field 65 (tertiary indicator) plus field 150, which has no
+        // definition and is therefore kept raw in lenient mode
+        let mut bitmap = Bitmap::new();
+        bitmap.set(2).unwrap();
+        bitmap.set(65).unwrap();
+        bitmap.set(150).unwrap();
+        let (bitmap_bytes, bitmap_len) = bitmap.to_bytes();
+        assert_eq!(bitmap_len, 24);
+
+        let mut wire = b"0100".to_vec();
+        wire.extend_from_slice(&bitmap_bytes[..bitmap_len]);
+        wire.extend_from_slice(b"164111111111111111");
+        wire.extend_from_slice(b"003abc");
+
+        let msg = ISO8583Message::from_bytes_with_options(&wire, &ParseOptions::lenient()).unwrap();
+        assert!(msg.bitmap().is_set(1));
+        assert!(msg.bitmap().is_set(65));
+        assert!(msg.bitmap().is_set(150));
+        assert_eq!(msg.get_unknown_field(150), Some(&b"003abc"[..]));
+        assert_eq!(msg.to_bytes(), wire);
+
+        // Strict parsing rejects the undefined field
+        assert!(ISO8583Message::from_bytes(&wire).is_err());
+    }
+
+    #[test]
+    fn test_private_version_roundtrip() {
+        let mti: MessageType = "8100".parse().unwrap();
+        let msg = ISO8583Message::builder()
+            .mti(mti)
+            .field(Field::PrimaryAccountNumber, "4111111111111111")
+            .field(Field::ProcessingCode, "000000")
+            .field(Field::TransactionAmount, "000000010000")
+            .field(Field::SystemTraceAuditNumber, "123456")
+            .field(Field::LocalTransactionTime, "120000")
+            .field(Field::LocalTransactionDate, "0219")
+            .build()
+            .unwrap();
+
+        let bytes = msg.to_bytes();
+        assert_eq!(&bytes[..4], b"8100");
+
+        let parsed = ISO8583Message::from_bytes(&bytes).unwrap();
+        assert_eq!(parsed.mti.to_string(), "8100");
+        assert_eq!(parsed, msg);
+    }
+
+    #[test]
+    fn test_version_spec_hook() {
+        fn private_spec(field_num: u8) -> Option<FieldDefinition> {
+            match field_num {
+                // This network sends the STAN as 12 digits
+                11 => Some(FieldDefinition {
+                    number: 11,
+                    name: "System Trace Audit Number",
+                    field_type: FieldType::Numeric,
+                    length: FieldLength::Fixed(12),
+                    description: "Extended STAN",
+                }),
+                _ => None,
+            }
+        }
+
+        let mut bytes = b"9100".to_vec();
+        let mut bitmap = Bitmap::new();
+        bitmap.set(3).unwrap();
+        bitmap.set(11).unwrap();
+        let (bitmap_bytes, len) = bitmap.to_bytes();
+        bytes.extend_from_slice(&bitmap_bytes[..len]);
+        bytes.extend_from_slice(b"000000000000123456");
+
+        let options = ParseOptions::new().version_spec(9, private_spec);
+        let parsed = ISO8583Message::from_bytes_with_options(&bytes, &options).unwrap();
+        assert_eq!(
+            parsed
+                .get_field(Field::SystemTraceAuditNumber)
+                .unwrap()
+                .as_string(),
+            Some("000000123456")
+        );
+        // The 12-digit STAN survives re-encoding
+        assert_eq!(parsed.to_bytes(), bytes);
+        assert_eq!(
+            parsed
+                .field_definition(Field::SystemTraceAuditNumber)
+                .length,
+            FieldLength::Fixed(12)
+        );
+        assert_eq!(
+            parsed.get_field(Field::ProcessingCode).unwrap().as_string(),
+            Some("000000")
+        );
+
+        // The hook only applies to its own version digit
+        bytes[0] = b'0';
+        let parsed = ISO8583Message::from_bytes_with_options(&bytes, &options).unwrap();
+        assert_eq!(
+            parsed
+                .get_field(Field::SystemTraceAuditNumber)
+                .unwrap()
+                .as_string(),
+            Some("000000")
+        );
+        assert_eq!(parsed.runtime_spec(), None);
+    }
+
+    #[test]
+    fn test_hex_roundtrip() {
+        let mut msg = ISO8583Message::new(MessageType::NETWORK_MANAGEMENT_REQUEST);
+        msg.set_field(
+            Field::SystemTraceAuditNumber,
+            FieldValue::from_string("000001"),
+        )
+        .unwrap();
+        msg.set_field(
+            Field::NetworkManagementInformationCode,
+            FieldValue::from_string("301"),
+        )
+        .unwrap();
+
+        let hex = msg.to_hex();
+        assert_eq!(ISO8583Message::from_hex(&hex).unwrap(), msg);
+
+        // Pretty-printed logs with spaces and line breaks
+        let pretty: Vec<String> = hex
+            .as_bytes()
+            .chunks(8)
+            .map(|c| String::from_utf8(c.to_vec()).unwrap())
+            .collect();
+        let pretty = pretty.join(" ").replacen(' ', "\n\t", 2);
+        assert_eq!(ISO8583Message::from_hex(&pretty).unwrap(), msg);
+
+        assert!(matches!(
+            ISO8583Message::from_hex(&hex[1..]),
+            Err(ISO8583Error::InvalidEncoding(reason)) if reason.contains("odd length")
+        ));
+        assert!(matches!(
+            ISO8583Message::from_hex("zz"),
+            Err(ISO8583Error::InvalidEncoding(_))
+        ));
+    }
+
+    #[test]
+    fn test_map_fields() {
+        let mut msg = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+        msg.set_field(
+            Field::PrimaryAccountNumber,
+            FieldValue::from_string("4111111111111111"),
+        )
+        .unwrap();
+        msg.set_field(
+            Field::SystemTraceAuditNumber,
+            FieldValue::from_string("123456"),
+        )
+        .unwrap();
+        msg.set_field(
+            Field::Track1Data,
+            FieldValue::from_string("B4111111111111111^"),
+        )
+        .unwrap();
+        msg.set_field(
+            Field::PersonalIdentificationNumberData,
+            FieldValue::from_binary(vec![0u8; 8]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            msg.sensitive_fields(),
+            vec![
+                Field::PrimaryAccountNumber,
+                Field::Track1Data,
+                Field::PersonalIdentificationNumberData
+            ]
+        );
+
+        let mut visited = Vec::new();
+        msg.map_fields(|field, value| {
+            visited.push(field.number());
+            match field {
+                Field::PrimaryAccountNumber => Some(FieldValue::from_string("9999990000000001")),
+                Field::Track1Data | Field::PersonalIdentificationNumberData => None,
+                _ => Some(value.clone()),
+            }
+        });
+
+        assert_eq!(visited, vec![2, 11, 45, 52]);
+        assert_eq!(msg.get_field_numbers(), vec![2, 11]);
+        assert_eq!(
+            msg.get_field(Field::PrimaryAccountNumber)
+                .unwrap()
+                .as_string(),
+            Some("9999990000000001")
+        );
+        assert!(!msg.bitmap().is_set(45));
+        assert!(!msg.bitmap().is_set(52));
+
+        let parsed = ISO8583Message::from_bytes(&msg.to_bytes()).unwrap();
+        assert_eq!(parsed, msg);
+    }
+
+    #[test]
+    fn test_sync_derived_fields() {
+        let mut msg = ISO8583Message::new(MessageType::AUTHORIZATION_RESPONSE);
+        msg.set_field(
+            Field::AuthorizationIdentificationResponse,
+            FieldValue::from_string("AB12"),
+        )
+        .unwrap();
+        msg.sync_derived_fields().unwrap();
+        assert_eq!(
+            msg.get_field(Field::AuthorizingIdentificationResponseLength)
+                .unwrap()
+                .as_string(),
+            Some("4")
+        );
+
+        msg.remove_field(Field::AuthorizationIdentificationResponse)
+            .unwrap();
+        msg.sync_derived_fields().unwrap();
+        assert!(!msg.has_field(Field::AuthorizingIdentificationResponseLength));
+    }
+
+    #[test]
+    fn test_builder_sync_derived_fields() {
+        let msg = ISO8583Message::builder()
+            .mti(MessageType::AUTHORIZATION_RESPONSE)
+            .field(Field::ProcessingCode, "000000")
+            .field(Field::SystemTraceAuditNumber, "123456")
+            .field(Field::LocalTransactionTime, "120000")
+            .field(Field::LocalTransactionDate, "0101")
+            .field(Field::AuthorizationIdentificationResponse, "123456")
+            .field(Field::ResponseCode, "00")
+            .sync_derived_fields()
+            .build()
+            .unwrap();
+        assert_eq!(
+            msg.get_field(Field::AuthorizingIdentificationResponseLength)
+                .unwrap()
+                .as_string(),
+            Some("6")
+        );
+
+        // Without opting in nothing is derived
+        let msg = ISO8583Message::builder()
+            .mti(MessageType::AUTHORIZATION_RESPONSE)
+            .field(Field::ProcessingCode, "000000")
+            .field(Field::SystemTraceAuditNumber, "123456")
+            .field(Field::LocalTransactionTime, "120000")
+            .field(Field::LocalTransactionDate, "0101")
+            .field(Field::AuthorizationIdentificationResponse, "123456")
+            .field(Field::ResponseCode, "00")
+            .build()
+            .unwrap();
+        assert!(!msg.has_field(Field::AuthorizingIdentificationResponseLength));
+    }
+
+    #[test]
+    fn test_builder_rejects_undefined_mti() {
+        let result = ISO8583Message::builder()
+            .mti(MessageType::new(
+                0,
+                crate::mti::MessageClass::Financial,
+                crate::mti::MessageFunction::Request,
+                crate::mti::MessageOrigin::Reserved7,
+            ))
+            .field(Field::ProcessingCode, "000000")
+            .field(Field::SystemTraceAuditNumber, "123456")
+            .field(Field::LocalTransactionTime, "120000")
+            .field(Field::LocalTransactionDate, "0101")
+            .build();
+        assert!(matches!(result, Err(ISO8583Error::InvalidMessageOrigin(_))));
+    }
+
+    #[test]
+    fn test_test_builder_defaults() {
+        for mti in [
+            MessageType::AUTHORIZATION_REQUEST,
+            MessageType::FINANCIAL_REQUEST,
+            MessageType::FINANCIAL_RESPONSE,
+            MessageType::REVERSAL_REQUEST,
+        ] {
+            assert!(ISO8583Message::test_builder(mti).build().is_ok(), "{}", mti);
+        }
+
+        let msg = ISO8583Message::test_builder(MessageType::FINANCIAL_REQUEST)
+            .field(Field::TransactionAmount, "000000005000")
+            .build()
+            .unwrap();
+        assert_eq!(
+            msg.get_field(Field::TransactionAmount).unwrap().as_string(),
+            Some("000000005000")
+        );
+        assert!(crate::validation::Validator::validate_pan(
+            msg.get_field(Field::PrimaryAccountNumber)
+                .unwrap()
+                .as_string()
+                .unwrap()
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialize_is_deterministic() {
+        let mut msg = ISO8583Message::new(MessageType::FINANCIAL_REQUEST);
+        for (field, value) in [
+            (Field::RetrievalReferenceNumber, "260115000042"),
+            (Field::ProcessingCode, "000000"),
+            (Field::PrimaryAccountNumber, "4111111111111111"),
+            (Field::SystemTraceAuditNumber, "000001"),
+            (Field::TransactionAmount, "000000001000"),
+        ] {
+            msg.set_field(field, FieldValue::from_string(value))
+                .unwrap();
+        }
+        msg.set_field(
+            Field::PersonalIdentificationNumberData,
+            FieldValue::from_binary(vec![0xAB; 8]),
+        )
+        .unwrap();
+
+        let first = serde_json::to_string(&msg).unwrap();
+        let second = serde_json::to_string(&msg.clone()).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(
+            first,
+            r#"{"mti":"0200","fields":{"2":"4111111111111111","3":"000000","4":"000000001000","11":"000001","37":"260115000042","52":"abababababababab"}}"#
+        );
+    }
+
+    #[test]
+    fn test_derive() {
+        let mut auth = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+        auth.set_field(
+            Field::PrimaryAccountNumber,
+            FieldValue::from_string("4111111111111111"),
+        )
+        .unwrap();
+        auth.set_field(
+            Field::TransactionAmount,
+            FieldValue::from_string("000000001000"),
+        )
+        .unwrap();
+        auth.set_field(
+            Field::SystemTraceAuditNumber,
+            FieldValue::from_string("000001"),
+        )
+        .unwrap();
+        auth.set_field(
+            Field::AccountIdentification1,
+            FieldValue::from_string("12345678"),
+        )
+        .unwrap();
+
+        let capture = auth
+            .derive(
+                MessageType::FINANCIAL_ADVICE,
+                &[
+                    Field::PrimaryAccountNumber,
+                    Field::TransactionAmount,
+                    Field::ResponseCode, // Not present in the source
+                ],
+            )
+            .unwrap();
+
+        assert_eq!(capture.mti, MessageType::FINANCIAL_ADVICE);
+        assert_eq!(capture.get_field_numbers(), vec![2, 4]);
+        let (bits, count) = capture.bitmap().get_set_fields();
+        assert_eq!(&bits[..count], &[2, 4]);
+        assert_eq!(
+            capture.get_field(Field::TransactionAmount),
+            auth.get_field(Field::TransactionAmount)
+        );
+
+        // Source message is untouched
+        assert!(auth.has_field(Field::AccountIdentification1));
+        assert!(auth.bitmap().is_set(1));
+
+        let invalid = MessageType::new(
+            0,
+            crate::mti::MessageClass::Reserved,
+            crate::mti::MessageFunction::Request,
+            crate::mti::MessageOrigin::Acquirer,
+        );
+        assert!(auth
+            .derive(invalid, &[Field::PrimaryAccountNumber])
+            .is_err());
+    }
+
+    #[test]
+    fn test_fill_missing_required() {
+        let mut response = ISO8583Message::new(MessageType::FINANCIAL_RESPONSE);
+        response
+            .set_field(Field::ProcessingCode, FieldValue::from_string("010000"))
+            .unwrap();
+        assert!(crate::validation::Validator::validate_required_fields(&response).is_err());
+
+        response
+            .fill_missing_required(&MtiProfile::for_mti(response.mti))
+            .unwrap();
+        assert!(crate::validation::Validator::validate_required_fields(&response).is_ok());
+        assert_eq!(
+            response
+                .get_field(Field::ProcessingCode)
+                .unwrap()
+                .as_string(),
+            Some("010000")
+        );
+        assert_eq!(
+            response.get_field(Field::ResponseCode).unwrap().as_string(),
+            Some("  ")
+        );
+
+        let mut request = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+        request
+            .fill_missing_required(&MtiProfile::for_mti(request.mti))
+            .unwrap();
+        assert!(crate::validation::Validator::validate_required_fields(&request).is_ok());
+        assert_eq!(
+            request
+                .get_field(Field::TransactionAmount)
+                .unwrap()
+                .as_string(),
+            Some("000000000000")
+        );
+
+        // Placeholders follow the message's runtime spec
+        let mut spec = RuntimeSpec::new();
+        spec.override_field(
+            11,
+            crate::spec::FieldDefinition::fixed(crate::spec::DataType::Numeric, 12),
+        );
+        let mut stub = ISO8583Message::new(MessageType::NETWORK_MANAGEMENT_REQUEST);
+        stub.set_runtime_spec(Some(spec));
+        let profile = MtiProfile::new(stub.mti, vec![Field::SystemTraceAuditNumber]);
+        stub.fill_missing_required(&profile).unwrap();
+        assert!(stub.missing_required(&profile).is_empty());
+        assert_eq!(
+            stub.get_field(Field::SystemTraceAuditNumber)
+                .unwrap()
+                .as_string(),
+            Some("000000000000")
+        );
+    }
+
+    #[test]
+    fn test_journal_roundtrip() {
+        // Private-use version with an undefined field captured leniently
+        let mut wire = b"9200".to_vec();
+        let mut bitmap = Bitmap::new();
+        for field in [2, 52, 58, 70] {
+            bitmap.set(field).unwrap();
+        }
+        let (bitmap_bytes, bitmap_len) = bitmap.to_bytes();
+        wire.extend_from_slice(&bitmap_bytes[..bitmap_len]);
+        wire.extend_from_slice(b"164111111111111111");
+        wire.extend_from_slice(&[0x00, 0xFF, 0x10, 0x20, 0x30, 0x40, 0x50, 0x60]);
+        wire.extend_from_slice(b"003abc");
+        wire.extend_from_slice(b"301");
+
+        let msg = ISO8583Message::from_bytes_with_options(&wire, &ParseOptions::lenient()).unwrap();
+        assert_eq!(msg.get_unknown_field(58), Some(&b"003abc"[..]));
+
+        let journal = msg.to_journal_bytes().unwrap();
+        let restored = ISO8583Message::from_journal_bytes(&journal).unwrap();
+        assert_eq!(restored, msg);
+        assert_eq!(restored.mti.version, 9);
+        assert_eq!(restored.to_bytes(), wire);
+        assert_eq!(restored.to_journal_bytes(), Ok(journal));
+    }
+
+    #[test]
+    fn test_journal_rejects_bad_input() {
+        let mut msg = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+        msg.set_field(Field::ProcessingCode, FieldValue::from_string("000000"))
+            .unwrap();
+        let journal = msg.to_journal_bytes().unwrap();
+
+        assert!(ISO8583Message::from_journal_bytes(&journal[..journal.len() - 1]).is_err());
+        assert!(ISO8583Message::from_journal_bytes(&msg.to_bytes()).is_err());
+
+        let mut bad_kind = journal.clone();
+        let kind_offset = journal.len() - 6 - 3;
+        bad_kind[kind_offset] = 9;
+        assert!(ISO8583Message::from_journal_bytes(&bad_kind).is_err());
+
+        // Records for bitmap slots, out-of-range fields or fields missing
+        // from the bitmap are refused rather than panicking on encode
+        for num in [0, 1, 65, 129, 200] {
+            let mut bad_field = journal.clone();
+            bad_field.extend_from_slice(&[num, JOURNAL_STRING, 0, 1, b'0']);
+            assert!(matches!(
+                ISO8583Message::from_journal_bytes(&bad_field),
+                Err(ISO8583Error::ParseError(_))
+            ));
+        }
+        let mut unset = journal.clone();
+        unset.extend_from_slice(&[11, JOURNAL_STRING, 0, 6]);
+        unset.extend_from_slice(b"000001");
+        assert!(matches!(
+            ISO8583Message::from_journal_bytes(&unset),
+            Err(ISO8583Error::ParseError(_))
+        ));
+
+        // A value too long for the u16 length is refused, not truncated
+        msg.set_field(
+            Field::AdditionalDataPrivate,
+            FieldValue::from_string("A".repeat(70_000)),
+        )
+        .unwrap();
+        assert!(matches!(
+            msg.to_journal_bytes(),
+            Err(ISO8583Error::InvalidFieldValue { field: 48, .. })
+        ));
+    }
+
+    #[test]
+    fn test_merge_from() {
+        let mut base = ISO8583Message::new(MessageType::FINANCIAL_ADVICE);
+        base.set_field(
+            Field::PrimaryAccountNumber,
+            FieldValue::from_string("4111111111111111"),
+        )
+        .unwrap();
+        base.set_field(
+            Field::TransactionAmount,
+            FieldValue::from_string("000000001000"),
+        )
+        .unwrap();
+
+        let mut update = ISO8583Message::new(MessageType::FINANCIAL_ADVICE_RESPONSE);
+        update
+            .set_field(
+                Field::TransactionAmount,
+                FieldValue::from_string("000000000500"),
+            )
+            .unwrap();
+        update
+            .set_field(
+                Field::PersonalIdentificationNumberData,
+                FieldValue::from_binary(vec![0x12; 8]),
+            )
+            .unwrap();
+        update
+            .set_field(
+                Field::AccountIdentification1,
+                FieldValue::from_string("ACCT01"),
+            )
+            .unwrap();
+
+        // Selected fields only; absent ones are skipped
+        let mut partial = base.clone();
+        partial
+            .merge_from(
+                &update,
+                Some(&[Field::PersonalIdentificationNumberData, Field::ResponseCode]),
+            )
+            .unwrap();
+        assert_eq!(partial.get_field_numbers(), vec![2, 4, 52]);
+        assert_eq!(
+            partial.get_field(Field::PersonalIdentificationNumberData),
+            Some(&FieldValue::from_binary(vec![0x12; 8]))
+        );
+        assert_eq!(
+            partial
+                .get_field(Field::TransactionAmount)
+                .unwrap()
+                .as_string(),
+            Some("000000001000")
+        );
+
+        // Everything present in `other`
+        base.merge_from(&update, None).unwrap();
+        assert_eq!(base.mti, MessageType::FINANCIAL_ADVICE);
+        assert_eq!(base.get_field_numbers(), vec![2, 4, 52, 102]);
+        assert_eq!(
+            base.get_field(Field::TransactionAmount)
+                .unwrap()
+                .as_string(),
+            Some("000000000500")
+        );
+        assert!(base.bitmap().is_set(1));
+        assert!(base.bitmap().is_set(102));
+
+        let reparsed = ISO8583Message::from_bytes(&base.to_bytes()).unwrap();
+        assert_eq!(reparsed.get_field_numbers(), base.get_field_numbers());
+    }
+
+    #[test]
+    fn test_field_presence_mask() {
+        let mut msg = ISO8583Message::new(MessageType::FINANCIAL_REQUEST);
+        for field in [
+            Field::PrimaryAccountNumber,
+            Field::ProcessingCode,
+            Field::ResponseCode,
+            Field::MessageAuthenticationCode2,
+        ] {
+            msg.set_field(field, field.definition().default_value())
+                .unwrap();
+        }
+
+        let mask = msg.field_presence_mask();
+        assert!(!mask[0]);
+        assert_eq!(mask.iter().filter(|&&present| present).count(), 4);
+        for num in 1..=128u8 {
+            let field = Field::from_number(num).unwrap();
+            assert_eq!(mask[num as usize], msg.has_field(field), "field {}", num);
+        }
+    }
+
+    #[test]
+    fn test_builder_field_num() {
+        let msg = ISO8583Message::builder()
+            .mti(MessageType::AUTHORIZATION_REQUEST)
+            .field_num(2, "4111111111111111")
+            .field_num(3, "000000")
+            .field_num(4, "000000001000")
+            .field_num(11, "000001")
+            .field_num(12, "120000")
+            .field_num(13, "0101")
+            .binary_field_num(52, vec![0xAB; 8])
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            msg.get_field(Field::ProcessingCode).unwrap().as_string(),
+            Some("000000")
+        );
+        assert_eq!(
+            msg.get_field(Field::PersonalIdentificationNumberData),
+            Some(&FieldValue::from_binary(vec![0xAB; 8]))
+        );
+
+        let result = ISO8583Message::test_builder(MessageType::AUTHORIZATION_REQUEST)
+            .field_num(0, "x")
+            .field_num(200, "y")
+            .build();
+        assert_eq!(result.unwrap_err(), ISO8583Error::InvalidFieldNumber(0));
+    }
+
+    #[test]
+    fn test_set_fields_batch() {
+        let values = [
+            (Field::ProcessingCode, FieldValue::from_string("000000")),
+            (
+                Field::SystemTraceAuditNumber,
+                FieldValue::from_string("000001"),
+            ),
+            (
+                Field::OriginalDataElements,
+                FieldValue::from_string("0".repeat(42)),
+            ),
+            (
+                Field::PrimaryAccountNumber,
+                FieldValue::from_string("4111111111111111"),
+            ),
+        ];
+
+        let mut batch = ISO8583Message::new(MessageType::FINANCIAL_REQUEST);
+        batch.set_fields_batch(values.clone()).unwrap();
+        let mut single = ISO8583Message::new(MessageType::FINANCIAL_REQUEST);
+        for (field, value) in values {
+            single.set_field(field, value).unwrap();
+        }
+        assert_eq!(batch, single);
+        assert_eq!(batch.to_bytes(), single.to_bytes());
+
+        // A rejected value leaves the message unchanged
+        let mut spec = RuntimeSpec::new();
+        spec.set_encoding(4, Some(Encoding::BCD));
+        batch.set_runtime_spec(Some(spec));
+        let before = batch.clone();
+        let result = batch.set_fields_batch([
+            (
+                Field::LocalTransactionTime,
+                FieldValue::from_string("120000"),
+            ),
+            (Field::TransactionAmount, FieldValue::from_string("12AB")),
+        ]);
+        assert!(result.is_err());
+        assert_eq!(batch, before);
+    }
+
+    #[test]
+    fn test_resend_as() {
+        use crate::stan::SequentialStan;
+
+        let original = ISO8583Message::test_builder(MessageType::FINANCIAL_REQUEST)
+            .build()
+            .unwrap();
+        let stans = SequentialStan::new(7);
+
+        let advice = original.resend_as(MessageType::FINANCIAL_ADVICE, &stans);
+        assert_eq!(advice.mti, MessageType::FINANCIAL_ADVICE);
+        assert_eq!(
+            advice
+                .get_field(Field::SystemTraceAuditNumber)
+                .unwrap()
+                .as_string(),
+            Some("000007")
+        );
+        assert_eq!(
+            advice.get_field(Field::PrimaryAccountNumber),
+            original.get_field(Field::PrimaryAccountNumber)
+        );
+
+        // The original is untouched and each resend takes a fresh STAN
+        assert_eq!(original.mti, MessageType::FINANCIAL_REQUEST);
+        assert_eq!(
+            original
+                .get_field(Field::SystemTraceAuditNumber)
+                .unwrap()
+                .as_string(),
+            Some("000001")
+        );
+        let again = original.resend_as(MessageType::FINANCIAL_ADVICE, &stans);
+        assert_eq!(
+            again
+                .get_field(Field::SystemTraceAuditNumber)
+                .unwrap()
+                .as_string(),
+            Some("000008")
+        );
+
+        // Out-of-range provider values still give 6 digits
+        struct Wild;
+        impl StanProvider for Wild {
+            fn next_stan(&self) -> u32 {
+                u32::MAX
+            }
+        }
+        let wild = original.resend_as(MessageType::FINANCIAL_ADVICE, &Wild);
+        assert_eq!(
+            wild.get_field(Field::SystemTraceAuditNumber).unwrap().len(),
+            6
+        );
+    }
+
+    #[test]
+    fn test_partial_approval() {
+        let request = ISO8583Message::test_builder(MessageType::AUTHORIZATION_REQUEST)
+            .field(Field::ProcessingCode, "002000")
+            .field(Field::TransactionAmount, "000000010000")
+            .field(Field::CurrencyCodeTransaction, "978")
+            .field(Field::Track2Data, "4111111111111111=2512")
+            .field(Field::SecurityRelatedControlInformation, "0000000000000000")
+            .binary_field(Field::ReservedISO1, vec![0x9F, 0x02, 0x01, 0x00])
+            .binary_field(Field::MessageAuthenticationCode, vec![0xAB; 8])
+            .build()
+            .unwrap();
+
+        let response = request.to_partial_approval(2500).unwrap();
+        assert_eq!(response.mti, MessageType::AUTHORIZATION_RESPONSE);
+        let code = response.get_response_code().unwrap();
+        assert!(code.is_approved());
+        assert!(!code.is_declined());
+        assert_eq!(
+            code.category(),
+            crate::response_code::ResponseCategory::Approved
+        );
+        assert_eq!(
+            response.get_field(Field::ResponseCode).unwrap().as_string(),
+            Some("10")
+        );
+        assert_eq!(
+            response
+                .get_field(Field::TransactionAmount)
+                .unwrap()
+                .as_string(),
+            Some("000000002500")
+        );
+        assert_eq!(
+            response
+                .get_field(Field::AdditionalAmounts)
+                .unwrap()
+                .as_string(),
+            Some("2057978C000000010000")
+        );
+        assert_eq!(
+            response.get_field(Field::SystemTraceAuditNumber),
+            request.get_field(Field::SystemTraceAuditNumber)
+        );
+        for field in [
+            Field::Track2Data,
+            Field::SecurityRelatedControlInformation,
+            Field::ReservedISO1,
+            Field::MessageAuthenticationCode,
+        ] {
+            assert!(request.has_field(field));
+            assert!(!response.has_field(field));
+            assert!(!response.bitmap().is_set(field.number()));
+        }
+        assert!(crate::validation::Validator::validate_required_fields(&response).is_ok());
+
+        assert!(request.to_partial_approval(10000).is_err());
+        assert!(request.to_partial_approval(0).is_err());
+        assert!(response.to_partial_approval(100).is_err()); // Not a request
+    }
+
+    #[test]
+    fn test_card_acceptor() {
+        let location =
+            CardAcceptorNameLocation::new("ACME STORE", "SPRINGFIELD", "IL", "USA").unwrap();
+        let mut msg = ISO8583Message::new(MessageType::FINANCIAL_REQUEST);
+        assert_eq!(
+            msg.get_card_acceptor(),
+            Err(ISO8583Error::FieldNotPresent(43))
+        );
+
+        msg.set_field(
+            Field::CardAcceptorNameLocation,
+            FieldValue::from_string(location.to_string()),
+        )
+        .unwrap();
+        let parsed = ISO8583Message::from_bytes(&msg.to_bytes()).unwrap();
+        assert_eq!(parsed.get_card_acceptor().unwrap(), location);
+    }
+
+    #[test]
+    fn test_overflow_policy() {
+        let name_location = "A".repeat(45);
+        let mut msg = ISO8583Message::new(MessageType::FINANCIAL_REQUEST);
+
+        // Field 43 (ans, fixed 40) truncates by default
+        msg.set_field(
+            Field::CardAcceptorNameLocation,
+            FieldValue::from_string(name_location.clone()),
+        )
+        .unwrap();
+        let bytes = msg.try_to_bytes().unwrap();
+        let parsed = ISO8583Message::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            parsed
+                .get_field(Field::CardAcceptorNameLocation)
+                .unwrap()
+                .as_string(),
+            Some(&name_location[..40])
+        );
+
+        // Field 4 (n, fixed 12) errors on a 13-digit amount
+        msg.set_field(
+            Field::TransactionAmount,
+            FieldValue::from_string("1000000000000"),
+        )
+        .unwrap();
+        assert_eq!(
+            msg.try_to_bytes().unwrap_err(),
+            ISO8583Error::field_length_mismatch(4, 12, 13)
+        );
+        // Unchecked generation still truncates
+        assert_eq!(msg.to_bytes().len(), bytes.len() + 12);
+
+        // Overrides apply per field type
+        let options =
+            ParseOptions::new().overflow_policy(FieldType::Numeric, OverflowPolicy::Truncate);
+        assert!(msg.try_to_bytes_with_options(&options).is_ok());
+
+        let options =
+            options.overflow_policy(FieldType::AlphaNumericSpecial, OverflowPolicy::Error);
+        assert_eq!(
+            msg.try_to_bytes_with_options(&options).unwrap_err(),
+            ISO8583Error::field_length_mismatch(43, 40, 45)
+        );
+
+        // Reject also fails unchecked generation with those options
+        let options =
+            ParseOptions::new().overflow_policy(FieldType::Numeric, OverflowPolicy::Reject);
+        assert_eq!(
+            msg.to_bytes_with_options(&options).unwrap_err(),
+            ISO8583Error::field_length_mismatch(4, 12, 13)
+        );
+
+        // The policy is not part of the message
+        let mut other = ISO8583Message::new(MessageType::FINANCIAL_REQUEST);
+        other
+            .set_field(
+                Field::CardAcceptorNameLocation,
+                FieldValue::from_string(name_location),
+            )
+            .unwrap();
+        other
+            .set_field(
+                Field::TransactionAmount,
+                FieldValue::from_string("1000000000000"),
+            )
+            .unwrap();
+        assert_eq!(msg, other);
+    }
+
+    #[test]
+    fn test_get_track1() {
+        let mut msg = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+        assert_eq!(msg.get_track1(), Err(ISO8583Error::FieldNotPresent(45)));
+
+        msg.set_field(
+            Field::Track1Data,
+            FieldValue::from_string("B4111111111111111^DOE/JOHN Q^2512101123456"),
+        )
+        .unwrap();
+        let parsed = ISO8583Message::from_bytes(&msg.to_bytes()).unwrap();
+        let track1 = parsed.get_track1().unwrap();
+        assert_eq!(track1.pan, "4111111111111111");
+        assert_eq!(track1.name, "DOE/JOHN Q");
+        assert_eq!(track1.discretionary.as_deref(), Some("123456"));
+    }
+
+    #[test]
+    fn test_fee_amounts() {
+        let mut msg = ISO8583Message::new(MessageType::FINANCIAL_REQUEST);
+        msg.set_field(
+            Field::TransactionFeeAmount,
+            FieldValue::from_string("C00000100"),
+        )
+        .unwrap();
+        msg.set_field(
+            Field::SettlementFeeAmount,
+            FieldValue::from_string("D00000250"),
+        )
+        .unwrap();
+        msg.set_field(
+            Field::TransactionProcessingFeeAmount,
+            FieldValue::from_string("D5"),
+        )
+        .unwrap();
+
+        assert_eq!(msg.get_fee_amount(Field::TransactionFeeAmount), Ok(100));
+        assert_eq!(msg.get_fee_amount(Field::SettlementFeeAmount), Ok(-250));
+        assert_eq!(
+            msg.get_fee_amount(Field::SettlementProcessingFeeAmount),
+            Err(ISO8583Error::FieldNotPresent(31))
+        );
+        assert!(msg.get_fee_amount(Field::TransactionAmount).is_err());
+
+        // Short values are zero-padded after the sign on the wire
+        let parsed = ISO8583Message::from_bytes(&msg.to_bytes()).unwrap();
+        assert_eq!(
+            parsed
+                .get_field(Field::TransactionProcessingFeeAmount)
+                .unwrap()
+                .as_string(),
+            Some("D00000005")
+        );
+        assert_eq!(
+            parsed.get_fee_amount(Field::TransactionProcessingFeeAmount),
+            Ok(-5)
+        );
+        assert!(crate::validation::Validator::validate_field_format(
+            Field::SettlementFeeAmount,
+            parsed.get_field(Field::SettlementFeeAmount).unwrap()
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_pan_bin() {
+        let mut msg = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+        assert_eq!(msg.pan_bin(6), Err(ISO8583Error::FieldNotPresent(2)));
+
+        msg.set_field(
+            Field::PrimaryAccountNumber,
+            FieldValue::from_string("5500000000000004"),
+        )
+        .unwrap();
+        assert_eq!(msg.pan_bin(6).unwrap(), "550000");
+        assert_eq!(msg.pan_bin(8).unwrap(), "55000000");
+        assert!(msg.pan_bin(20).is_err());
+    }
+
+    #[test]
+    fn test_write_to_matches_framed_bytes() {
+        let msg = ISO8583Message::test_builder(MessageType::FINANCIAL_REQUEST)
+            .binary_field(Field::MessageAuthenticationCode, vec![0xAB; 8])
+            .field(Field::AdditionalDataPrivate, "extra")
+            .build()
+            .unwrap();
+        assert_eq!(msg.encoded_len(), msg.to_bytes().len());
+
+        for mli in [
+            MliType::None,
+            MliType::Binary2,
+            MliType::Binary4,
+            MliType::Ascii4,
+        ] {
+            let framed = msg.to_framed_bytes(mli).unwrap();
+
+            let mut out = Vec::new();
+            {
+                let mut writer = io::BufWriter::new(&mut out);
+                let written = msg.write_to(&mut writer, mli).unwrap();
+                assert_eq!(written, framed.len());
+            }
+            assert_eq!(out, framed);
+
+            assert_eq!(
+                ISO8583Message::from_framed_bytes(&framed, mli).unwrap(),
+                msg
+            );
+            assert_eq!(
+                ISO8583Message::read_from(&mut framed.as_slice(), mli).unwrap(),
+                msg
+            );
+        }
+
+        // An overlong LLVAR value gets a 3-digit prefix in both paths
+        let mut overlong = ISO8583Message::new(MessageType::FINANCIAL_REQUEST);
+        overlong
+            .set_field(
+                Field::PrimaryAccountNumber,
+                FieldValue::from_string("1".repeat(120)),
+            )
+            .unwrap();
+        assert_eq!(overlong.encoded_len(), overlong.to_bytes().len());
+        let framed = overlong.to_framed_bytes(MliType::Binary2).unwrap();
+        let mut out = Vec::new();
+        let written = overlong.write_to(&mut out, MliType::Binary2).unwrap();
+        assert_eq!(written, framed.len());
+        assert_eq!(out, framed);
+    }
+
+    #[test]
+    fn test_desync_check() {
+        let msg = ISO8583Message::test_builder(MessageType::FINANCIAL_REQUEST)
+            .build()
+            .unwrap();
+        let bytes = msg.to_bytes();
+        let options = ParseOptions::new().check_desync(true);
+
+        assert_eq!(
+            ISO8583Message::from_bytes_with_options(&bytes, &options).unwrap(),
+            msg
+        );
+
+        // The PAN's length prefix claims 2 fewer digits: fields 3 onwards shift
+        let mut shifted = bytes.clone();
+        let pan_offset = 4 + 8;
+        assert_eq!(&shifted[pan_offset..pan_offset + 2], b"16");
+        shifted[pan_offset..pan_offset + 2].copy_from_slice(b"14");
+
+        // Without the check the shifted fields parse silently as garbage
+        let garbled = ISO8583Message::from_bytes(&shifted).unwrap();
+        assert_ne!(garbled, msg);
+        match ISO8583Message::from_bytes_with_options(&shifted, &options) {
+            Err(ISO8583Error::Desync { field, offset, .. }) => {
+                assert!(field > 2, "field {}", field);
+                assert!(offset > pan_offset);
+            }
+            other => panic!("expected desync error, got {:?}", other),
+        }
+
+        // Trailing bytes after an otherwise valid message
+        let mut padded = bytes.clone();
+        padded.extend_from_slice(b"XYZ");
+        assert!(ISO8583Message::from_bytes_with_options(&padded, &ParseOptions::new()).is_ok());
+        assert!(matches!(
+            ISO8583Message::from_bytes_with_options(&padded, &options),
+            Err(ISO8583Error::Desync { field: 13, reason, .. }) if reason.contains("3 bytes")
+        ));
+    }
+
+    #[test]
+    fn test_secondary_bitmap_policy() {
+        let mut msg = ISO8583Message::new(MessageType::NETWORK_MANAGEMENT_REQUEST);
+        msg.set_field(
+            Field::SystemTraceAuditNumber,
+            FieldValue::from_string("000001"),
+        )
+        .unwrap();
+        let primary_only = msg.to_bytes();
+        assert_eq!(primary_only.len(), 4 + 8 + 6);
+
+        // Indicator set with no fields 65-128
+        msg.bitmap.set(1).unwrap();
+        let encode = |msg: &ISO8583Message, policy| {
+            msg.to_bytes_with_options(&ParseOptions::new().secondary_bitmap(policy))
+                .unwrap()
+        };
+
+        let auto = encode(&msg, SecondaryBitmapPolicy::Auto);
+        assert_eq!(auto, msg.to_bytes());
+        assert_eq!(auto.len(), 4 + 16 + 6);
+        assert_eq!(&auto[12..20], &[0u8; 8]);
+
+        let indicated = encode(&msg, SecondaryBitmapPolicy::AlwaysWhenIndicated);
+        assert_eq!(indicated, auto);
+
+        let trimmed = encode(&msg, SecondaryBitmapPolicy::NeverIfEmpty);
+        assert_eq!(trimmed, primary_only);
+        assert_eq!(
+            ISO8583Message::from_bytes(&trimmed).unwrap().fields,
+            msg.fields
+        );
+
+        // A secondary block with data is always kept
+        msg.set_field(
+            Field::NetworkManagementInformationCode,
+            FieldValue::from_string("301"),
+        )
+        .unwrap();
+        assert_eq!(
+            encode(&msg, SecondaryBitmapPolicy::NeverIfEmpty),
+            encode(&msg, SecondaryBitmapPolicy::Auto)
+        );
+    }
+
+    #[test]
+    fn test_builder_from_pairs() {
+        let original = ISO8583Message::test_builder(MessageType::FINANCIAL_REQUEST)
+            .binary_field(
+                Field::PersonalIdentificationNumberData,
+                vec![0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC, 0xDE, 0xF0],
+            )
+            .field_num(120, "EXTRA")
+            .build()
+            .unwrap();
+
+        let pairs = original.get_field_numbers().into_iter().map(|num| {
+            let field = Field::from_number(num).unwrap();
+            (num, original.get_field(field).unwrap().clone())
+        });
+        let replayed = MessageBuilder::from_pairs(original.mti, pairs)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(replayed, original);
+
+        assert!(MessageBuilder::from_pairs(
+            MessageType::FINANCIAL_REQUEST,
+            [(0, FieldValue::from_string("X"))]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_emv_cryptogram_data() {
+        let mut msg = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+        assert_eq!(
+            msg.emv_cryptogram_data(),
+            Err(ISO8583Error::FieldNotPresent(55))
+        );
+
+        // Template 77 wrapping the cryptogram tags
+        let icc = "771B9F260801020304050607089F360200019F3704DEADBEEF9F270180";
+        msg.set_field(Field::ReservedISO1, FieldValue::from_string(icc))
+            .unwrap();
+        let data = msg.emv_cryptogram_data().unwrap();
+        assert_eq!(data.arqc, [1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(data.atc, 1);
+        assert_eq!(data.unpredictable_number, [0xDE, 0xAD, 0xBE, 0xEF]);
+        assert_eq!(data.cryptogram_info, 0x80);
+
+        // Hex text in field 55 round-trips as text with the built-in table
+        let parsed = ISO8583Message::from_bytes(&msg.to_bytes()).unwrap();
+        assert_eq!(
+            parsed.get_field(Field::ReservedISO1),
+            Some(&FieldValue::from_string(icc))
+        );
+
+        // Binary field 55 without the unpredictable number
+        let icc = hex::decode("9F260801020304050607089F360200019F270180").unwrap();
+        msg.set_field(Field::ReservedISO1, FieldValue::from_binary(icc))
+            .unwrap();
+        assert!(msg.emv_cryptogram_data().is_err());
+    }
+
+    #[test]
+    fn test_bitmap_consistency() {
+        let mut msg = ISO8583Message::test_builder(MessageType::FINANCIAL_REQUEST)
+            .field_num(120, "EXTRA")
+            .build()
+            .unwrap();
+        assert!(msg.validate_bitmap_consistency().is_ok());
+
+        // Value without its bit
+        let mut corrupted = msg.clone();
+        corrupted
+            .fields
+            .insert(48, FieldValue::from_string("ORPHAN"));
+        assert!(matches!(
+            corrupted.validate_bitmap_consistency(),
+            Err(ISO8583Error::InvalidBitmap(reason)) if reason.contains("48")
+        ));
+
+        // Bit without its value
+        msg.fields.remove(&120);
+        assert!(matches!(
+            msg.validate_bitmap_consistency(),
+            Err(ISO8583Error::InvalidBitmap(reason)) if reason.contains("120")
+        ));
+    }
+
+    #[test]
+    fn test_owned_accessors() {
+        let mut msg = ISO8583Message::new(MessageType::FINANCIAL_REQUEST);
+        msg.set_field(
+            Field::SystemTraceAuditNumber,
+            FieldValue::from_string("000042"),
+        )
+        .unwrap();
+        msg.set_field(
+            Field::PersonalIdentificationNumberData,
+            FieldValue::from_binary(vec![0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC, 0xDE, 0xF0]),
+        )
+        .unwrap();
+        msg.set_field(Field::ProcessingCode, FieldValue::from_string("000000"))
+            .unwrap();
+
+        assert_eq!(
+            msg.take_str(Field::SystemTraceAuditNumber),
+            Some("000042".to_string())
+        );
+        assert_eq!(msg.take_str(Field::PersonalIdentificationNumberData), None);
+        assert_eq!(msg.take_str(Field::ResponseCode), None);
+
+        let fields = msg.to_owned_fields();
+        assert_eq!(fields.keys().copied().collect::<Vec<_>>(), vec![3, 11, 52]);
+        assert_eq!(fields[&11], FieldValue::from_string("000042"));
+
+        // The snapshot is independent of the message
+        drop(msg);
+        assert_eq!(fields.len(), 3);
+    }
+
+    #[test]
+    fn test_parse_header() {
+        let msg = ISO8583Message::test_builder(MessageType::FINANCIAL_REQUEST)
+            .field(Field::ReceivingInstitutionIdentificationCode, "123456")
+            .build()
+            .unwrap();
+        let bytes = msg.to_bytes();
 
-        // Update bitmap
-        self.bitmap.set(field_num)?;
+        let (mti, bitmap, offset) = ISO8583Message::parse_header(&bytes).unwrap();
+        assert_eq!(mti, MessageType::FINANCIAL_REQUEST);
+        assert_eq!(&bitmap, msg.bitmap());
+        assert_eq!(offset, 4 + 16); // Secondary bitmap present
 
-        // Store value
-        self.fields.insert(field_num, value);
+        // The offset points at field 2 (LLVAR PAN)
+        let pan = msg.get_field(Field::PrimaryAccountNumber).unwrap();
+        let prefix = format!("{:02}", pan.len());
+        assert!(bytes[offset..].starts_with(prefix.as_bytes()));
+        assert_eq!(
+            &bytes[offset + 2..offset + 2 + pan.len()],
+            pan.as_string().unwrap().as_bytes()
+        );
 
-        Ok(())
+        // Only the header has to be well-formed
+        assert!(ISO8583Message::parse_header(&bytes[..offset]).is_ok());
+        assert!(ISO8583Message::parse_header(&bytes[..offset - 1]).is_err());
     }
 
-    /// Remove field
-    pub fn remove_field(&mut self, field: Field) -> Result<()> {
-        let field_num = field.number();
+    #[test]
+    fn test_from_bytes_checked_trailing() {
+        let msg = ISO8583Message::test_builder(MessageType::FINANCIAL_REQUEST)
+            .build()
+            .unwrap();
+        let exact = msg.to_bytes();
 
-        // Update bitmap
-        self.bitmap.clear(field_num)?;
+        assert_eq!(
+            ISO8583Message::from_bytes_checked(&exact, true).unwrap(),
+            (msg.clone(), 0)
+        );
 
-        // Remove value
-        self.fields.remove(&field_num);
+        let mut padded = exact.clone();
+        padded.extend_from_slice(b"12345");
 
-        Ok(())
+        let (parsed, trailing) = ISO8583Message::from_bytes_checked(&padded, false).unwrap();
+        assert_eq!(parsed, msg);
+        assert_eq!(trailing, 5);
+        assert_eq!(
+            ISO8583Message::from_bytes_checked(&padded, true),
+            Err(ISO8583Error::TrailingBytes(5))
+        );
+        // from_bytes still ignores them
+        assert_eq!(ISO8583Message::from_bytes(&padded).unwrap(), msg);
     }
 
-    /// Check if field is present
-    pub fn has_field(&self, field: Field) -> bool {
-        self.fields.contains_key(&field.number())
+    #[test]
+    fn test_unencodable_bcd_value() {
+        let mut msg = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+        msg.set_field(Field::ProcessingCode, FieldValue::from_string("00100A"))
+            .unwrap();
+        let mut spec = RuntimeSpec::new();
+        spec.set_encoding(3, Some(Encoding::BCD));
+        msg.set_runtime_spec(Some(spec));
+
+        // Infallible encoders substitute, checked ones report
+        assert!(msg.to_bytes().ends_with(&[0x00, 0x10, 0x00]));
+        assert_eq!(
+            msg.to_bytes_with_bitmap(BitmapEncoding::AsciiHex).len(),
+            4 + 16 + 3
+        );
+        assert_eq!(msg.masked_bytes().len(), msg.to_bytes().len());
+        assert!(msg.try_to_bytes().is_err());
+        assert!(msg.to_framed_bytes(MliType::Binary2).is_err());
+        assert!(msg.write_to(&mut Vec::new(), MliType::Binary2).is_err());
+
+        // Same through build_unchecked, which skips every check
+        let mut msg = ISO8583Message::builder()
+            .mti(MessageType::AUTHORIZATION_REQUEST)
+            .field(Field::ProcessingCode, "00100A")
+            .build_unchecked();
+        let mut spec = RuntimeSpec::new();
+        spec.set_encoding(3, Some(Encoding::BCD));
+        msg.set_runtime_spec(Some(spec));
+        assert_eq!(msg.to_bytes().len(), 4 + 8 + 3);
+        assert!(msg.try_to_bytes().is_err());
     }
 
-    /// Get all present field numbers
-    pub fn get_field_numbers(&self) -> Vec<u8> {
-        let mut numbers: Vec<u8> = self.fields.keys().copied().collect();
-        numbers.sort();
-        numbers
+    #[test]
+    fn test_alpha_generation() {
+        let mut spec = RuntimeSpec::new();
+        spec.override_field(
+            40,
+            crate::spec::FieldDefinition::fixed(crate::spec::DataType::Alpha, 3),
+        );
+        let mut msg = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+        msg.set_runtime_spec(Some(spec));
+
+        // Lowercase is normalized on request, padding stays left-justified
+        msg.set_field(Field::ServiceRestrictionCode, FieldValue::from_string("ab"))
+            .unwrap();
+        let options = ParseOptions::new().uppercase_alpha(true);
+        let bytes = msg.to_bytes_with_options(&options).unwrap();
+        assert!(bytes.ends_with(b"AB "));
+        assert!(msg.to_bytes().ends_with(b"ab "));
+        assert!(msg.try_to_bytes().is_ok());
+        assert_eq!(
+            msg.get_field(Field::ServiceRestrictionCode),
+            Some(&FieldValue::from_string("ab"))
+        );
+
+        // Digits are rejected by strict generation
+        msg.set_field(Field::ServiceRestrictionCode, FieldValue::from_string("a1"))
+            .unwrap();
+        assert!(matches!(
+            msg.try_to_bytes(),
+            Err(ISO8583Error::InvalidFieldValue { field: 40, .. })
+        ));
     }
 
-    /// Get bitmap reference
-    pub fn bitmap(&self) -> &Bitmap {
-        &self.bitmap
+    #[test]
+    fn test_from_bytes_exact() {
+        let msg = ISO8583Message::test_builder(MessageType::FINANCIAL_REQUEST)
+            .field(Field::AdditionalDataPrivate, "extra")
+            .build()
+            .unwrap();
+        let exact = msg.to_bytes();
+        assert_eq!(ISO8583Message::from_bytes_exact(&exact).unwrap(), msg);
+
+        // One byte short: field 48 ("005" + "extra") is cut
+        assert_eq!(
+            ISO8583Message::from_bytes_exact(&exact[..exact.len() - 1]),
+            Err(ISO8583Error::message_too_short(8, 7))
+        );
+
+        let mut padded = exact.clone();
+        padded.push(b' ');
+        assert_eq!(
+            ISO8583Message::from_bytes_exact(&padded),
+            Err(ISO8583Error::TrailingBytes(1))
+        );
     }
 
-    /// Create a builder for constructing messages
-    pub fn builder() -> MessageBuilder {
-        MessageBuilder::new()
+    #[test]
+    fn test_read_from_stream() {
+        let first = ISO8583Message::test_builder(MessageType::NETWORK_MANAGEMENT_REQUEST)
+            .build()
+            .unwrap();
+        let second = ISO8583Message::test_builder(MessageType::NETWORK_MANAGEMENT_RESPONSE)
+            .build()
+            .unwrap();
+
+        let mut stream = Vec::new();
+        first.write_to(&mut stream, MliType::Binary2).unwrap();
+        second.write_to(&mut stream, MliType::Binary2).unwrap();
+
+        let mut reader = stream.as_slice();
+        assert_eq!(
+            ISO8583Message::read_from(&mut reader, MliType::Binary2).unwrap(),
+            first
+        );
+        assert_eq!(
+            ISO8583Message::read_from(&mut reader, MliType::Binary2).unwrap(),
+            second
+        );
+        assert_eq!(
+            ISO8583Message::read_from(&mut reader, MliType::Binary2)
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+
+        // Header announcing more than is available
+        let framed = first.to_framed_bytes(MliType::Ascii4).unwrap();
+        assert!(
+            ISO8583Message::from_framed_bytes(&framed[..framed.len() - 1], MliType::Ascii4)
+                .is_err()
+        );
+        let garbage = b"00041234";
+        assert_eq!(
+            ISO8583Message::read_from(&mut garbage.as_slice(), MliType::Ascii4)
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::InvalidData
+        );
     }
-}
 
-/// Builder for ISO 8583 messages
-#[derive(Debug)]
-pub struct MessageBuilder {
-    message: ISO8583Message,
-}
+    #[test]
+    fn test_read_from_oversized_header() {
+        // A 4 GiB header is refused before anything is buffered
+        let header = [0xFF, 0xFF, 0xFF, 0xFF, b'0', b'8'];
+        let err = ISO8583Message::read_from(&mut header.as_slice(), MliType::Binary4).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(
+            *err.into_inner()
+                .unwrap()
+                .downcast::<ISO8583Error>()
+                .unwrap(),
+            ISO8583Error::FrameTooLarge {
+                len: u32::MAX as usize,
+                max: DEFAULT_MAX_FRAME_LEN
+            }
+        );
 
-impl MessageBuilder {
-    /// Create a new builder
-    pub fn new() -> Self {
-        Self {
-            message: ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST),
+        // A custom limit, both with and without a length header
+        let msg = ISO8583Message::test_builder(MessageType::NETWORK_MANAGEMENT_REQUEST)
+            .build()
+            .unwrap();
+        let framed = msg.to_framed_bytes(MliType::Binary2).unwrap();
+        let body_len = framed.len() - 2;
+        assert_eq!(
+            ISO8583Message::read_from_with_limit(&mut &framed[..], MliType::Binary2, body_len)
+                .unwrap(),
+            msg
+        );
+        for (mli, bytes) in [
+            (MliType::Binary2, &framed[..]),
+            (MliType::None, &framed[2..]),
+        ] {
+            assert_eq!(
+                ISO8583Message::read_from_with_limit(&mut &bytes[..], mli, body_len - 1)
+                    .unwrap_err()
+                    .kind(),
+                io::ErrorKind::InvalidData
+            );
         }
     }
 
-    /// Set the MTI
-    pub fn mti(mut self, mti: MessageType) -> Self {
-        self.message.mti = mti;
-        self
+    #[test]
+    fn test_delimited_roundtrip() {
+        let options = ParseOptions::new().bitmap_encoding(BitmapEncoding::AsciiHex);
+        let first = ISO8583Message::test_builder(MessageType::FINANCIAL_REQUEST)
+            .field(Field::AdditionalDataPrivate, "line\nbreak")
+            .build()
+            .unwrap();
+        let second = ISO8583Message::test_builder(MessageType::NETWORK_MANAGEMENT_REQUEST)
+            .build()
+            .unwrap();
+
+        for trailer in [Trailer::Etx, Trailer::EtxLrc, Trailer::CrLf] {
+            let mut stream = first.to_delimited_bytes(trailer, &options).unwrap();
+            stream.extend(second.to_delimited_bytes(trailer, &options).unwrap());
+
+            let mut reader = stream.as_slice();
+            for expected in [&first, &second] {
+                assert_eq!(
+                    &ISO8583Message::read_delimited(&mut reader, trailer, &options).unwrap(),
+                    expected
+                );
+            }
+            assert_eq!(
+                ISO8583Message::read_delimited(&mut reader, trailer, &options)
+                    .unwrap_err()
+                    .kind(),
+                io::ErrorKind::UnexpectedEof
+            );
+        }
+
+        let framed = first.to_delimited_bytes(Trailer::None, &options).unwrap();
+        assert_eq!(framed, first.to_bytes_with_options(&options).unwrap());
+        assert_eq!(
+            ISO8583Message::read_delimited(&mut framed.as_slice(), Trailer::None, &options)
+                .unwrap(),
+            first
+        );
     }
 
-    /// Add a field
-    pub fn field<S: Into<String>>(mut self, field: Field, value: S) -> Self {
-        let _ = self
-            .message
-            .set_field(field, FieldValue::from_string(value.into()));
-        self
+    #[test]
+    fn test_delimited_frame_limit() {
+        let options = ParseOptions::new().bitmap_encoding(BitmapEncoding::AsciiHex);
+        let msg = ISO8583Message::test_builder(MessageType::NETWORK_MANAGEMENT_REQUEST)
+            .build()
+            .unwrap();
+        let framed = msg.to_delimited_bytes(Trailer::EtxLrc, &options).unwrap();
+        let body_len = framed.len() - 2;
+        assert_eq!(
+            ISO8583Message::read_delimited_with_limit(
+                &mut framed.as_slice(),
+                Trailer::EtxLrc,
+                &options,
+                body_len
+            )
+            .unwrap(),
+            msg
+        );
+
+        // Body one byte over the limit, and a peer that never sends ETX
+        for (stream, trailer) in [
+            (framed.clone(), Trailer::EtxLrc),
+            (vec![b'0'; 4096], Trailer::Etx),
+            (vec![b'\n'; 4096], Trailer::CrLf),
+        ] {
+            let err = ISO8583Message::read_delimited_with_limit(
+                &mut stream.as_slice(),
+                trailer,
+                &options,
+                body_len - 1,
+            )
+            .unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+            assert!(matches!(
+                *err.into_inner()
+                    .unwrap()
+                    .downcast::<ISO8583Error>()
+                    .unwrap(),
+                ISO8583Error::FrameTooLarge { max, .. } if max == body_len - 1
+            ));
+        }
     }
 
-    /// Add a binary field
-    pub fn binary_field(mut self, field: Field, value: Vec<u8>) -> Self {
-        let _ = self
-            .message
-            .set_field(field, FieldValue::from_binary(value));
-        self
+    #[test]
+    fn test_delimited_lrc_verification() {
+        let options = ParseOptions::new().bitmap_encoding(BitmapEncoding::AsciiHex);
+        let msg = ISO8583Message::test_builder(MessageType::NETWORK_MANAGEMENT_REQUEST)
+            .build()
+            .unwrap();
+        let mut framed = msg.to_delimited_bytes(Trailer::EtxLrc, &options).unwrap();
+        assert_eq!(
+            ISO8583Message::from_delimited_bytes(&framed, Trailer::EtxLrc, &options).unwrap(),
+            msg
+        );
+
+        // Flip a bit in the body: the LRC no longer matches
+        framed[0] ^= 0x01;
+        assert!(ISO8583Message::from_delimited_bytes(&framed, Trailer::EtxLrc, &options).is_err());
+        assert_eq!(
+            ISO8583Message::read_delimited(&mut framed.as_slice(), Trailer::EtxLrc, &options)
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::InvalidData
+        );
+
+        // Stream cut between ETX and LRC
+        assert_eq!(
+            ISO8583Message::read_delimited(
+                &mut &framed[..framed.len() - 1],
+                Trailer::EtxLrc,
+                &options
+            )
+            .unwrap_err()
+            .kind(),
+            io::ErrorKind::UnexpectedEof
+        );
     }
 
-    /// Build the message
-    pub fn build(self) -> Result<ISO8583Message> {
-        // Validate the message
-        crate::validation::Validator::validate_required_fields(&self.message)?;
+    /// Async reader that hands out at most 3 bytes per read
+    #[cfg(feature = "tokio")]
+    struct TrickleReader<'a>(&'a [u8]);
 
-        Ok(self.message)
+    #[cfg(feature = "tokio")]
+    impl tokio::io::AsyncRead for TrickleReader<'_> {
+        fn poll_read(
+            mut self: std::pin::Pin<&mut Self>,
+            _cx: &mut std::task::Context<'_>,
+            buf: &mut tokio::io::ReadBuf<'_>,
+        ) -> std::task::Poll<io::Result<()>> {
+            let n = self.0.len().min(buf.remaining()).min(3);
+            buf.put_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            std::task::Poll::Ready(Ok(()))
+        }
     }
-}
 
-impl Default for MessageBuilder {
-    fn default() -> Self {
-        Self::new()
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_read_async_stream() {
+        let first = ISO8583Message::test_builder(MessageType::NETWORK_MANAGEMENT_REQUEST)
+            .build()
+            .unwrap();
+        let second = ISO8583Message::test_builder(MessageType::NETWORK_MANAGEMENT_RESPONSE)
+            .build()
+            .unwrap();
+
+        let mut stream = Vec::new();
+        first.write_to(&mut stream, MliType::Ascii4).unwrap();
+        second.write_to(&mut stream, MliType::Ascii4).unwrap();
+
+        let mut reader = TrickleReader(&stream);
+        assert_eq!(
+            ISO8583Message::read_async(&mut reader, MliType::Ascii4)
+                .await
+                .unwrap(),
+            first
+        );
+        assert_eq!(
+            ISO8583Message::read_async(&mut reader, MliType::Ascii4)
+                .await
+                .unwrap(),
+            second
+        );
+
+        // Stream ends mid-frame
+        let framed = first.to_framed_bytes(MliType::Ascii4).unwrap();
+        let mut truncated = TrickleReader(&framed[..framed.len() - 1]);
+        assert_eq!(
+            ISO8583Message::read_async(&mut truncated, MliType::Ascii4)
+                .await
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+
+        // Complete frame, malformed body
+        let mut garbage = TrickleReader(b"00041234");
+        assert_eq!(
+            ISO8583Message::read_async(&mut garbage, MliType::Ascii4)
+                .await
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::InvalidData
+        );
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_read_async_oversized_header() {
+        let mut oversized = TrickleReader(&[0xFF, 0xFF, 0xFF, 0xFF, b'0', b'8']);
+        let err = ISO8583Message::read_async(&mut oversized, MliType::Binary4)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(
+            *err.into_inner()
+                .unwrap()
+                .downcast::<ISO8583Error>()
+                .unwrap(),
+            ISO8583Error::FrameTooLarge {
+                len: u32::MAX as usize,
+                max: DEFAULT_MAX_FRAME_LEN
+            }
+        );
+
+        let msg = ISO8583Message::test_builder(MessageType::NETWORK_MANAGEMENT_REQUEST)
+            .build()
+            .unwrap();
+        let framed = msg.to_framed_bytes(MliType::Ascii4).unwrap();
+        let body_len = framed.len() - 4;
+        assert_eq!(
+            ISO8583Message::read_async_with_limit(
+                &mut TrickleReader(&framed),
+                MliType::Ascii4,
+                body_len
+            )
+            .await
+            .unwrap(),
+            msg
+        );
+        for (mli, bytes) in [
+            (MliType::Ascii4, &framed[..]),
+            (MliType::None, &framed[4..]),
+        ] {
+            assert_eq!(
+                ISO8583Message::read_async_with_limit(&mut TrickleReader(bytes), mli, body_len - 1)
+                    .await
+                    .unwrap_err()
+                    .kind(),
+                io::ErrorKind::InvalidData
+            );
+        }
+    }
 
     #[test]
-    fn test_message_creation() {
-        let msg = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
-        assert_eq!(msg.mti, MessageType::AUTHORIZATION_REQUEST);
-        assert_eq!(msg.get_field_numbers().len(), 0);
+    fn test_presets() {
+        let purchase = ISO8583Message::pos_purchase(
+            "4111111111111111",
+            7550,
+            "840",
+            "POS12345",
+            "MERCHANT0000001",
+        )
+        .build()
+        .unwrap();
+        let atm = ISO8583Message::atm_withdrawal(
+            "4111111111111111",
+            20000,
+            "978",
+            "ATM00123",
+            "BANK00000000001",
+        )
+        .field(Field::PointOfServiceEntryMode, "021")
+        .build()
+        .unwrap();
+
+        let expected = [2, 3, 4, 7, 11, 12, 13, 18, 22, 25, 41, 42, 49];
+        assert_eq!(purchase.get_field_numbers(), expected);
+        assert_eq!(atm.get_field_numbers(), expected);
+        assert_eq!(purchase.mti, MessageType::FINANCIAL_REQUEST);
+
+        for msg in [&purchase, &atm] {
+            assert!(crate::validation::Validator::validate_message(msg).is_ok());
+            for num in msg.get_field_numbers() {
+                let field = Field::from_number(num).unwrap();
+                assert!(field
+                    .definition()
+                    .validate(msg.get_field(field).unwrap())
+                    .is_ok());
+            }
+            assert_eq!(
+                ISO8583Message::from_bytes(&msg.try_to_bytes().unwrap()).unwrap(),
+                *msg
+            );
+        }
+
+        assert_eq!(
+            purchase
+                .get_field(Field::TransactionAmount)
+                .unwrap()
+                .as_string(),
+            Some("000000007550")
+        );
+        assert_eq!(
+            atm.get_field(Field::ProcessingCode).unwrap().as_string(),
+            Some("010000")
+        );
+        assert_eq!(
+            atm.get_field(Field::MerchantType).unwrap().as_string(),
+            Some("6011")
+        );
+        assert_eq!(
+            atm.get_field(Field::PointOfServiceEntryMode)
+                .unwrap()
+                .as_string(),
+            Some("021")
+        );
     }
 
     #[test]
-    fn test_set_and_get_field() {
+    fn test_message_view_borrows() {
+        let msg = ISO8583Message::test_builder(MessageType::FINANCIAL_REQUEST)
+            .binary_field(Field::MessageAuthenticationCode, vec![0x5A; 8])
+            .build()
+            .unwrap();
+        let bytes = msg.to_bytes();
+
+        let view = MessageView::parse(&bytes).unwrap();
+        assert_eq!(view.mti, msg.mti);
+        assert_eq!(view.get_field_numbers(), msg.get_field_numbers());
+        for num in view.get_field_numbers() {
+            let field = Field::from_number(num).unwrap();
+            let value = view.get_field(field).unwrap();
+            assert!(value.is_borrowed());
+            assert_eq!(*value, *msg.get_field(field).unwrap());
+        }
+
+        // Borrowed string points into the input buffer
+        let pan = view.get_field(Field::PrimaryAccountNumber).unwrap();
+        let pan_ptr = pan.as_string().unwrap().as_ptr();
+        assert!(bytes.as_ptr_range().contains(&pan_ptr));
+
+        assert_eq!(view.into_message(), msg);
+    }
+
+    #[test]
+    fn test_field_48_free_text() {
+        let mut msg = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+        msg.set_field(
+            Field::AdditionalDataPrivate,
+            FieldValue::from_string("1234 FREE TEXT"),
+        )
+        .unwrap();
+        let bytes = msg.to_bytes();
+
+        // Not structured: parses as plain text and refuses subfield access
+        let parsed = ISO8583Message::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            parsed
+                .get_field(Field::AdditionalDataPrivate)
+                .unwrap()
+                .as_string(),
+            Some("1234 FREE TEXT")
+        );
+        assert!(parsed
+            .get_subfield(Field::AdditionalDataPrivate, 12)
+            .is_err());
+
+        // The same bytes are rejected when field 48 is declared structured
+        let mut spec = RuntimeSpec::new();
+        spec.set_structured(48, true);
+        let options = ParseOptions::new().runtime_spec(spec);
+        assert!(ISO8583Message::from_bytes_with_options(&bytes, &options).is_err());
+    }
+
+    #[test]
+    fn test_field_48_structured() {
+        let data = subfield::encode(&[(10, "HELLO"), (42, "ABC")], 48).unwrap();
         let mut msg = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+        msg.set_field(
+            Field::AdditionalDataPrivate,
+            FieldValue::from_string(data.clone()),
+        )
+        .unwrap();
+        let bytes = msg.to_bytes();
+
+        let mut spec = RuntimeSpec::new();
+        spec.set_structured(48, true);
+        let options = ParseOptions::new().runtime_spec(spec);
+        let parsed = ISO8583Message::from_bytes_with_options(&bytes, &options).unwrap();
+
+        assert_eq!(parsed.runtime_spec(), Some(&spec));
+        assert_eq!(
+            parsed
+                .get_field(Field::AdditionalDataPrivate)
+                .unwrap()
+                .as_string(),
+            Some(data.as_str())
+        );
+        assert_eq!(
+            parsed
+                .get_subfield(Field::AdditionalDataPrivate, 42)
+                .unwrap(),
+            Some("ABC")
+        );
+        assert_eq!(
+            parsed
+                .get_subfield(Field::AdditionalDataPrivate, 11)
+                .unwrap(),
+            None
+        );
+        assert!(parsed.get_subfield(Field::ProcessingCode, 1).is_err());
+        assert_eq!(parsed.to_bytes(), bytes);
+    }
+
+    #[test]
+    fn test_bitmap_diff() {
+        let mut six = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+        for (field, value) in [
+            (Field::PrimaryAccountNumber, "4111111111111111"),
+            (Field::ProcessingCode, "000000"),
+            (Field::TransactionAmount, "000000010000"),
+            (Field::SystemTraceAuditNumber, "123456"),
+            (Field::CardAcceptorTerminalIdentification, "TERM0001"),
+            (Field::AdditionalDataPrivate, "NOTE"),
+        ] {
+            six.set_field(field, FieldValue::from_string(value))
+                .unwrap();
+        }
+
+        let mut eight = six.clone();
+        eight.remove_field(Field::AdditionalDataPrivate).unwrap();
+        for (field, value) in [
+            (Field::ResponseCode, "00"),
+            (Field::CurrencyCodeTransaction, "840"),
+            (Field::ReceivingInstitutionIdentificationCode, "123456"),
+        ] {
+            eight
+                .set_field(field, FieldValue::from_string(value))
+                .unwrap();
+        }
+        assert_eq!(eight.get_field_numbers().len(), 8);
 
+        let (six_only, eight_only) = six.bitmap_diff(&eight);
+        let (fields, count) = six_only.get_set_fields();
+        assert_eq!(fields[..count], [48]);
+        let (fields, count) = eight_only.get_set_fields();
+        assert_eq!(fields[..count], [1, 39, 49, 100]);
+
+        let (left, right) = six.bitmap_diff(&six.clone());
+        assert!(left.is_empty() && right.is_empty());
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_zeroize_sensitive() {
+        let mut msg = ISO8583Message::new(MessageType::FINANCIAL_REQUEST);
         msg.set_field(
             Field::PrimaryAccountNumber,
             FieldValue::from_string("4111111111111111"),
         )
         .unwrap();
+        msg.set_field(Field::ProcessingCode, FieldValue::from_string("000000"))
+            .unwrap();
+        msg.set_field(
+            Field::Track2Data,
+            FieldValue::from_string("4111111111111111=2512"),
+        )
+        .unwrap();
+        msg.set_field(
+            Field::PersonalIdentificationNumberData,
+            FieldValue::from_binary(vec![0xAB; 8]),
+        )
+        .unwrap();
 
-        assert!(msg.has_field(Field::PrimaryAccountNumber));
-        let value = msg.get_field(Field::PrimaryAccountNumber).unwrap();
-        assert_eq!(value.as_string(), Some("4111111111111111"));
+        let mut expected = ISO8583Message::new(MessageType::FINANCIAL_REQUEST);
+        expected
+            .set_field(Field::ProcessingCode, FieldValue::from_string("000000"))
+            .unwrap();
+
+        msg.zeroize_sensitive();
+        assert_eq!(msg.get_field_numbers(), vec![3]);
+        assert_eq!(msg.to_bytes(), expected.to_bytes());
     }
 
     #[test]
-    fn test_remove_field() {
+    fn test_additional_amounts() {
+        let mut msg = ISO8583Message::new(MessageType::FINANCIAL_RESPONSE);
+        assert!(msg.get_additional_amounts().is_err());
+
+        msg.set_field(
+            Field::AdditionalAmounts,
+            FieldValue::from_string("1001840C0000000150001002840C000000017500"),
+        )
+        .unwrap();
+        let amounts = msg.get_additional_amounts().unwrap();
+        assert!(amounts[0].amount_type.is_available());
+        assert!(amounts[1].amount_type.is_balance() && !amounts[1].amount_type.is_available());
+        assert_eq!(amounts[1].amount, 17500);
+    }
+
+    #[test]
+    fn test_missing_required() {
         let mut msg = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+        for (field, value) in [
+            (Field::ProcessingCode, "000000"),
+            (Field::SystemTraceAuditNumber, "123456"),
+            (Field::LocalTransactionTime, "120000"),
+            (Field::LocalTransactionDate, "1016"),
+        ] {
+            msg.set_field(field, FieldValue::from_string(value))
+                .unwrap();
+        }
+
+        let profile = MtiProfile::for_mti(MessageType::AUTHORIZATION_REQUEST);
+        assert_eq!(
+            msg.missing_required(&profile),
+            vec![Field::PrimaryAccountNumber, Field::TransactionAmount]
+        );
 
+        let custom = MtiProfile::new(
+            MessageType::AUTHORIZATION_REQUEST,
+            vec![
+                Field::ResponseCode,
+                Field::ProcessingCode,
+                Field::Track2Data,
+            ],
+        );
+        assert_eq!(
+            msg.missing_required(&custom),
+            vec![Field::Track2Data, Field::ResponseCode]
+        );
+    }
+
+    #[test]
+    fn test_ascii_hex_bitmap_roundtrip() {
+        let mut msg = ISO8583Message::new(MessageType::NETWORK_MANAGEMENT_REQUEST);
         msg.set_field(
-            Field::PrimaryAccountNumber,
-            FieldValue::from_string("4111111111111111"),
+            Field::SystemTraceAuditNumber,
+            FieldValue::from_string("000001"),
+        )
+        .unwrap();
+        msg.set_field(
+            Field::NetworkManagementInformationCode,
+            FieldValue::from_string("301"),
         )
         .unwrap();
 
-        assert!(msg.has_field(Field::PrimaryAccountNumber));
+        let bytes = msg.to_bytes_with_bitmap(BitmapEncoding::AsciiHex);
+        assert_eq!(&bytes[4..36], b"80200000000000000400000000000000");
+        assert_eq!(&bytes[36..], b"000001301");
 
-        msg.remove_field(Field::PrimaryAccountNumber).unwrap();
+        let options = ParseOptions::new().bitmap_encoding(BitmapEncoding::AsciiHex);
+        let parsed = ISO8583Message::from_bytes_with_options(&bytes, &options).unwrap();
+        assert_eq!(parsed, msg);
 
-        assert!(!msg.has_field(Field::PrimaryAccountNumber));
+        // '0'/'1' characters: fields start right after 128 bitmap characters
+        let bytes = msg.to_bytes_with_bitmap(BitmapEncoding::AsciiBits);
+        assert_eq!(&bytes[4..12], b"10000000");
+        assert_eq!(&bytes[132..], b"000001301");
+        let options = ParseOptions::new().bitmap_encoding(BitmapEncoding::AsciiBits);
+        let parsed = ISO8583Message::from_bytes_with_options(&bytes, &options).unwrap();
+        assert_eq!(parsed, msg);
+        assert_eq!(
+            ISO8583Message::parse_header_with_options(&bytes, &options)
+                .unwrap()
+                .2,
+            132
+        );
+    }
+
+    #[test]
+    fn test_truncate_to_field() {
+        let mut msg = ISO8583Message::new(MessageType::FINANCIAL_REQUEST);
+        for (field, value) in [
+            (Field::ProcessingCode, "000000"),
+            (Field::SystemTraceAuditNumber, "123456"),
+            (Field::NetworkManagementInformationCode, "001"),
+            (Field::OriginalDataElements, "0".repeat(42).as_str()),
+        ] {
+            msg.set_field(field, FieldValue::from_string(value))
+                .unwrap();
+        }
+        assert!(msg.bitmap().is_set(1));
+
+        msg.truncate_to_field(64);
+        assert_eq!(msg.get_field_numbers(), vec![3, 11]);
+        assert!(!msg.bitmap().is_set(1));
+
+        let bytes = msg.to_bytes();
+        assert_eq!(bytes.len(), 4 + 8 + 6 + 6);
+        assert_eq!(&bytes[4..12], &[0x20, 0x20, 0, 0, 0, 0, 0, 0]);
+
+        msg.truncate_to_field(3);
+        assert_eq!(msg.get_field_numbers(), vec![3]);
+    }
+
+    #[test]
+    fn test_bcd_mti_roundtrip() {
+        let mut msg = ISO8583Message::new(MessageType::FINANCIAL_REQUEST);
+        msg.set_field(Field::ProcessingCode, FieldValue::from_string("000000"))
+            .unwrap();
+        msg.set_field(
+            Field::NetworkManagementInformationCode,
+            FieldValue::from_string("301"),
+        )
+        .unwrap();
+
+        let options = ParseOptions::new().mti_encoding(Encoding::BCD);
+        let bytes = msg.to_bytes_with_options(&options).unwrap();
+        assert_eq!(bytes.len(), msg.to_bytes().len() - 2);
+        assert_eq!(&bytes[..2], &[0x02, 0x00]);
+        assert_eq!(
+            ISO8583Message::from_bytes_with_options(&bytes, &options).unwrap(),
+            msg
+        );
+
+        // Combined with an ASCII hex bitmap
+        let options = options.bitmap_encoding(BitmapEncoding::AsciiHex);
+        let bytes = msg.to_bytes_with_options(&options).unwrap();
+        assert_eq!(&bytes[2..34], b"A0000000000000000400000000000000");
+        assert_eq!(
+            ISO8583Message::from_bytes_with_options(&bytes, &options).unwrap(),
+            msg
+        );
+
+        let options = ParseOptions::new().mti_encoding(Encoding::EBCDIC);
+        let bytes = msg.to_bytes_with_options(&options).unwrap();
+        assert_eq!(&bytes[..4], &[0xF0, 0xF2, 0xF0, 0xF0]);
+        assert_eq!(
+            ISO8583Message::from_bytes_with_options(&bytes, &options).unwrap(),
+            msg
+        );
+    }
+
+    #[test]
+    fn test_trimmed_ids() {
+        let mut left = ISO8583Message::new(MessageType::FINANCIAL_REQUEST);
+        left.set_field(
+            Field::CardAcceptorTerminalIdentification,
+            FieldValue::from_string("1234    "),
+        )
+        .unwrap();
+        left.set_field(
+            Field::CardAcceptorIdentificationCode,
+            FieldValue::from_string("MERCHANT1"),
+        )
+        .unwrap();
+        let left = ISO8583Message::from_bytes(&left.to_bytes()).unwrap();
+
+        let mut right = ISO8583Message::new(MessageType::FINANCIAL_REQUEST);
+        right
+            .set_field(
+                Field::CardAcceptorTerminalIdentification,
+                FieldValue::from_string("00001234"),
+            )
+            .unwrap();
+        right
+            .set_field(
+                Field::CardAcceptorIdentificationCode,
+                FieldValue::from_string("000000MERCHANT1"),
+            )
+            .unwrap();
+
+        assert_eq!(left.terminal_id_trimmed(), Some("1234"));
+        assert_eq!(left.terminal_id_trimmed(), right.terminal_id_trimmed());
+        assert_eq!(left.merchant_id_trimmed(), Some("MERCHANT1"));
+        assert_eq!(left.merchant_id_trimmed(), right.merchant_id_trimmed());
+        assert_eq!(
+            ISO8583Message::new(MessageType::FINANCIAL_REQUEST).terminal_id_trimmed(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_masked_bytes() {
+        let mut msg = ISO8583Message::new(MessageType::FINANCIAL_REQUEST);
+        for (field, value) in [
+            (Field::PrimaryAccountNumber, "4111111111111111"),
+            (Field::ProcessingCode, "000000"),
+            (Field::Track2Data, "4111111111111111=2512"),
+            (Field::CardAcceptorTerminalIdentification, "TERM0001"),
+        ] {
+            msg.set_field(field, FieldValue::from_string(value))
+                .unwrap();
+        }
+        msg.set_field(
+            Field::PersonalIdentificationNumberData,
+            FieldValue::from_binary(vec![0xAB; 8]),
+        )
+        .unwrap();
+
+        let plain = msg.to_bytes();
+        let masked = msg.masked_bytes();
+        assert_eq!(masked.len(), plain.len());
+
+        // MTI + bitmap, then: 2 (16+LL), 3 (6), 35 (21+LL), 41 (8), 52 (8)
+        let header = 4 + 8;
+        assert_eq!(masked[..header], plain[..header]);
+        assert_eq!(&masked[header..header + 18], b"16****************");
+        let pos = header + 18;
+        assert_eq!(masked[pos..pos + 6], plain[pos..pos + 6]);
+        let pos = pos + 6;
+        assert_eq!(&masked[pos..pos + 2], b"21");
+        assert!(masked[pos + 2..pos + 23].iter().all(|&b| b == b'*'));
+        let pos = pos + 23;
+        assert_eq!(&masked[pos..pos + 8], b"TERM0001");
+        assert_eq!(&masked[pos + 8..], b"********");
+
+        let text = String::from_utf8_lossy(&masked);
+        assert!(!text.contains("4111"));
+    }
+
+    #[test]
+    fn test_auth_id_length_from_field_27() {
+        let mut msg = ISO8583Message::new(MessageType::AUTHORIZATION_RESPONSE);
+        msg.set_field(
+            Field::SystemTraceAuditNumber,
+            FieldValue::from_string("123456"),
+        )
+        .unwrap();
+        msg.set_field(
+            Field::AuthorizationIdentificationResponse,
+            FieldValue::from_string("AB12"),
+        )
+        .unwrap();
+        msg.set_field(Field::ResponseCode, FieldValue::from_string("00"))
+            .unwrap();
+
+        let options = ParseOptions::new().auth_id_length_from_field_27(true);
+        let bytes = msg.to_bytes_with_options(&options).unwrap();
+        assert!(bytes.ends_with(b"1234564AB1200"));
+
+        let parsed = ISO8583Message::from_bytes_with_options(&bytes, &options).unwrap();
+        assert_eq!(
+            parsed.get_field(Field::AuthorizingIdentificationResponseLength),
+            Some(&FieldValue::from_string("4"))
+        );
+        assert_eq!(
+            parsed.get_field(Field::AuthorizationIdentificationResponse),
+            Some(&FieldValue::from_string("AB12"))
+        );
+        assert_eq!(
+            parsed.get_field(Field::ResponseCode),
+            msg.get_field(Field::ResponseCode)
+        );
+
+        // The default fixed-6 reading swallows the response code
+        assert!(ISO8583Message::from_bytes(&bytes).is_err());
+
+        msg.set_field(
+            Field::AuthorizationIdentificationResponse,
+            FieldValue::from_string("ABCDEFGHIJ"),
+        )
+        .unwrap();
+        assert!(msg.to_bytes_with_options(&options).is_err());
+    }
+
+    #[test]
+    fn test_mark_fallback() {
+        let mut msg = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+        assert_eq!(msg.mark_fallback(), Err(ISO8583Error::FieldNotPresent(22)));
+
+        msg.set_field(
+            Field::PointOfServiceEntryMode,
+            FieldValue::from_string("072"),
+        )
+        .unwrap();
+        assert!(msg.get_pos_entry_mode().unwrap().is_chip());
+
+        msg.mark_fallback().unwrap();
+        let mode = msg.get_pos_entry_mode().unwrap();
+        assert!(mode.is_fallback());
+        assert_eq!(mode.pin_capability, crate::PinCapability::NotCapable);
+        assert_eq!(
+            msg.get_field(Field::PointOfServiceEntryMode)
+                .unwrap()
+                .as_string(),
+            Some("802")
+        );
     }
 
     #[test]
@@ -490,4 +5420,26 @@ mod tests {
         // This is expected behavior
         assert!(msg.build().is_err());
     }
+
+    #[test]
+    fn test_build_unchecked() {
+        let builder = || {
+            ISO8583Message::builder()
+                .mti(MessageType::FINANCIAL_REQUEST)
+                .field(Field::ProcessingCode, "000000")
+                .field_num(200, "X")
+        };
+        assert!(builder().build().is_err());
+
+        let msg = builder().build_unchecked();
+        assert_eq!(msg.mti, MessageType::FINANCIAL_REQUEST);
+        assert_eq!(msg.get_field_numbers(), vec![3]);
+        assert!(crate::validation::Validator::validate_required_fields(&msg).is_err());
+
+        // An MTI that `build` rejects still goes through
+        let mut bad_mti = MessageType::FINANCIAL_REQUEST;
+        bad_mti.class = crate::mti::MessageClass::Reserved;
+        let msg = MessageBuilder::new().mti(bad_mti).build_unchecked();
+        assert_eq!(msg.mti, bad_mti);
+    }
 }
@@ -0,0 +1,91 @@
+//! Amount arithmetic in minor units
+//!
+//! Amounts are kept as integers of the currency's minor unit (e.g. cents),
+//! and all arithmetic is exact integer math; `f64` is never used.
+
+use crate::error::{ISO8583Error, Result};
+
+/// Parse a conversion rate field (9, 10 or 11 in some networks)
+///
+/// Returns `(mantissa, decimal_places)`: the first digit is the number of
+/// decimal places in the remaining 7 digits, so `"69972522"` is
+/// `(9972522, 6)`, i.e. 9.972522.
+pub fn parse_conversion_rate(rate: &str, field: u8) -> Result<(i64, u32)> {
+    if rate.len() != 8 || !rate.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(ISO8583Error::invalid_field_value(
+            field,
+            "Conversion rate must be 8 digits",
+        ));
+    }
+
+    let places = u32::from(rate.as_bytes()[0] - b'0');
+    if places > 7 {
+        return Err(ISO8583Error::invalid_field_value(
+            field,
+            format!("Conversion rate has {} decimal places, at most 7", places),
+        ));
+    }
+
+    let mantissa = rate[1..].parse().unwrap_or_default();
+    Ok((mantissa, places))
+}
+
+/// Convert a transaction amount to settlement currency using field 9
+///
+/// The product is rounded half away from zero to the nearest minor unit.
+///
+/// # Example
+/// ```
+/// use iso8583_core::money::apply_conversion_rate;
+///
+/// // 100.00 at a rate of 0.851234
+/// assert_eq!(apply_conversion_rate(10000, "60851234")?, 8512);
+/// # Ok::<(), iso8583_core::ISO8583Error>(())
+/// ```
+pub fn apply_conversion_rate(amount_minor: i64, rate_field9: &str) -> Result<i64> {
+    let (mantissa, places) = parse_conversion_rate(rate_field9, 9)?;
+    let divisor = 10i128.pow(places);
+
+    let product = i128::from(amount_minor) * i128::from(mantissa);
+    let half = divisor / 2;
+    let rounded = if product >= 0 {
+        (product + half) / divisor
+    } else {
+        (product - half) / divisor
+    };
+
+    i64::try_from(rounded).map_err(|_| {
+        ISO8583Error::invalid_field_value(9, "Converted amount does not fit in 64 bits")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversion_rates() {
+        // Rate 1.000000
+        assert_eq!(apply_conversion_rate(12345, "61000000").unwrap(), 12345);
+        // Rate 9.972522
+        assert_eq!(apply_conversion_rate(10000, "69972522").unwrap(), 99725);
+        // Rate 0.0012345 rounds to the nearest minor unit
+        assert_eq!(apply_conversion_rate(100000, "70012345").unwrap(), 123);
+        // Rate 1234567 (no decimal places)
+        assert_eq!(apply_conversion_rate(2, "01234567").unwrap(), 2469134);
+        // Half away from zero, including negative amounts (reversals)
+        assert_eq!(apply_conversion_rate(1, "15000000").unwrap(), 500000);
+        assert_eq!(apply_conversion_rate(1, "70000005").unwrap(), 0);
+        assert_eq!(apply_conversion_rate(1, "60500000").unwrap(), 1);
+        assert_eq!(apply_conversion_rate(-1, "60500000").unwrap(), -1);
+        assert_eq!(apply_conversion_rate(0, "69972522").unwrap(), 0);
+    }
+
+    #[test]
+    fn test_invalid_rates() {
+        assert!(apply_conversion_rate(100, "6100000").is_err()); // 7 digits
+        assert!(apply_conversion_rate(100, "6100000A").is_err());
+        assert!(apply_conversion_rate(100, "81000000").is_err()); // 8 places
+        assert!(apply_conversion_rate(i64::MAX, "09999999").is_err()); // Overflow
+    }
+}
@@ -20,7 +20,12 @@ pub struct MessageType {
 }
 
 /// Message Class (2nd digit of MTI)
+///
+/// With the `serde` feature, serialized as the variant name (e.g.
+/// `"NetworkManagement"`); the same applies to [`MessageFunction`] and
+/// [`MessageOrigin`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MessageClass {
     /// Reserved for ISO use (0xxx)
     Reserved = 0,
@@ -46,6 +51,7 @@ pub enum MessageClass {
 
 /// Message Function (3rd digit of MTI)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MessageFunction {
     /// Request (xx0x)
     Request = 0,
@@ -71,6 +77,7 @@ pub enum MessageFunction {
 
 /// Message Origin (4th digit of MTI)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MessageOrigin {
     /// Acquirer (xxx0)
     Acquirer = 0,
@@ -232,6 +239,108 @@ impl MessageType {
         }
     }
 
+    /// Create a new MTI from components, rejecting combinations ISO does not define
+    ///
+    /// Use [`MessageType::new`] to build an unchecked MTI (e.g. for private
+    /// network formats).
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::mti::*;
+    ///
+    /// let mti = MessageType::new_checked(
+    ///     0,
+    ///     MessageClass::Financial,
+    ///     MessageFunction::Request,
+    ///     MessageOrigin::Acquirer,
+    /// )
+    /// .unwrap();
+    /// assert_eq!(mti, MessageType::FINANCIAL_REQUEST);
+    ///
+    /// assert!(MessageType::new_checked(
+    ///     0,
+    ///     MessageClass::Financial,
+    ///     MessageFunction::Instruction,
+    ///     MessageOrigin::Acquirer,
+    /// )
+    /// .is_err());
+    /// ```
+    pub fn new_checked(
+        version: u8,
+        class: MessageClass,
+        function: MessageFunction,
+        origin: MessageOrigin,
+    ) -> Result<Self> {
+        let mti = Self::new(version, class, function, origin);
+        mti.check()?;
+        Ok(mti)
+    }
+
+    /// Check that this MTI is a combination defined by ISO
+    ///
+    /// Rejects versions above 9, the reserved class, function and origin
+    /// digits, and instruction functions outside the file action,
+    /// administrative and network management classes.
+    pub fn check(&self) -> Result<()> {
+        if self.version > 9 {
+            return Err(ISO8583Error::InvalidMTI(format!(
+                "Version must be a single digit, got {}",
+                self.version
+            )));
+        }
+
+        if matches!(
+            self.class,
+            MessageClass::Reserved | MessageClass::ReservedISO
+        ) {
+            return Err(ISO8583Error::InvalidMessageClass(format!(
+                "Message class {} is reserved",
+                self.class.to_digit()
+            )));
+        }
+
+        if matches!(
+            self.function,
+            MessageFunction::Reserved8 | MessageFunction::Reserved9
+        ) {
+            return Err(ISO8583Error::InvalidMessageFunction(format!(
+                "Message function {} is reserved",
+                self.function.to_digit()
+            )));
+        }
+
+        if matches!(
+            self.function,
+            MessageFunction::Instruction | MessageFunction::InstructionAck
+        ) && !matches!(
+            self.class,
+            MessageClass::FileActions
+                | MessageClass::Administrative
+                | MessageClass::NetworkManagement
+        ) {
+            return Err(ISO8583Error::InvalidMessageFunction(format!(
+                "Instruction function {} is not defined for message class {}",
+                self.function.to_digit(),
+                self.class.to_digit()
+            )));
+        }
+
+        if matches!(
+            self.origin,
+            MessageOrigin::Reserved6
+                | MessageOrigin::Reserved7
+                | MessageOrigin::Reserved8
+                | MessageOrigin::Reserved9
+        ) {
+            return Err(ISO8583Error::InvalidMessageOrigin(format!(
+                "Message origin {} is reserved",
+                self.origin.to_digit()
+            )));
+        }
+
+        Ok(())
+    }
+
     /// Parse MTI from bytes
     pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
         if bytes.len() < 4 {
@@ -252,6 +361,30 @@ impl MessageType {
         self.to_string().into_bytes()
     }
 
+    /// Parse MTI from 2 BCD bytes, e.g. `[0x01, 0x00]` for "0100"
+    pub fn from_bcd_bytes(bytes: &[u8; 2]) -> Result<Self> {
+        crate::encoding::decode_bcd(bytes, 4)
+            .map_err(|e| ISO8583Error::InvalidMTI(e.to_string()))?
+            .parse()
+    }
+
+    /// Convert to 2 BCD bytes
+    ///
+    /// Fails if the version does not fit a single digit.
+    pub fn to_bcd_bytes(&self) -> Result<[u8; 2]> {
+        if self.version > 9 {
+            return Err(ISO8583Error::InvalidMTI(format!(
+                "Version must be a single digit, got {}",
+                self.version
+            )));
+        }
+
+        Ok([
+            (self.version << 4) | self.class.to_digit(),
+            (self.function.to_digit() << 4) | self.origin.to_digit(),
+        ])
+    }
+
     /// Check if this is a request message
     pub fn is_request(&self) -> bool {
         matches!(self.function, MessageFunction::Request)
@@ -270,6 +403,43 @@ impl MessageType {
         )
     }
 
+    /// Check if this is a repeat (retransmitted) message
+    pub fn is_repeat(&self) -> bool {
+        matches!(
+            self.origin,
+            MessageOrigin::AcquirerRepeat
+                | MessageOrigin::IssuerRepeat
+                | MessageOrigin::OtherRepeat
+        )
+    }
+
+    /// Get the repeat form of this MTI (e.g. 0200 -> 0201)
+    ///
+    /// Already-repeat MTIs are returned unchanged, as are reserved origins,
+    /// which have no repeat form.
+    pub fn as_repeat(&self) -> Self {
+        let origin = match self.origin {
+            MessageOrigin::Acquirer => MessageOrigin::AcquirerRepeat,
+            MessageOrigin::Issuer => MessageOrigin::IssuerRepeat,
+            MessageOrigin::Other => MessageOrigin::OtherRepeat,
+            origin => origin,
+        };
+        Self { origin, ..*self }
+    }
+
+    /// Get the original (non-repeat) form of this MTI (e.g. 0201 -> 0200)
+    ///
+    /// Non-repeat and reserved origins are returned unchanged.
+    pub fn as_original(&self) -> Self {
+        let origin = match self.origin {
+            MessageOrigin::AcquirerRepeat => MessageOrigin::Acquirer,
+            MessageOrigin::IssuerRepeat => MessageOrigin::Issuer,
+            MessageOrigin::OtherRepeat => MessageOrigin::Other,
+            origin => origin,
+        };
+        Self { origin, ..*self }
+    }
+
     /// Get the corresponding response MTI for a request
     pub fn to_response(&self) -> Result<Self> {
         if !self.is_request() {
@@ -285,12 +455,34 @@ impl MessageType {
             origin: self.origin,
         })
     }
+
+    /// Human-readable description for logs and dashboards
+    ///
+    /// `to_string()` still gives the 4-digit wire code.
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::MessageType;
+    ///
+    /// assert_eq!(
+    ///     MessageType::AUTHORIZATION_REQUEST.describe(),
+    ///     "0100 Authorization Request (Acquirer)"
+    /// );
+    /// ```
+    pub fn describe(&self) -> String {
+        format!(
+            "{} {} {} ({})",
+            self, self.class, self.function, self.origin
+        )
+    }
 }
 
 impl std::str::FromStr for MessageType {
     type Err = ISO8583Error;
 
+    /// Surrounding ASCII whitespace (e.g. from padded log columns) is ignored
     fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim_matches(|c: char| c.is_ascii_whitespace());
         if s.len() != 4 {
             return Err(ISO8583Error::InvalidMTI(format!(
                 "MTI must be 4 digits, got {}",
@@ -404,10 +596,93 @@ impl fmt::Display for MessageType {
     }
 }
 
+impl fmt::Display for MessageClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Reserved | Self::ReservedISO => "Reserved",
+            Self::Authorization => "Authorization",
+            Self::Financial => "Financial",
+            Self::FileActions => "File Actions",
+            Self::Reversal => "Reversal",
+            Self::Reconciliation => "Reconciliation",
+            Self::Administrative => "Administrative",
+            Self::FeeCollection => "Fee Collection",
+            Self::NetworkManagement => "Network Management",
+        })
+    }
+}
+
+impl fmt::Display for MessageFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Request => "Request",
+            Self::Response => "Response",
+            Self::Advice => "Advice",
+            Self::AdviceResponse => "Advice Response",
+            Self::Notification => "Notification",
+            Self::NotificationAck => "Notification Acknowledgement",
+            Self::Instruction => "Instruction",
+            Self::InstructionAck => "Instruction Acknowledgement",
+            Self::Reserved8 | Self::Reserved9 => "Reserved",
+        })
+    }
+}
+
+impl fmt::Display for MessageOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Acquirer => "Acquirer",
+            Self::AcquirerRepeat => "Acquirer Repeat",
+            Self::Issuer => "Issuer",
+            Self::IssuerRepeat => "Issuer Repeat",
+            Self::Other => "Other",
+            Self::OtherRepeat => "Other Repeat",
+            Self::Reserved6 | Self::Reserved7 | Self::Reserved8 | Self::Reserved9 => "Reserved",
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_roundtrip() {
+        for digit in 0..=9 {
+            let class = MessageClass::from_digit(digit).unwrap();
+            let json = serde_json::to_string(&class).unwrap();
+            assert_eq!(serde_json::from_str::<MessageClass>(&json).unwrap(), class);
+
+            let function = MessageFunction::from_digit(digit).unwrap();
+            let json = serde_json::to_string(&function).unwrap();
+            assert_eq!(
+                serde_json::from_str::<MessageFunction>(&json).unwrap(),
+                function
+            );
+
+            let origin = MessageOrigin::from_digit(digit).unwrap();
+            let json = serde_json::to_string(&origin).unwrap();
+            assert_eq!(
+                serde_json::from_str::<MessageOrigin>(&json).unwrap(),
+                origin
+            );
+        }
+
+        assert_eq!(
+            serde_json::to_string(&MessageClass::NetworkManagement).unwrap(),
+            r#""NetworkManagement""#
+        );
+        assert_eq!(
+            serde_json::to_string(&MessageFunction::AdviceResponse).unwrap(),
+            r#""AdviceResponse""#
+        );
+        assert_eq!(
+            serde_json::to_string(&MessageOrigin::AcquirerRepeat).unwrap(),
+            r#""AcquirerRepeat""#
+        );
+    }
+
     #[test]
     fn test_mti_parsing() {
         let mti: MessageType = "0100".parse().unwrap();
@@ -418,6 +693,27 @@ mod tests {
         assert_eq!(mti.to_string(), "0100");
     }
 
+    #[test]
+    fn test_mti_parsing_trims_whitespace() {
+        assert_eq!(
+            "0100 ".parse::<MessageType>().unwrap(),
+            MessageType::AUTHORIZATION_REQUEST
+        );
+        assert_eq!(
+            "  0200".parse::<MessageType>().unwrap(),
+            MessageType::FINANCIAL_REQUEST
+        );
+        assert_eq!(
+            "\t0800\r\n".parse::<MessageType>().unwrap(),
+            MessageType::NETWORK_MANAGEMENT_REQUEST
+        );
+        // Length is still checked on the trimmed value
+        assert!(" 100 ".parse::<MessageType>().is_err());
+        assert!("01 00".parse::<MessageType>().is_err());
+        // Wire parsing is unaffected
+        assert!(MessageType::from_bytes(b"010 ").is_err());
+    }
+
     #[test]
     fn test_mti_constants() {
         assert_eq!(MessageType::AUTHORIZATION_REQUEST.to_string(), "0100");
@@ -457,6 +753,118 @@ mod tests {
         assert!(err.is_err());
     }
 
+    #[test]
+    fn test_repeat_origin() {
+        let original = MessageType::FINANCIAL_REQUEST;
+        let repeat = original.as_repeat();
+        assert_eq!(repeat.to_string(), "0201");
+        assert!(repeat.is_repeat());
+        assert!(!original.is_repeat());
+        assert_eq!(repeat.as_repeat(), repeat);
+        assert_eq!(repeat.as_original(), original);
+        assert_eq!(original.as_original(), original);
+
+        let issuer: MessageType = "0422".parse().unwrap();
+        assert_eq!(issuer.as_repeat().to_string(), "0423");
+        let other: MessageType = "0124".parse().unwrap();
+        assert_eq!(other.as_repeat().to_string(), "0125");
+        assert_eq!(other.as_repeat().as_original(), other);
+
+        // Reserved origins have no repeat form
+        let reserved: MessageType = "0107".parse().unwrap();
+        assert_eq!(reserved.as_repeat(), reserved);
+        assert_eq!(reserved.as_original(), reserved);
+        assert!(!reserved.is_repeat());
+    }
+
+    #[test]
+    fn test_new_checked() {
+        let mti = MessageType::new_checked(
+            0,
+            MessageClass::Authorization,
+            MessageFunction::Request,
+            MessageOrigin::Acquirer,
+        )
+        .unwrap();
+        assert_eq!(mti, MessageType::AUTHORIZATION_REQUEST);
+
+        assert!(MessageType::new_checked(
+            10,
+            MessageClass::Authorization,
+            MessageFunction::Request,
+            MessageOrigin::Acquirer,
+        )
+        .is_err());
+        assert!(MessageType::new_checked(
+            0,
+            MessageClass::Authorization,
+            MessageFunction::Request,
+            MessageOrigin::Reserved6,
+        )
+        .is_err());
+        assert!(MessageType::new_checked(
+            0,
+            MessageClass::Financial,
+            MessageFunction::Instruction,
+            MessageOrigin::Acquirer,
+        )
+        .is_err());
+        assert!(MessageType::new_checked(
+            0,
+            MessageClass::Administrative,
+            MessageFunction::Instruction,
+            MessageOrigin::Acquirer,
+        )
+        .is_ok());
+
+        // Unchecked construction is still available
+        let mti = MessageType::new(
+            0,
+            MessageClass::Financial,
+            MessageFunction::Instruction,
+            MessageOrigin::Acquirer,
+        );
+        assert_eq!(mti.to_string(), "0260");
+    }
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(
+            MessageClass::NetworkManagement.to_string(),
+            "Network Management"
+        );
+        assert_eq!(
+            MessageFunction::AdviceResponse.to_string(),
+            "Advice Response"
+        );
+        assert_eq!(MessageOrigin::IssuerRepeat.to_string(), "Issuer Repeat");
+
+        assert_eq!(
+            MessageType::REVERSAL_ADVICE_RESPONSE.describe(),
+            "0430 Reversal Advice Response (Acquirer)"
+        );
+        let mti: MessageType = "0812".parse().unwrap();
+        assert_eq!(mti.describe(), "0812 Network Management Response (Issuer)");
+        assert_eq!(mti.to_string(), "0812");
+    }
+
+    #[test]
+    fn test_bcd_bytes() {
+        let mti = MessageType::from_bcd_bytes(&[0x01, 0x00]).unwrap();
+        assert_eq!(mti, MessageType::AUTHORIZATION_REQUEST);
+        assert_eq!(
+            MessageType::REVERSAL_ADVICE.to_bcd_bytes(),
+            Ok([0x04, 0x20])
+        );
+
+        let mut mti = MessageType::AUTHORIZATION_REQUEST;
+        mti.version = 2;
+        assert_eq!(mti.to_bcd_bytes(), Ok([0x21, 0x00]));
+        mti.version = 10;
+        assert!(mti.to_bcd_bytes().is_err());
+        assert!(MessageType::from_bcd_bytes(&[0x0A, 0x00]).is_err());
+    }
+
     #[test]
     fn test_invalid_mti() {
         assert!("123".parse::<MessageType>().is_err()); // Too short
@@ -0,0 +1,173 @@
+//! Network management messages (08xx)
+//!
+//! Acquirers and switches keep connections alive with an echo test: an
+//! 0800 with network management information code 301 (field 70), answered
+//! by an 0810 that echoes the STAN (field 11) with response code 00.
+
+use crate::error::{ISO8583Error, Result};
+use crate::field::{Field, FieldValue};
+use crate::message::ISO8583Message;
+use crate::mti::MessageType;
+use crate::response_code::ResponseCode;
+use crate::stan::MAX_STAN;
+
+/// Network management information code for an echo test (field 70)
+pub const ECHO_TEST_CODE: &str = "301";
+
+/// Builders and checks for network management messages
+#[derive(Debug, Clone, Copy)]
+pub struct NetworkMessage;
+
+impl NetworkMessage {
+    /// Build an 0800 echo test with the given STAN
+    ///
+    /// Sets fields 7 (current UTC time), 11 and 70 (301).
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::*;
+    /// use iso8583_core::network::NetworkMessage;
+    ///
+    /// let request = NetworkMessage::echo_test(42)?;
+    /// assert_eq!(request.mti, MessageType::NETWORK_MANAGEMENT_REQUEST);
+    /// assert_eq!(
+    ///     request.get_field(Field::SystemTraceAuditNumber).unwrap().as_string(),
+    ///     Some("000042")
+    /// );
+    /// # Ok::<(), ISO8583Error>(())
+    /// ```
+    pub fn echo_test(stan: u32) -> Result<ISO8583Message> {
+        if stan > MAX_STAN {
+            return Err(ISO8583Error::invalid_field_value(
+                11,
+                format!("STAN {} exceeds {}", stan, MAX_STAN),
+            ));
+        }
+
+        let mut msg = ISO8583Message::new(MessageType::NETWORK_MANAGEMENT_REQUEST);
+        msg.set_field(
+            Field::TransmissionDateTime,
+            FieldValue::from_string(crate::utils::generate_transmission_datetime()),
+        )?;
+        msg.set_field(
+            Field::SystemTraceAuditNumber,
+            FieldValue::from_string(format!("{:06}", stan)),
+        )?;
+        msg.set_field(
+            Field::NetworkManagementInformationCode,
+            FieldValue::from_string(ECHO_TEST_CODE),
+        )?;
+        Ok(msg)
+    }
+
+    /// Check that `response` is a successful 0810 answer to the echo `request`
+    ///
+    /// The response must be an 0810 carrying the request's STAN (field 11)
+    /// and response code 00. If the request has field 70, the response must
+    /// echo it too. A stale reply to an earlier echo is rejected.
+    pub fn is_valid_echo_response(request: &ISO8583Message, response: &ISO8583Message) -> bool {
+        if request.mti != MessageType::NETWORK_MANAGEMENT_REQUEST
+            || response.mti != MessageType::NETWORK_MANAGEMENT_RESPONSE
+        {
+            return false;
+        }
+
+        let stan = match request.get_field(Field::SystemTraceAuditNumber) {
+            Some(stan) => stan,
+            None => return false,
+        };
+        if response.get_field(Field::SystemTraceAuditNumber) != Some(stan) {
+            return false;
+        }
+
+        if let Some(code) = request.get_field(Field::NetworkManagementInformationCode) {
+            if response.get_field(Field::NetworkManagementInformationCode) != Some(code) {
+                return false;
+            }
+        }
+
+        response
+            .get_response_code()
+            .is_ok_and(|rc| rc == ResponseCode::APPROVED)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn echo_response(request: &ISO8583Message, stan: &str, rc: &str) -> ISO8583Message {
+        let mut response = ISO8583Message::new(MessageType::NETWORK_MANAGEMENT_RESPONSE);
+        response
+            .merge_from(
+                request,
+                Some(&[
+                    Field::TransmissionDateTime,
+                    Field::NetworkManagementInformationCode,
+                ]),
+            )
+            .unwrap();
+        response
+            .set_field(Field::SystemTraceAuditNumber, FieldValue::from_string(stan))
+            .unwrap();
+        response
+            .set_field(Field::ResponseCode, FieldValue::from_string(rc))
+            .unwrap();
+        response
+    }
+
+    #[test]
+    fn test_echo_test_request() {
+        let request = NetworkMessage::echo_test(123456).unwrap();
+        assert_eq!(
+            request
+                .get_field(Field::NetworkManagementInformationCode)
+                .unwrap()
+                .as_string(),
+            Some("301")
+        );
+        assert!(request.has_field(Field::TransmissionDateTime));
+        assert!(NetworkMessage::echo_test(1_000_000).is_err());
+    }
+
+    #[test]
+    fn test_echo_response_correlation() {
+        let request = NetworkMessage::echo_test(42).unwrap();
+
+        assert!(NetworkMessage::is_valid_echo_response(
+            &request,
+            &echo_response(&request, "000042", "00")
+        ));
+
+        // Stale reply to an earlier echo
+        assert!(!NetworkMessage::is_valid_echo_response(
+            &request,
+            &echo_response(&request, "000041", "00")
+        ));
+        // Declined
+        assert!(!NetworkMessage::is_valid_echo_response(
+            &request,
+            &echo_response(&request, "000042", "91")
+        ));
+
+        // Missing response code
+        let mut response = echo_response(&request, "000042", "00");
+        response.remove_field(Field::ResponseCode).unwrap();
+        assert!(!NetworkMessage::is_valid_echo_response(&request, &response));
+
+        // Wrong MTI
+        let mut response = echo_response(&request, "000042", "00");
+        response.mti = MessageType::NETWORK_MANAGEMENT_ADVICE;
+        assert!(!NetworkMessage::is_valid_echo_response(&request, &response));
+
+        // Different network management code
+        let mut response = echo_response(&request, "000042", "00");
+        response
+            .set_field(
+                Field::NetworkManagementInformationCode,
+                FieldValue::from_string("001"),
+            )
+            .unwrap();
+        assert!(!NetworkMessage::is_valid_echo_response(&request, &response));
+    }
+}
@@ -0,0 +1,161 @@
+//! Point of Service Condition Code (field 25)
+//!
+//! Two digits describing the conditions under which the transaction took
+//! place, e.g. whether the cardholder and card were present. Fraud scoring
+//! and interchange qualification depend on it.
+
+use crate::error::{ISO8583Error, Result};
+use std::fmt;
+
+/// POS Condition Code (2 digits)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PosConditionCode {
+    /// Normal presentment (00)
+    Normal,
+    /// Customer not present (01)
+    CustomerNotPresent,
+    /// Unattended terminal able to retain card (02)
+    UnattendedTerminal,
+    /// Merchant suspicious (03)
+    MerchantSuspicious,
+    /// Customer present, card not present (05)
+    CardNotPresent,
+    /// Pre-authorized request (06)
+    Preauthorized,
+    /// Mail or telephone order (08)
+    MailTelephoneOrder,
+    /// Recurring payment (10)
+    Recurring,
+    /// Suspected fraud (11)
+    SuspectedFraud,
+    /// Electronic commerce (59)
+    ECommerce,
+    /// Any other code, preserved as-is
+    Other(u8),
+}
+
+impl PosConditionCode {
+    /// Create from the numeric code
+    pub fn from_code(code: u8) -> Self {
+        match code {
+            0 => Self::Normal,
+            1 => Self::CustomerNotPresent,
+            2 => Self::UnattendedTerminal,
+            3 => Self::MerchantSuspicious,
+            5 => Self::CardNotPresent,
+            6 => Self::Preauthorized,
+            8 => Self::MailTelephoneOrder,
+            10 => Self::Recurring,
+            11 => Self::SuspectedFraud,
+            59 => Self::ECommerce,
+            other => Self::Other(other),
+        }
+    }
+
+    /// Get the numeric code
+    pub fn to_code(&self) -> u8 {
+        match self {
+            Self::Normal => 0,
+            Self::CustomerNotPresent => 1,
+            Self::UnattendedTerminal => 2,
+            Self::MerchantSuspicious => 3,
+            Self::CardNotPresent => 5,
+            Self::Preauthorized => 6,
+            Self::MailTelephoneOrder => 8,
+            Self::Recurring => 10,
+            Self::SuspectedFraud => 11,
+            Self::ECommerce => 59,
+            Self::Other(code) => *code,
+        }
+    }
+
+    /// Check if the card was physically presented at the terminal
+    ///
+    /// Unrecognised codes are treated as card-not-present.
+    pub fn is_card_present(&self) -> bool {
+        matches!(
+            self,
+            Self::Normal | Self::UnattendedTerminal | Self::MerchantSuspicious
+        )
+    }
+
+    /// Check if the transaction is a recurring payment
+    pub fn is_recurring(&self) -> bool {
+        matches!(self, Self::Recurring)
+    }
+
+    /// Check if the transaction is a mail or telephone order
+    pub fn is_mail_order(&self) -> bool {
+        matches!(self, Self::MailTelephoneOrder)
+    }
+}
+
+impl std::str::FromStr for PosConditionCode {
+    type Err = ISO8583Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s.len() != 2 || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ISO8583Error::invalid_field_value(
+                25,
+                "POS condition code must be 2 digits",
+            ));
+        }
+
+        let digits = s.as_bytes();
+        Ok(Self::from_code(
+            (digits[0] - b'0') * 10 + (digits[1] - b'0'),
+        ))
+    }
+}
+
+impl fmt::Display for PosConditionCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}", self.to_code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_common_codes() {
+        let cases = [
+            ("00", PosConditionCode::Normal),
+            ("01", PosConditionCode::CustomerNotPresent),
+            ("02", PosConditionCode::UnattendedTerminal),
+            ("05", PosConditionCode::CardNotPresent),
+            ("08", PosConditionCode::MailTelephoneOrder),
+            ("10", PosConditionCode::Recurring),
+            ("59", PosConditionCode::ECommerce),
+        ];
+
+        for (code, expected) in cases {
+            let parsed: PosConditionCode = code.parse().unwrap();
+            assert_eq!(parsed, expected);
+            assert_eq!(parsed.to_string(), code);
+        }
+
+        assert!(PosConditionCode::Normal.is_card_present());
+        assert!(PosConditionCode::UnattendedTerminal.is_card_present());
+        assert!(!PosConditionCode::CardNotPresent.is_card_present());
+        assert!(!PosConditionCode::MailTelephoneOrder.is_card_present());
+
+        assert!(PosConditionCode::Recurring.is_recurring());
+        assert!(!PosConditionCode::MailTelephoneOrder.is_recurring());
+
+        assert!(PosConditionCode::MailTelephoneOrder.is_mail_order());
+        assert!(!PosConditionCode::ECommerce.is_mail_order());
+    }
+
+    #[test]
+    fn test_unknown_codes_preserved() {
+        let code: PosConditionCode = "42".parse().unwrap();
+        assert_eq!(code, PosConditionCode::Other(42));
+        assert_eq!(code.to_string(), "42");
+        assert!(!code.is_card_present());
+
+        assert!("8".parse::<PosConditionCode>().is_err());
+        assert!("0A".parse::<PosConditionCode>().is_err());
+    }
+}
@@ -0,0 +1,224 @@
+//! Point of Service Entry Mode (field 22)
+//!
+//! Format: PPN where:
+//! - PP (positions 1-2): How the PAN was entered
+//! - N (position 3): Terminal PIN entry capability
+
+use crate::error::{ISO8583Error, Result};
+use std::fmt;
+
+/// POS Entry Mode (3 digits)
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PosEntryMode {
+    pub pan_entry: PanEntryMode,
+    pub pin_capability: PinCapability,
+}
+
+/// PAN entry mode (first 2 digits)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PanEntryMode {
+    /// Unknown (00)
+    Unknown,
+    /// Manual key entry (01)
+    Manual,
+    /// Magnetic stripe, track data may be incomplete (02)
+    MagneticStripe,
+    /// Bar code (03)
+    BarCode,
+    /// Optical character reader (04)
+    Ocr,
+    /// Integrated circuit card (05)
+    Chip,
+    /// Contactless chip (07)
+    ContactlessChip,
+    /// Magnetic stripe read after a failed chip read (80)
+    Fallback,
+    /// Full magnetic stripe read (90)
+    MagneticStripeFull,
+    /// Contactless magnetic stripe (91)
+    ContactlessMagneticStripe,
+    /// Chip read, CVV may be unreliable (95)
+    ChipUnreliableCvv,
+    /// Any other code
+    Other(u8),
+}
+
+/// PIN entry capability (3rd digit)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PinCapability {
+    /// Unknown (0)
+    Unknown,
+    /// Terminal can accept PINs (1)
+    Capable,
+    /// Terminal cannot accept PINs (2)
+    NotCapable,
+    /// PIN pad inoperative (8)
+    PinPadInoperative,
+    /// Any other digit
+    Other(u8),
+}
+
+impl PosEntryMode {
+    /// Create a new entry mode
+    pub fn new(pan_entry: PanEntryMode, pin_capability: PinCapability) -> Self {
+        Self {
+            pan_entry,
+            pin_capability,
+        }
+    }
+
+    /// Get the chip-fallback form of this entry mode (PAN entry 80)
+    ///
+    /// The PIN entry capability is preserved.
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::pos_entry_mode::PosEntryMode;
+    ///
+    /// let chip: PosEntryMode = "051".parse().unwrap();
+    /// assert_eq!(chip.fallback().to_string(), "801");
+    /// ```
+    pub fn fallback(&self) -> Self {
+        Self {
+            pan_entry: PanEntryMode::Fallback,
+            ..*self
+        }
+    }
+
+    /// Check if the card was read by magnetic stripe after a chip failure
+    pub fn is_fallback(&self) -> bool {
+        self.pan_entry == PanEntryMode::Fallback
+    }
+
+    /// Check if the card was read by chip (contact or contactless)
+    pub fn is_chip(&self) -> bool {
+        matches!(
+            self.pan_entry,
+            PanEntryMode::Chip | PanEntryMode::ContactlessChip | PanEntryMode::ChipUnreliableCvv
+        )
+    }
+}
+
+#[allow(missing_docs)]
+impl PanEntryMode {
+    pub fn from_code(code: u8) -> Self {
+        match code {
+            0 => Self::Unknown,
+            1 => Self::Manual,
+            2 => Self::MagneticStripe,
+            3 => Self::BarCode,
+            4 => Self::Ocr,
+            5 => Self::Chip,
+            7 => Self::ContactlessChip,
+            80 => Self::Fallback,
+            90 => Self::MagneticStripeFull,
+            91 => Self::ContactlessMagneticStripe,
+            95 => Self::ChipUnreliableCvv,
+            other => Self::Other(other),
+        }
+    }
+
+    pub fn to_code(&self) -> u8 {
+        match self {
+            Self::Unknown => 0,
+            Self::Manual => 1,
+            Self::MagneticStripe => 2,
+            Self::BarCode => 3,
+            Self::Ocr => 4,
+            Self::Chip => 5,
+            Self::ContactlessChip => 7,
+            Self::Fallback => 80,
+            Self::MagneticStripeFull => 90,
+            Self::ContactlessMagneticStripe => 91,
+            Self::ChipUnreliableCvv => 95,
+            Self::Other(code) => *code,
+        }
+    }
+}
+
+#[allow(missing_docs)]
+impl PinCapability {
+    pub fn from_digit(digit: u8) -> Self {
+        match digit {
+            0 => Self::Unknown,
+            1 => Self::Capable,
+            2 => Self::NotCapable,
+            8 => Self::PinPadInoperative,
+            other => Self::Other(other),
+        }
+    }
+
+    pub fn to_digit(&self) -> u8 {
+        match self {
+            Self::Unknown => 0,
+            Self::Capable => 1,
+            Self::NotCapable => 2,
+            Self::PinPadInoperative => 8,
+            Self::Other(digit) => *digit,
+        }
+    }
+}
+
+impl std::str::FromStr for PosEntryMode {
+    type Err = ISO8583Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s.len() != 3 || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(ISO8583Error::invalid_field_value(
+                22,
+                "POS entry mode must be 3 digits",
+            ));
+        }
+
+        let digits = s.as_bytes();
+        Ok(Self {
+            pan_entry: PanEntryMode::from_code((digits[0] - b'0') * 10 + (digits[1] - b'0')),
+            pin_capability: PinCapability::from_digit(digits[2] - b'0'),
+        })
+    }
+}
+
+impl fmt::Display for PosEntryMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:02}{}",
+            self.pan_entry.to_code(),
+            self.pin_capability.to_digit()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_display() {
+        let mode: PosEntryMode = "051".parse().unwrap();
+        assert_eq!(mode.pan_entry, PanEntryMode::Chip);
+        assert_eq!(mode.pin_capability, PinCapability::Capable);
+        assert!(mode.is_chip());
+        assert_eq!(mode.to_string(), "051");
+
+        let other: PosEntryMode = "812".parse().unwrap();
+        assert_eq!(other.pan_entry, PanEntryMode::Other(81));
+        assert_eq!(other.to_string(), "812");
+
+        assert!("05".parse::<PosEntryMode>().is_err());
+        assert!("05A".parse::<PosEntryMode>().is_err());
+    }
+
+    #[test]
+    fn test_fallback() {
+        let chip: PosEntryMode = "052".parse().unwrap();
+        let fallback = chip.fallback();
+        assert!(fallback.is_fallback());
+        assert!(!chip.is_fallback());
+        assert!(!fallback.is_chip());
+        assert_eq!(fallback.pin_capability, PinCapability::NotCapable);
+        assert_eq!(fallback.to_string(), "802");
+        assert_eq!(fallback.fallback(), fallback);
+    }
+}
@@ -6,7 +6,9 @@
 //! - FF (positions 3-4): From account type
 //! - TT (positions 5-6): To account type
 
+use crate::error::{ISO8583Error, Result};
 use std::fmt;
+use std::hash::{Hash, Hasher};
 
 /// Processing Code (6 digits)
 #[allow(missing_docs)]
@@ -18,7 +20,17 @@ pub struct ProcessingCode {
 }
 
 /// Transaction Type (first 2 digits)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+///
+/// With the `serde` feature, serialized as the variant name (e.g.
+/// `"BalanceInquiry"`), like [`AccountType`].
+///
+/// Codes without a variant are kept as [`Other`](Self::Other), serialized as
+/// `{"Other": 17}`. Equality and hashing go by the code, so an `Other`
+/// holding 00 equals [`Purchase`](Self::Purchase); build values with
+/// [`from_raw`](Self::from_raw) to get the canonical variant.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u8)]
 pub enum TransactionType {
     /// Purchase (00)
     Purchase = 0,
@@ -54,10 +66,82 @@ pub enum TransactionType {
     Refund = 20,
     /// Payment (50)
     Payment = 50,
+    /// Any other code, preserved as-is
+    Other(OtherCode),
 }
 
-/// Account Type (positions 3-4 and 5-6)
+/// A two-digit code (00-99) kept in [`TransactionType::Other`] or
+/// [`AccountType::Other`]
+///
+/// Only codes that fit the two digits can be built, so every processing
+/// code prints as exactly six digits. With the `serde` feature, serialized
+/// as the bare number.
+///
+/// # Example
+/// ```
+/// use iso8583_core::processing_code::OtherCode;
+///
+/// assert_eq!(OtherCode::new(17).map(OtherCode::get), Some(17));
+/// assert_eq!(OtherCode::new(100), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "u8", into = "u8")
+)]
+pub struct OtherCode(u8);
+
+impl OtherCode {
+    /// Wrap `code`, or `None` if it is above 99
+    pub fn new(code: u8) -> Option<Self> {
+        (code <= 99).then_some(Self(code))
+    }
+
+    /// Get the code
+    pub fn get(self) -> u8 {
+        self.0
+    }
+}
+
+impl TryFrom<u8> for OtherCode {
+    type Error = ISO8583Error;
+
+    fn try_from(code: u8) -> Result<Self> {
+        Self::new(code).ok_or_else(|| {
+            ISO8583Error::invalid_field_value(3, "Processing code parts must be 00-99")
+        })
+    }
+}
+
+impl From<OtherCode> for u8 {
+    fn from(code: OtherCode) -> Self {
+        code.0
+    }
+}
+
+/// Reporting bucket for a transaction type
+#[allow(missing_docs)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TransactionCategory {
+    Sale,
+    Cash,
+    Refund,
+    Inquiry,
+    Transfer,
+    Payment,
+    /// Transaction type without a known category
+    Other,
+}
+
+/// Account Type (positions 3-4 and 5-6)
+///
+/// Codes without a variant are kept as [`Other`](Self::Other) by
+/// [`from_raw`](Self::from_raw). As with
+/// [`TransactionType`], equality and hashing go by the code.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u8)]
 pub enum AccountType {
     /// Default/Unspecified (00)
     Default = 0,
@@ -71,6 +155,8 @@ pub enum AccountType {
     Universal = 40,
     /// Investment account (50)
     Investment = 50,
+    /// Any other code, preserved as-is
+    Other(OtherCode),
 }
 
 impl ProcessingCode {
@@ -152,43 +238,107 @@ impl ProcessingCode {
         }
     }
 
+    /// Create from the three raw digit pairs, without interpreting them
+    ///
+    /// Codes without a [`TransactionType`] or [`AccountType`] variant are
+    /// kept as `Other`. Fails if a code is above 99 and so does not fit the
+    /// 6-digit field.
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::{ProcessingCode, TransactionType};
+    ///
+    /// let code = ProcessingCode::from_raw(17, 0, 0)?;
+    /// assert!(matches!(code.transaction_type, TransactionType::Other(c) if c.get() == 17));
+    /// assert_eq!(code.to_string(), "170000");
+    /// assert!(ProcessingCode::from_raw(100, 0, 0).is_err());
+    /// # Ok::<(), iso8583_core::ISO8583Error>(())
+    /// ```
+    pub fn from_raw(transaction_type: u8, from_account: u8, to_account: u8) -> Result<Self> {
+        let invalid =
+            || ISO8583Error::invalid_field_value(3, "Processing code parts must be 00-99");
+
+        Ok(Self {
+            transaction_type: TransactionType::from_raw(transaction_type).ok_or_else(invalid)?,
+            from_account: AccountType::from_raw(from_account).ok_or_else(invalid)?,
+            to_account: AccountType::from_raw(to_account).ok_or_else(invalid)?,
+        })
+    }
+
+    /// Get the three digit pairs as numbers: transaction type, from account
+    /// and to account
+    ///
+    /// This is the lossless view under the semantic enums, e.g. for generic
+    /// routing: `"170099"` gives `(17, 0, 99)`.
+    pub fn raw(&self) -> (u8, u8, u8) {
+        (
+            self.transaction_type.to_code(),
+            self.from_account.to_code(),
+            self.to_account.to_code(),
+        )
+    }
+
     /// Get transaction description
     pub fn description(&self) -> String {
-        let txn_desc = match self.transaction_type {
+        let other;
+        let txn_desc = match self.transaction_type.canonical() {
             TransactionType::Purchase => "Purchase",
             TransactionType::CashWithdrawal => "Cash Withdrawal",
+            TransactionType::DebitAdjustment => "Debit Adjustment",
+            TransactionType::CheckGuarantee => "Check Guarantee",
+            TransactionType::CheckVerification => "Check Verification",
+            TransactionType::Eurocheque => "Eurocheque",
+            TransactionType::TravelersCheck => "Travelers Check",
+            TransactionType::LetterOfCredit => "Letter of Credit",
+            TransactionType::Giro => "Giro",
             TransactionType::CashDeposit => "Deposit",
+            TransactionType::CheckDeposit => "Check Deposit",
             TransactionType::BalanceInquiry => "Balance Inquiry",
+            TransactionType::MiniStatement => "Mini Statement",
             TransactionType::Refund => "Refund",
             TransactionType::Payment => "Payment",
             TransactionType::TransferCheckingToSavings => "Transfer",
             TransactionType::TransferSavingsToChecking => "Transfer",
-            _ => "Transaction",
+            TransactionType::Other(code) => {
+                other = format!("Transaction Type {:02}", code.get());
+                &other
+            }
         };
 
-        let from_desc = match self.from_account {
-            AccountType::Savings => " from Savings",
-            AccountType::Checking => " from Checking",
-            AccountType::Credit => " from Credit",
-            AccountType::Default => "",
-            _ => " from Account",
+        let account_desc = |account: AccountType, direction: &str| match account.canonical() {
+            AccountType::Savings => format!(" {} Savings", direction),
+            AccountType::Checking => format!(" {} Checking", direction),
+            AccountType::Credit => format!(" {} Credit", direction),
+            AccountType::Universal => format!(" {} Universal", direction),
+            AccountType::Investment => format!(" {} Investment", direction),
+            AccountType::Other(code) => format!(" {} Account Type {:02}", direction, code.get()),
+            AccountType::Default => String::new(),
         };
 
-        let to_desc = match self.to_account {
-            AccountType::Savings => " to Savings",
-            AccountType::Checking => " to Checking",
-            AccountType::Credit => " to Credit",
-            AccountType::Default => "",
-            _ => " to Account",
-        };
+        format!(
+            "{}{}{}",
+            txn_desc,
+            account_desc(self.from_account, "from"),
+            account_desc(self.to_account, "to")
+        )
+    }
 
-        format!("{}{}{}", txn_desc, from_desc, to_desc)
+    /// Get the code followed by its description, e.g. `"000000 (Purchase)"`
+    ///
+    /// Equivalent to formatting with `{:#}`.
+    pub fn describe(&self) -> String {
+        format!("{:#}", self)
+    }
+
+    /// Get the reporting category of the transaction type
+    pub fn category(&self) -> TransactionCategory {
+        self.transaction_type.category()
     }
 
     /// Check if this is a balance inquiry
     pub fn is_inquiry(&self) -> bool {
         matches!(
-            self.transaction_type,
+            self.transaction_type.canonical(),
             TransactionType::BalanceInquiry | TransactionType::MiniStatement
         )
     }
@@ -196,7 +346,7 @@ impl ProcessingCode {
     /// Check if this is a cash transaction
     pub fn is_cash(&self) -> bool {
         matches!(
-            self.transaction_type,
+            self.transaction_type.canonical(),
             TransactionType::CashWithdrawal | TransactionType::CashDeposit
         )
     }
@@ -204,7 +354,7 @@ impl ProcessingCode {
     /// Check if this is a transfer
     pub fn is_transfer(&self) -> bool {
         matches!(
-            self.transaction_type,
+            self.transaction_type.canonical(),
             TransactionType::TransferCheckingToSavings | TransactionType::TransferSavingsToChecking
         )
     }
@@ -213,20 +363,22 @@ impl ProcessingCode {
 impl std::str::FromStr for ProcessingCode {
     type Err = ();
 
+    /// Surrounding ASCII whitespace is ignored
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let s = s.trim_matches(|c: char| c.is_ascii_whitespace());
         if s.len() != 6 {
             return Err(());
         }
 
+        if !s.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(());
+        }
+
         let tt = s[0..2].parse::<u8>().map_err(|_| ())?;
         let from = s[2..4].parse::<u8>().map_err(|_| ())?;
         let to = s[4..6].parse::<u8>().map_err(|_| ())?;
 
-        Ok(Self {
-            transaction_type: TransactionType::from_code(tt).ok_or(())?,
-            from_account: AccountType::from_code(from).ok_or(())?,
-            to_account: AccountType::from_code(to).ok_or(())?,
-        })
+        Self::from_raw(tt, from, to).map_err(|_| ())
     }
 }
 
@@ -237,6 +389,12 @@ impl TransactionType {
             0 => Some(Self::Purchase),
             1 => Some(Self::CashWithdrawal),
             2 => Some(Self::DebitAdjustment),
+            3 => Some(Self::CheckGuarantee),
+            4 => Some(Self::CheckVerification),
+            5 => Some(Self::Eurocheque),
+            6 => Some(Self::TravelersCheck),
+            7 => Some(Self::LetterOfCredit),
+            8 => Some(Self::Giro),
             20 => Some(Self::Refund),
             21 => Some(Self::CashDeposit),
             22 => Some(Self::CheckDeposit),
@@ -249,11 +407,29 @@ impl TransactionType {
         }
     }
 
+    /// Create from a code, using `Other` only for codes without a variant
+    ///
+    /// `None` if the code is above 99.
+    pub fn from_raw(code: u8) -> Option<Self> {
+        Self::from_code(code).or_else(|| OtherCode::new(code).map(Self::Other))
+    }
+
+    /// The named variant for an `Other` holding a known code
+    fn canonical(&self) -> Self {
+        Self::from_code(self.to_code()).unwrap_or(*self)
+    }
+
     pub fn to_code(&self) -> u8 {
         match self {
             Self::Purchase => 0,
             Self::CashWithdrawal => 1,
             Self::DebitAdjustment => 2,
+            Self::CheckGuarantee => 3,
+            Self::CheckVerification => 4,
+            Self::Eurocheque => 5,
+            Self::TravelersCheck => 6,
+            Self::LetterOfCredit => 7,
+            Self::Giro => 8,
             Self::Refund => 20,
             Self::CashDeposit => 21,
             Self::CheckDeposit => 22,
@@ -262,7 +438,36 @@ impl TransactionType {
             Self::TransferCheckingToSavings => 40,
             Self::TransferSavingsToChecking => 41,
             Self::Payment => 50,
-            _ => 0,
+            Self::Other(code) => code.get(),
+        }
+    }
+}
+
+impl TransactionType {
+    /// Get the reporting category
+    ///
+    /// Deposits and cash-equivalent instruments (cheques, letters of credit)
+    /// count as cash; check guarantee and verification count as inquiries
+    /// since no funds move.
+    pub fn category(&self) -> TransactionCategory {
+        match self.canonical() {
+            Self::Purchase | Self::DebitAdjustment => TransactionCategory::Sale,
+            Self::CashWithdrawal
+            | Self::CashDeposit
+            | Self::CheckDeposit
+            | Self::Eurocheque
+            | Self::TravelersCheck
+            | Self::LetterOfCredit => TransactionCategory::Cash,
+            Self::Refund => TransactionCategory::Refund,
+            Self::BalanceInquiry
+            | Self::MiniStatement
+            | Self::CheckGuarantee
+            | Self::CheckVerification => TransactionCategory::Inquiry,
+            Self::TransferCheckingToSavings | Self::TransferSavingsToChecking => {
+                TransactionCategory::Transfer
+            }
+            Self::Payment | Self::Giro => TransactionCategory::Payment,
+            Self::Other(_) => TransactionCategory::Other,
         }
     }
 }
@@ -270,6 +475,19 @@ impl TransactionType {
 #[allow(missing_docs)]
 impl AccountType {
     pub fn from_code(code: u8) -> Option<Self> {
+        Some(Self::named(code).unwrap_or(Self::Default)) // Default for unrecognized codes
+    }
+
+    /// Create from a code, using `Other` only for codes without a variant
+    ///
+    /// Unlike [`from_code`](Self::from_code), unrecognized codes are kept.
+    /// `None` if the code is above 99.
+    pub fn from_raw(code: u8) -> Option<Self> {
+        Self::named(code).or_else(|| OtherCode::new(code).map(Self::Other))
+    }
+
+    /// The variant named for `code`, if any
+    fn named(code: u8) -> Option<Self> {
         match code {
             0 => Some(Self::Default),
             10 => Some(Self::Savings),
@@ -277,15 +495,58 @@ impl AccountType {
             30 => Some(Self::Credit),
             40 => Some(Self::Universal),
             50 => Some(Self::Investment),
-            _ => Some(Self::Default), // Default for unrecognized codes
+            _ => None,
         }
     }
 
+    /// The named variant for an `Other` holding a known code
+    fn canonical(&self) -> Self {
+        Self::named(self.to_code()).unwrap_or(*self)
+    }
+
     pub fn to_code(&self) -> u8 {
-        *self as u8
+        match self {
+            Self::Default => 0,
+            Self::Savings => 10,
+            Self::Checking => 20,
+            Self::Credit => 30,
+            Self::Universal => 40,
+            Self::Investment => 50,
+            Self::Other(code) => code.get(),
+        }
+    }
+}
+
+impl PartialEq for TransactionType {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_code() == other.to_code()
+    }
+}
+
+impl Eq for TransactionType {}
+
+impl Hash for TransactionType {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.to_code().hash(state);
     }
 }
 
+impl PartialEq for AccountType {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_code() == other.to_code()
+    }
+}
+
+impl Eq for AccountType {}
+
+impl Hash for AccountType {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.to_code().hash(state);
+    }
+}
+
+/// `{}` prints the bare 6-digit wire code; `{:#}` appends the description,
+/// e.g. `"011000 (Cash Withdrawal from Savings)"`.
 impl fmt::Display for ProcessingCode {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -294,7 +555,13 @@ impl fmt::Display for ProcessingCode {
             self.transaction_type.to_code(),
             self.from_account.to_code(),
             self.to_account.to_code()
-        )
+        )?;
+
+        if f.alternate() {
+            write!(f, " ({})", self.description())?;
+        }
+
+        Ok(())
     }
 }
 
@@ -302,6 +569,82 @@ impl fmt::Display for ProcessingCode {
 mod tests {
     use super::*;
 
+    fn other(code: u8) -> OtherCode {
+        OtherCode::new(code).unwrap()
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_roundtrip() {
+        use TransactionType::*;
+        for transaction_type in [
+            Purchase,
+            CashWithdrawal,
+            DebitAdjustment,
+            CheckGuarantee,
+            CheckVerification,
+            Eurocheque,
+            TravelersCheck,
+            LetterOfCredit,
+            Giro,
+            CashDeposit,
+            CheckDeposit,
+            BalanceInquiry,
+            MiniStatement,
+            TransferCheckingToSavings,
+            TransferSavingsToChecking,
+            Refund,
+            Payment,
+            Other(other(17)),
+        ] {
+            let json = serde_json::to_string(&transaction_type).unwrap();
+            assert_eq!(
+                serde_json::from_str::<TransactionType>(&json).unwrap(),
+                transaction_type
+            );
+        }
+
+        for account_type in [
+            AccountType::Default,
+            AccountType::Savings,
+            AccountType::Checking,
+            AccountType::Credit,
+            AccountType::Universal,
+            AccountType::Investment,
+            AccountType::Other(other(45)),
+        ] {
+            let json = serde_json::to_string(&account_type).unwrap();
+            assert_eq!(
+                serde_json::from_str::<AccountType>(&json).unwrap(),
+                account_type
+            );
+        }
+
+        assert_eq!(
+            serde_json::to_string(&TransactionType::BalanceInquiry).unwrap(),
+            r#""BalanceInquiry""#
+        );
+        assert_eq!(
+            serde_json::to_string(&AccountType::Checking).unwrap(),
+            r#""Checking""#
+        );
+    }
+
+    #[test]
+    fn test_category() {
+        let cases = [
+            ("000000", TransactionCategory::Sale),
+            ("011000", TransactionCategory::Cash),
+            ("312000", TransactionCategory::Inquiry),
+            ("200000", TransactionCategory::Refund),
+            ("401020", TransactionCategory::Transfer),
+            ("500000", TransactionCategory::Payment),
+        ];
+        for (code, category) in cases {
+            assert_eq!(code.parse::<ProcessingCode>().unwrap().category(), category);
+        }
+    }
+
     #[test]
     fn test_processing_codes() {
         assert_eq!(ProcessingCode::PURCHASE.to_string(), "000000");
@@ -312,6 +655,80 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_raw_roundtrip() {
+        for input in ["170000", "000000", "031020", "999999", "014599", "407030"] {
+            let code = input.parse::<ProcessingCode>().unwrap();
+            let (tt, from, to) = code.raw();
+            assert_eq!(format!("{:02}{:02}{:02}", tt, from, to), input);
+            assert_eq!(ProcessingCode::from_raw(tt, from, to), Ok(code));
+            assert_eq!(code.to_string(), input);
+        }
+
+        let code = ProcessingCode::from_raw(17, 45, 20).unwrap();
+        assert_eq!(code.transaction_type, TransactionType::Other(other(17)));
+        assert_eq!(code.from_account, AccountType::Other(other(45)));
+        assert_eq!(code.to_account, AccountType::Checking);
+        assert_eq!(code.category(), TransactionCategory::Other);
+        assert_eq!(
+            code.description(),
+            "Transaction Type 17 from Account Type 45 to Checking"
+        );
+        assert_eq!(
+            "030000".parse::<ProcessingCode>().unwrap().transaction_type,
+            TransactionType::CheckGuarantee
+        );
+        assert!("17000A".parse::<ProcessingCode>().is_err());
+        assert!("+17000".parse::<ProcessingCode>().is_err());
+    }
+
+    #[test]
+    fn test_other_normalised() {
+        // A known code wrapped in Other is the same transaction type
+        assert_eq!(TransactionType::Other(other(0)), TransactionType::Purchase);
+        assert_eq!(
+            TransactionType::Other(other(31)).category(),
+            TransactionCategory::Inquiry
+        );
+        assert_eq!(AccountType::Other(other(20)), AccountType::Checking);
+        assert!(ProcessingCode::new(
+            TransactionType::Other(other(31)),
+            AccountType::Default,
+            AccountType::Default
+        )
+        .is_inquiry());
+        assert_eq!(
+            ProcessingCode::new(
+                TransactionType::Other(other(1)),
+                AccountType::Other(other(10)),
+                AccountType::Default
+            )
+            .description(),
+            "Cash Withdrawal from Savings"
+        );
+        assert_eq!(
+            TransactionType::from_raw(0),
+            Some(TransactionType::Purchase)
+        );
+        assert!(matches!(
+            TransactionType::from_raw(17),
+            Some(TransactionType::Other(code)) if code.get() == 17
+        ));
+        assert_eq!(
+            AccountType::from_raw(45),
+            Some(AccountType::Other(other(45)))
+        );
+
+        // from_code keeps mapping unrecognized account codes to Default
+        assert_eq!(AccountType::from_code(45), Some(AccountType::Default));
+
+        // Codes above 99 cannot be represented
+        assert!(ProcessingCode::from_raw(0, 100, 0).is_err());
+        assert_eq!(TransactionType::from_raw(150), None);
+        assert_eq!(OtherCode::new(255), None);
+        assert_eq!(OtherCode::try_from(99).map(u8::from), Ok(99));
+    }
+
     #[test]
     fn test_from_string() {
         let code = "000000".parse::<ProcessingCode>().unwrap();
@@ -321,6 +738,32 @@ mod tests {
         assert_eq!(code, ProcessingCode::WITHDRAWAL_CHECKING);
     }
 
+    #[test]
+    fn test_from_string_trims_whitespace() {
+        assert_eq!(
+            " 000000 ".parse::<ProcessingCode>(),
+            Ok(ProcessingCode::PURCHASE)
+        );
+        assert_eq!(
+            "\t010000".parse::<ProcessingCode>(),
+            Ok(ProcessingCode::WITHDRAWAL_CHECKING)
+        );
+        assert!(" 00000 ".parse::<ProcessingCode>().is_err());
+    }
+
+    #[test]
+    fn test_describe() {
+        let code = ProcessingCode::WITHDRAWAL_SAVINGS;
+        assert_eq!(code.to_string(), "011000");
+        assert_eq!(format!("{}", code), "011000");
+        assert_eq!(
+            format!("{:#}", code),
+            "011000 (Cash Withdrawal from Savings)"
+        );
+        assert_eq!(code.describe(), "011000 (Cash Withdrawal from Savings)");
+        assert_eq!(ProcessingCode::PURCHASE.describe(), "000000 (Purchase)");
+    }
+
     #[test]
     fn test_descriptions() {
         assert_eq!(ProcessingCode::PURCHASE.description(), "Purchase");
@@ -334,6 +777,60 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_descriptions_cover_all_variants() {
+        let transaction_types = [
+            TransactionType::Purchase,
+            TransactionType::CashWithdrawal,
+            TransactionType::DebitAdjustment,
+            TransactionType::CheckGuarantee,
+            TransactionType::CheckVerification,
+            TransactionType::Eurocheque,
+            TransactionType::TravelersCheck,
+            TransactionType::LetterOfCredit,
+            TransactionType::Giro,
+            TransactionType::CashDeposit,
+            TransactionType::CheckDeposit,
+            TransactionType::BalanceInquiry,
+            TransactionType::MiniStatement,
+            TransactionType::TransferCheckingToSavings,
+            TransactionType::TransferSavingsToChecking,
+            TransactionType::Refund,
+            TransactionType::Payment,
+        ];
+        let account_types = [
+            AccountType::Savings,
+            AccountType::Checking,
+            AccountType::Credit,
+            AccountType::Universal,
+            AccountType::Investment,
+        ];
+
+        for transaction_type in transaction_types {
+            let code =
+                ProcessingCode::new(transaction_type, AccountType::Default, AccountType::Default);
+            assert_ne!(code.description(), "Transaction");
+
+            for account in account_types {
+                let code = ProcessingCode::new(transaction_type, account, account);
+                let description = code.description();
+                assert!(!description.ends_with("Account"), "{}", description);
+                assert!(description.contains(" from "), "{}", description);
+                assert!(description.contains(" to "), "{}", description);
+            }
+        }
+
+        assert_eq!(
+            ProcessingCode::new(
+                TransactionType::Payment,
+                AccountType::Investment,
+                AccountType::Universal
+            )
+            .description(),
+            "Payment from Investment to Universal"
+        );
+    }
+
     #[test]
     fn test_predicates() {
         assert!(ProcessingCode::BALANCE_INQUIRY_CHECKING.is_inquiry());
@@ -3,6 +3,7 @@
 //! Standard response codes used in authorization and financial responses.
 //! These indicate the outcome of a transaction request.
 
+use std::collections::HashMap;
 use std::fmt;
 
 /// ISO 8583 Response Code
@@ -15,7 +16,12 @@ impl ResponseCode {
     // Approval codes
     pub const APPROVED: Self = Self(0, 0);
     pub const APPROVED_WITH_ID: Self = Self(0, 1);
+    #[deprecated(
+        note = "02 is \"Refer to card issuer, special condition\"; use REFER_SPECIAL, or APPROVED_PARTIAL_AMOUNT for a partial approval"
+    )]
     pub const APPROVED_PARTIAL: Self = Self(0, 2);
+    pub const APPROVED_PARTIAL_AMOUNT: Self = Self(1, 0);
+    pub const APPROVED_VIP: Self = Self(1, 1);
 
     // Referral codes
     pub const REFER_TO_ISSUER: Self = Self(0, 1);
@@ -87,7 +93,9 @@ impl ResponseCode {
     pub const CRYPTOGRAPHIC_KEY_SYNC_ERROR: Self = Self(8, 1);
     pub const CVV_FAILURE: Self = Self(8, 2);
     pub const CANT_VERIFY_PIN: Self = Self(8, 3);
+    #[deprecated(note = "85 is \"No reason to decline\"; use NOT_DECLINED")]
     pub const MESSAGE_FLOW_ERROR: Self = Self(8, 5);
+    pub const NOT_DECLINED: Self = Self(8, 5);
     pub const CUTOVER_IN_PROGRESS: Self = Self(9, 0);
     pub const ISSUER_UNAVAILABLE: Self = Self(9, 1);
     pub const ROUTING_ERROR: Self = Self(9, 2);
@@ -105,6 +113,11 @@ impl ResponseCode {
     }
 
     /// Get human-readable description
+    ///
+    /// Covers the full ISO 8583:1987 table (00-99). Codes the standard
+    /// leaves to private use (76-89, 97-98) carry the meanings most card
+    /// networks agree on. Reserved codes return "Reserved for ISO use",
+    /// "Reserved for private use" or "Reserved for national use".
     pub fn description(&self) -> &'static str {
         match (self.0, self.1) {
             (0, 0) => "Approved or completed successfully",
@@ -115,30 +128,144 @@ impl ResponseCode {
             (0, 5) => "Do not honor",
             (0, 6) => "Error",
             (0, 7) => "Pick up card, special condition",
+            (0, 8) => "Honor with identification",
+            (0, 9) => "Request in progress",
+            (1, 0) => "Approved, partial",
+            (1, 1) => "Approved, VIP",
             (1, 2) => "Invalid transaction",
             (1, 3) => "Invalid amount",
             (1, 4) => "Invalid card number",
             (1, 5) => "No such issuer",
+            (1, 6) => "Approved, update track 3",
+            (1, 7) => "Customer cancellation",
+            (1, 8) => "Customer dispute",
+            (1, 9) => "Re-enter transaction",
+            (2, 0) => "Invalid response",
+            (2, 1) => "No action taken",
+            (2, 2) => "Suspected malfunction",
+            (2, 3) => "Unacceptable transaction fee",
+            (2, 4) => "File update not supported by receiver",
+            (2, 5) => "Unable to locate record on file",
+            (2, 6) => "Duplicate file update record, old record replaced",
+            (2, 7) => "File update field edit error",
+            (2, 8) => "File update file locked out",
+            (2, 9) => "File update not successful, contact acquirer",
             (3, 0) => "Format error",
+            (3, 1) => "Bank not supported by switch",
+            (3, 2) => "Completed partially",
+            (3, 3) => "Expired card, pick up",
+            (3, 4) => "Suspected fraud, pick up",
+            (3, 5) => "Card acceptor contact acquirer, pick up",
+            (3, 6) => "Restricted card, pick up",
+            (3, 7) => "Card acceptor call acquirer security, pick up",
+            (3, 8) => "Allowable PIN tries exceeded, pick up",
+            (3, 9) => "No credit account",
+            (4, 0) => "Requested function not supported",
             (4, 1) => "Lost card, pick up",
+            (4, 2) => "No universal account",
             (4, 3) => "Stolen card, pick up",
+            (4, 4) => "No investment account",
+            (4, 5..=9) | (5, 0) => "Reserved for ISO use",
             (5, 1) => "Insufficient funds",
+            (5, 2) => "No checking account",
+            (5, 3) => "No savings account",
             (5, 4) => "Expired card",
             (5, 5) => "Incorrect PIN",
+            (5, 6) => "No card record",
             (5, 7) => "Transaction not permitted to cardholder",
             (5, 8) => "Transaction not permitted to terminal",
+            (5, 9) => "Suspected fraud",
+            (6, 0) => "Card acceptor contact acquirer",
             (6, 1) => "Exceeds withdrawal amount limit",
-            (7, 5) => "PIN required",
-            (7, 7) => "PIN tries exceeded",
+            (6, 2) => "Restricted card",
+            (6, 3) => "Security violation",
+            (6, 4) => "Original amount incorrect",
+            (6, 5) => "Exceeds withdrawal frequency limit",
+            (6, 6) => "Card acceptor call acquirer security",
+            (6, 7) => "Hard capture, pick up card at ATM",
+            (6, 8) => "Response received too late",
+            (6, 9) | (7, 0..=4) => "Reserved for ISO use",
+            (7, 5) => "Allowable number of PIN tries exceeded",
+            (7, 6) => "Invalid or nonexistent to account specified",
+            (7, 7) => "Invalid or nonexistent from account specified",
+            (7, 8) => "Invalid or nonexistent account specified",
+            (7, 9) => "Already reversed",
+            (8, 0) => "Cryptographic failure",
+            (8, 1) => "PIN cryptographic error",
+            (8, 2) => "Negative CVV results",
+            (8, 3) => "Unable to verify PIN",
+            (8, 4) => "Invalid authorization life cycle",
+            (8, 5) => "No reason to decline",
+            (8, 6) => "Cannot verify PIN",
+            (8, 7) => "Purchase amount only, no cash back allowed",
+            (8, 8..=9) => "Reserved for private use",
+            (9, 0) => "Cutoff is in process",
             (9, 1) => "Issuer or switch inoperative",
+            (9, 2) => "Financial institution or intermediate network cannot be found for routing",
+            (9, 3) => "Transaction cannot be completed, violation of law",
+            (9, 4) => "Duplicate transmission",
+            (9, 5) => "Reconcile error",
             (9, 6) => "System malfunction",
+            (9, 7) => "MAC error",
+            (9, 8) => "Failed security check",
+            (9, 9) => "Reserved for national use",
             _ => "Unknown response code",
         }
     }
 
+    /// Look up a response code by its description (case-insensitive)
+    ///
+    /// Reverse of [`description`](Self::description). Reserved codes share a
+    /// description and are never returned.
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::ResponseCode;
+    ///
+    /// assert_eq!(
+    ///     ResponseCode::from_description("insufficient funds"),
+    ///     Some(ResponseCode::INSUFFICIENT_FUNDS)
+    /// );
+    /// assert_eq!(ResponseCode::from_description("Reserved for ISO use"), None);
+    /// ```
+    pub fn from_description(description: &str) -> Option<Self> {
+        if description.starts_with("Reserved") {
+            return None;
+        }
+
+        (0..10)
+            .flat_map(|first| (0..10).map(move |second| Self(first, second)))
+            .find(|code| code.description().eq_ignore_ascii_case(description))
+    }
+
+    /// Get the two ASCII digits sent in field 39
+    pub fn to_ascii(&self) -> [u8; 2] {
+        [b'0' + self.0 % 10, b'0' + self.1 % 10]
+    }
+
+    /// Get a description, preferring codes registered in `registry`
+    ///
+    /// Falls back to [`description`](Self::description) for unregistered codes.
+    pub fn describe_with(&self, registry: &ResponseCodeRegistry) -> &'static str {
+        registry
+            .description(self.to_ascii())
+            .unwrap_or_else(|| self.description())
+    }
+
+    /// Get the category, preferring codes registered in `registry`
+    pub fn category_with(&self, registry: &ResponseCodeRegistry) -> ResponseCategory {
+        registry
+            .category(self.to_ascii())
+            .unwrap_or_else(|| self.category())
+    }
+
     /// Check if the response indicates approval
+    ///
+    /// Partial (10) and VIP (11) approvals count as approved, as does 85
+    /// (no reason to decline), the answer to a successful account
+    /// verification.
     pub fn is_approved(&self) -> bool {
-        self.0 == 0 && self.1 == 0
+        matches!((self.0, self.1), (0, 0) | (1, 0) | (1, 1) | (8, 5))
     }
 
     /// Check if response indicates a decline
@@ -153,7 +280,8 @@ impl ResponseCode {
 
     /// Check if response indicates a system/network error
     pub fn is_system_error(&self) -> bool {
-        matches!(self.0, 9 | 8) || matches!((self.0, self.1), (3, 0) | (0, 6))
+        (matches!(self.0, 9 | 8) && !self.is_approved())
+            || matches!((self.0, self.1), (3, 0) | (0, 6))
     }
 
     /// Check if response indicates card should be retained
@@ -164,7 +292,7 @@ impl ResponseCode {
     /// Get response category
     pub fn category(&self) -> ResponseCategory {
         match (self.0, self.1) {
-            (0, 0..=2) => ResponseCategory::Approved,
+            (0, 0..=2) | (1, 0..=1) | (8, 5) => ResponseCategory::Approved,
             (0, 1..=4) | (0, 7) => ResponseCategory::Referral,
             (4, 1) | (4, 3) => ResponseCategory::CardRetention,
             (5, 1) | (6, 1) | (6, 5) => ResponseCategory::InsufficientFunds,
@@ -179,7 +307,9 @@ impl ResponseCode {
 impl std::str::FromStr for ResponseCode {
     type Err = ();
 
+    /// Surrounding ASCII whitespace is ignored
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let s = s.trim_matches(|c: char| c.is_ascii_whitespace());
         if s.len() != 2 {
             return Err(());
         }
@@ -191,9 +321,64 @@ impl std::str::FromStr for ResponseCode {
     }
 }
 
+/// Network-specific response codes layered over the ISO table
+///
+/// Codes are the two ASCII characters sent in field 39, so alphanumeric
+/// private codes such as `"N3"` can be registered alongside numeric ones.
+/// A registered numeric code overrides its built-in description.
+///
+/// # Example
+/// ```
+/// use iso8583_core::response_code::ResponseCodeRegistry;
+/// use iso8583_core::{ResponseCategory, ResponseCode};
+///
+/// let mut registry = ResponseCodeRegistry::new();
+/// registry.register(*b"N3", "Cash service not available", ResponseCategory::Declined);
+/// registry.register(*b"05", "Do not honor (velocity)", ResponseCategory::Declined);
+///
+/// assert_eq!(registry.description(*b"N3"), Some("Cash service not available"));
+/// assert_eq!(ResponseCode::DO_NOT_HONOR.describe_with(&registry), "Do not honor (velocity)");
+/// assert_eq!(ResponseCode::APPROVED.describe_with(&registry), "Approved or completed successfully");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ResponseCodeRegistry {
+    codes: HashMap<[u8; 2], (&'static str, ResponseCategory)>,
+}
+
+impl ResponseCodeRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a code, replacing any earlier registration for it
+    pub fn register(
+        &mut self,
+        code: [u8; 2],
+        description: &'static str,
+        category: ResponseCategory,
+    ) {
+        self.codes.insert(code, (description, category));
+    }
+
+    /// Description of a registered code
+    pub fn description(&self, code: [u8; 2]) -> Option<&'static str> {
+        self.codes.get(&code).map(|&(description, _)| description)
+    }
+
+    /// Category of a registered code
+    pub fn category(&self, code: [u8; 2]) -> Option<ResponseCategory> {
+        self.codes.get(&code).map(|&(_, category)| category)
+    }
+}
+
 /// Response code category
+///
+/// With the `serde` feature, serialized as the variant name (e.g.
+/// `"InsufficientFunds"`), not the spaced [`Display`](fmt::Display) form.
 #[allow(missing_docs)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ResponseCategory {
     Approved,
     Declined,
@@ -230,10 +415,70 @@ impl fmt::Display for ResponseCategory {
 mod tests {
     use super::*;
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_category_serde_roundtrip() {
+        for category in [
+            ResponseCategory::Approved,
+            ResponseCategory::Declined,
+            ResponseCategory::Referral,
+            ResponseCategory::CardRetention,
+            ResponseCategory::InsufficientFunds,
+            ResponseCategory::ExpiredCard,
+            ResponseCategory::PINError,
+            ResponseCategory::SystemError,
+        ] {
+            let json = serde_json::to_string(&category).unwrap();
+            assert_eq!(json, format!("{:?}", format!("{:?}", category)));
+            assert_eq!(
+                serde_json::from_str::<ResponseCategory>(&json).unwrap(),
+                category
+            );
+        }
+        assert_eq!(
+            serde_json::to_string(&ResponseCategory::InsufficientFunds).unwrap(),
+            r#""InsufficientFunds""#
+        );
+    }
+
+    #[test]
+    fn test_registry() {
+        let mut registry = ResponseCodeRegistry::new();
+        registry.register(
+            *b"P2",
+            "Invalid biller information",
+            ResponseCategory::Declined,
+        );
+        registry.register(*b"51", "Over credit limit", ResponseCategory::Declined);
+
+        assert_eq!(registry.category(*b"P2"), Some(ResponseCategory::Declined));
+        assert_eq!(registry.description(*b"N3"), None);
+
+        let nsf = ResponseCode::INSUFFICIENT_FUNDS;
+        assert_eq!(nsf.to_ascii(), *b"51");
+        assert_eq!(nsf.describe_with(&registry), "Over credit limit");
+        assert_eq!(nsf.category_with(&registry), ResponseCategory::Declined);
+        assert_eq!(nsf.category(), ResponseCategory::InsufficientFunds);
+        assert_eq!(
+            ResponseCode::EXPIRED_CARD.describe_with(&registry),
+            ResponseCode::EXPIRED_CARD.description()
+        );
+    }
+
     #[test]
     fn test_response_codes() {
         assert!(ResponseCode::APPROVED.is_approved());
         assert!(!ResponseCode::DO_NOT_HONOR.is_approved());
+        for approved in [
+            ResponseCode::APPROVED_PARTIAL_AMOUNT,
+            ResponseCode::APPROVED_VIP,
+            ResponseCode::NOT_DECLINED,
+        ] {
+            assert!(approved.is_approved());
+            assert!(!approved.is_declined());
+            assert!(!approved.is_system_error());
+            assert_eq!(approved.category(), ResponseCategory::Approved);
+        }
         assert!(ResponseCode::INSUFFICIENT_FUNDS.is_declined());
         assert!(ResponseCode::REFER_TO_ISSUER.is_referral());
         assert!(ResponseCode::ISSUER_UNAVAILABLE.is_system_error());
@@ -262,6 +507,70 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_from_string_trims_whitespace() {
+        assert_eq!(
+            "05 ".parse::<ResponseCode>(),
+            Ok(ResponseCode::DO_NOT_HONOR)
+        );
+        assert_eq!(" 00".parse::<ResponseCode>(), Ok(ResponseCode::APPROVED));
+        assert_eq!(
+            "\t94\n".parse::<ResponseCode>(),
+            Ok(ResponseCode::DUPLICATE_TRANSMISSION)
+        );
+        assert!(" 0 ".parse::<ResponseCode>().is_err());
+    }
+
+    #[test]
+    fn test_full_description_table() {
+        for first in 0..10 {
+            for second in 0..10 {
+                let code = ResponseCode(first, second);
+                assert_ne!(code.description(), "Unknown response code", "{}", code);
+
+                if !code.description().starts_with("Reserved") {
+                    assert_eq!(
+                        ResponseCode::from_description(code.description()),
+                        Some(code)
+                    );
+                }
+            }
+        }
+
+        assert_eq!(
+            ResponseCode(5, 8).description(),
+            "Transaction not permitted to terminal"
+        );
+        assert_eq!(ResponseCode(6, 2).description(), "Restricted card");
+        assert_eq!(
+            ResponseCode(6, 5).description(),
+            "Exceeds withdrawal frequency limit"
+        );
+        assert_eq!(
+            ResponseCode(7, 5).description(),
+            "Allowable number of PIN tries exceeded"
+        );
+        assert_eq!(
+            ResponseCode(7, 6).description(),
+            "Invalid or nonexistent to account specified"
+        );
+        assert_eq!(
+            ResponseCode(7, 7).description(),
+            "Invalid or nonexistent from account specified"
+        );
+        assert_eq!(
+            ResponseCode(7, 8).description(),
+            "Invalid or nonexistent account specified"
+        );
+        assert_eq!(
+            ResponseCode::NOT_DECLINED.description(),
+            "No reason to decline"
+        );
+        assert_eq!(ResponseCode(1, 0).description(), "Approved, partial");
+        assert_eq!(ResponseCode(10, 0).description(), "Unknown response code");
+        assert_eq!(ResponseCode::from_description("no such thing"), None);
+    }
+
     #[test]
     fn test_from_string() {
         let code = "00".parse::<ResponseCode>().unwrap();
@@ -0,0 +1,206 @@
+//! JSON-defined field formats and per-MTI requirements
+//!
+//! Lets field formats and required fields be tuned in a configuration file
+//! loaded at startup instead of in Rust code:
+//!
+//! ```json
+//! {
+//!   "fields": {
+//!     "48": { "type": "alphanumeric_special", "length": "lllvar", "max_len": 120 },
+//!     "62": { "type": "binary", "length": "fixed", "max_len": 8 }
+//!   },
+//!   "required": {
+//!     "*": [11],
+//!     "0200": [2, 3, 4, 41, 42]
+//!   }
+//! }
+//! ```
+//!
+//! Field types and length types use the snake_case names of
+//! [`DataType`] and [`LengthType`]. The `"*"` requirement list applies to
+//! every MTI.
+
+use crate::error::{ISO8583Error, Result};
+use crate::mti::MessageType;
+use crate::spec::{DataType, FieldDefinition, LengthType, RuntimeSpec};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Requirement key matching every MTI
+pub const ANY_MTI: &str = "*";
+
+/// Format of one field in a [`SpecSchema`]
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SchemaField {
+    #[serde(rename = "type")]
+    pub data_type: DataType,
+    pub length: LengthType,
+    pub max_len: u16,
+}
+
+impl From<SchemaField> for FieldDefinition {
+    fn from(field: SchemaField) -> Self {
+        FieldDefinition::new(field.data_type, field.length, field.max_len)
+    }
+}
+
+/// Field formats and per-MTI required fields loaded from JSON
+///
+/// # Example
+/// ```
+/// use iso8583_core::schema::SpecSchema;
+/// use iso8583_core::*;
+///
+/// let schema = SpecSchema::from_json(r#"{ "required": { "0800": [11, 70] } }"#)?;
+/// assert_eq!(schema.required_fields(&MessageType::NETWORK_MANAGEMENT_REQUEST), vec![11, 70]);
+/// # Ok::<(), ISO8583Error>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SpecSchema {
+    /// Field format overrides, by field number
+    #[serde(default)]
+    pub fields: BTreeMap<u8, SchemaField>,
+    /// Required field numbers, by 4-digit MTI or [`ANY_MTI`]
+    #[serde(default)]
+    pub required: BTreeMap<String, Vec<u8>>,
+}
+
+impl SpecSchema {
+    /// Parse and check a schema from JSON
+    pub fn from_json(json: &str) -> Result<Self> {
+        let schema: Self = serde_json::from_str(json)
+            .map_err(|e| ISO8583Error::ParseError(format!("Invalid spec schema: {}", e)))?;
+        schema.check()?;
+        Ok(schema)
+    }
+
+    /// Check field numbers, lengths and MTI keys
+    pub fn check(&self) -> Result<()> {
+        for (&number, field) in &self.fields {
+            check_data_field(number)?;
+
+            let limit = match field.length {
+                LengthType::Fixed => u16::MAX,
+                LengthType::Llvar => 99,
+                LengthType::Lllvar => 999,
+            };
+            if field.max_len == 0 || field.max_len > limit {
+                return Err(ISO8583Error::invalid_field_value(
+                    number,
+                    format!("Schema max_len {} out of range", field.max_len),
+                ));
+            }
+        }
+
+        for (mti, fields) in &self.required {
+            if mti != ANY_MTI {
+                mti.parse::<MessageType>()?;
+            }
+            for &number in fields {
+                check_data_field(number)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Schema definition of a field, if it overrides one
+    pub fn definition(&self, number: u8) -> Option<FieldDefinition> {
+        self.fields.get(&number).copied().map(FieldDefinition::from)
+    }
+
+    /// The 1987 table with this schema's field formats applied
+    pub fn runtime_spec(&self) -> RuntimeSpec {
+        let mut spec = RuntimeSpec::new();
+        for (&number, &field) in &self.fields {
+            spec.override_field(number, field.into());
+        }
+        spec
+    }
+
+    /// Required field numbers for a message type, sorted and deduplicated
+    pub fn required_fields(&self, mti: &MessageType) -> Vec<u8> {
+        let mut fields: Vec<u8> = [ANY_MTI, mti.to_string().as_str()]
+            .iter()
+            .filter_map(|key| self.required.get(*key))
+            .flatten()
+            .copied()
+            .collect();
+        fields.sort_unstable();
+        fields.dedup();
+        fields
+    }
+}
+
+/// Reject bitmap positions and numbers outside 2-128
+fn check_data_field(number: u8) -> Result<()> {
+    if !(2..=128).contains(&number) || number == 65 {
+        return Err(ISO8583Error::InvalidFieldNumber(number));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &str = r#"{
+        "fields": {
+            "48": { "type": "alphanumeric_special", "length": "lllvar", "max_len": 120 },
+            "62": { "type": "binary", "length": "fixed", "max_len": 8 }
+        },
+        "required": {
+            "*": [11],
+            "0200": [2, 3, 4, 11]
+        }
+    }"#;
+
+    #[test]
+    fn test_from_json() {
+        let schema = SpecSchema::from_json(SCHEMA).unwrap();
+
+        assert_eq!(
+            schema.definition(62),
+            Some(FieldDefinition::fixed(DataType::Binary, 8))
+        );
+        assert_eq!(schema.definition(2), None);
+        assert_eq!(
+            schema.runtime_spec().get_field(48),
+            Some(&FieldDefinition::lllvar(DataType::AlphanumericSpecial, 120))
+        );
+        assert_eq!(
+            schema.required_fields(&MessageType::FINANCIAL_REQUEST),
+            vec![2, 3, 4, 11]
+        );
+        assert_eq!(
+            schema.required_fields(&MessageType::NETWORK_MANAGEMENT_REQUEST),
+            vec![11]
+        );
+
+        let json = serde_json::to_string(&schema).unwrap();
+        assert_eq!(SpecSchema::from_json(&json).unwrap(), schema);
+    }
+
+    #[test]
+    fn test_invalid_schemas() {
+        // Unknown data type
+        assert!(SpecSchema::from_json(
+            r#"{ "fields": { "48": { "type": "text", "length": "llvar", "max_len": 10 } } }"#
+        )
+        .is_err());
+        // LLVAR longer than 99
+        assert!(SpecSchema::from_json(
+            r#"{ "fields": { "48": { "type": "binary", "length": "llvar", "max_len": 100 } } }"#
+        )
+        .is_err());
+        // Bitmap position
+        assert!(SpecSchema::from_json(r#"{ "required": { "0200": [65] } }"#).is_err());
+        // Bad MTI key
+        assert!(SpecSchema::from_json(r#"{ "required": { "02X0": [2] } }"#).is_err());
+        // Unknown top-level key
+        assert!(SpecSchema::from_json(r#"{ "mandatory": {} }"#).is_err());
+    }
+}
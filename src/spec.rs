@@ -8,6 +8,8 @@
 /// Data type for field values
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum DataType {
     /// Numeric digits only (0-9)
     Numeric = 0,
@@ -23,11 +25,15 @@ pub enum DataType {
     Track2 = 5,
     /// Track 3 magnetic stripe format
     Track3 = 6,
+    /// Signed amount (x+n): 'C' or 'D' followed by digits
+    SignedAmount = 7,
 }
 
 /// Length encoding type for field
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum LengthType {
     /// Fixed length (no length indicator)
     Fixed = 0,
@@ -175,17 +181,17 @@ pub const ISO8583_1987_TABLE: [Option<FieldDefinition>; 129] = iso_table! {
     // Field 27: Authorization Identification Response Length (numeric, fixed 1)
     27 => FieldDefinition::fixed(DataType::Numeric, 1),
 
-    // Field 28: Transaction Fee Amount (numeric, fixed 9)
-    28 => FieldDefinition::fixed(DataType::Numeric, 9),
+    // Field 28: Transaction Fee Amount (x+n: C/D sign + 8 digits, fixed 9)
+    28 => FieldDefinition::fixed(DataType::SignedAmount, 9),
 
-    // Field 29: Settlement Fee Amount (numeric, fixed 9)
-    29 => FieldDefinition::fixed(DataType::Numeric, 9),
+    // Field 29: Settlement Fee Amount (x+n: C/D sign + 8 digits, fixed 9)
+    29 => FieldDefinition::fixed(DataType::SignedAmount, 9),
 
-    // Field 30: Transaction Processing Fee Amount (numeric, fixed 9)
-    30 => FieldDefinition::fixed(DataType::Numeric, 9),
+    // Field 30: Transaction Processing Fee Amount (x+n: C/D sign + 8 digits, fixed 9)
+    30 => FieldDefinition::fixed(DataType::SignedAmount, 9),
 
-    // Field 31: Settlement Processing Fee Amount (numeric, fixed 9)
-    31 => FieldDefinition::fixed(DataType::Numeric, 9),
+    // Field 31: Settlement Processing Fee Amount (x+n: C/D sign + 8 digits, fixed 9)
+    31 => FieldDefinition::fixed(DataType::SignedAmount, 9),
 
     // Field 32: Acquiring Institution ID Code (LLVAR, max 11)
     32 => FieldDefinition::llvar(DataType::Numeric, 11),
@@ -341,11 +347,33 @@ pub const ISO8583_1987_TABLE: [Option<FieldDefinition>; 129] = iso_table! {
     128 => FieldDefinition::fixed(DataType::Binary, 8),
 };
 
+/// Count the defined entries of a field table at compile time
+pub const fn count_defined(table: &[Option<FieldDefinition>]) -> usize {
+    let mut count = 0;
+    let mut i = 0;
+    while i < table.len() {
+        if table[i].is_some() {
+            count += 1;
+        }
+        i += 1;
+    }
+    count
+}
+
 /// Trait for ISO 8583 specification versions
 pub trait IsoSpec {
     /// Static field definition table
     const TABLE: &'static [Option<FieldDefinition>];
 
+    /// Number of fields the table defines, computed at compile time
+    ///
+    /// ```
+    /// use iso8583_core::spec::{Iso1987, IsoSpec};
+    ///
+    /// const _: () = assert!(Iso1987::DEFINED_COUNT == 128);
+    /// ```
+    const DEFINED_COUNT: usize = count_defined(Self::TABLE);
+
     /// Get field definition by number (O(1) lookup)
     #[inline]
     fn get_field(number: u8) -> Option<&'static FieldDefinition> {
@@ -355,6 +383,40 @@ pub trait IsoSpec {
             None
         }
     }
+
+    /// Iterate over every defined field as `(number, definition)`, in order
+    #[inline]
+    fn defined_fields() -> DefinedFields<'static> {
+        DefinedFields {
+            table: Self::TABLE,
+            next: 0,
+        }
+    }
+}
+
+/// Iterator over the defined fields of a spec table
+///
+/// Created by [`IsoSpec::defined_fields`] and [`RuntimeSpec::defined_fields`].
+#[derive(Debug, Clone)]
+pub struct DefinedFields<'a> {
+    table: &'a [Option<FieldDefinition>],
+    next: usize,
+}
+
+impl<'a> Iterator for DefinedFields<'a> {
+    type Item = (u8, &'a FieldDefinition);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let table = self.table;
+        while self.next < table.len() {
+            let number = self.next;
+            self.next += 1;
+            if let Some(def) = table[number].as_ref() {
+                return Some((number as u8, def));
+            }
+        }
+        None
+    }
 }
 
 /// ISO 8583:1987 Specification
@@ -364,10 +426,226 @@ impl IsoSpec for Iso1987 {
     const TABLE: &'static [Option<FieldDefinition>] = &ISO8583_1987_TABLE;
 }
 
+/// Field table that can be changed at runtime
+///
+/// Starts as a copy of the 1987 table; individual fields can then be
+/// overridden or removed to describe a network's private usage (e.g. a
+/// private field sent as fixed binary instead of LLLVAR). Fields can also
+/// be marked structured, meaning their value is a sequence of subfields
+/// (see [`subfield`](crate::subfield)), or given their own wire encoding
+/// (e.g. one BCD field in an ASCII message). Lookup is O(1) and the table
+/// lives inline, so no allocation is needed.
+///
+/// # Example
+/// ```
+/// use iso8583_core::spec::{DataType, FieldDefinition, LengthType, RuntimeSpec};
+///
+/// let mut spec = RuntimeSpec::new();
+/// spec.override_field(62, FieldDefinition::fixed(DataType::Binary, 8));
+///
+/// assert_eq!(spec.get_field(62).unwrap().length_type, LengthType::Fixed);
+/// assert_eq!(spec.get_field(2).unwrap().max_len, 19);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuntimeSpec {
+    table: [Option<FieldDefinition>; 129],
+    structured: [bool; 129],
+    #[cfg(feature = "std")]
+    encodings: [Option<crate::encoding::Encoding>; 129],
+    #[cfg(feature = "std")]
+    binary_encodings: [crate::encoding::BinaryEncoding; 129],
+}
+
+impl RuntimeSpec {
+    /// Create a spec initialised from the 1987 table
+    pub const fn new() -> Self {
+        Self {
+            table: ISO8583_1987_TABLE,
+            structured: [false; 129],
+            #[cfg(feature = "std")]
+            encodings: [None; 129],
+            #[cfg(feature = "std")]
+            binary_encodings: [crate::encoding::BinaryEncoding::Raw; 129],
+        }
+    }
+
+    /// Create a spec with no fields defined
+    pub const fn empty() -> Self {
+        Self {
+            table: [None; 129],
+            structured: [false; 129],
+            #[cfg(feature = "std")]
+            encodings: [None; 129],
+            #[cfg(feature = "std")]
+            binary_encodings: [crate::encoding::BinaryEncoding::Raw; 129],
+        }
+    }
+
+    /// Get field definition by number
+    #[inline]
+    pub fn get_field(&self, number: u8) -> Option<&FieldDefinition> {
+        self.table.get(number as usize).and_then(Option::as_ref)
+    }
+
+    /// Replace the definition of a field
+    ///
+    /// Numbers above 128 are ignored.
+    pub fn override_field(&mut self, number: u8, def: FieldDefinition) {
+        if let Some(slot) = self.table.get_mut(number as usize) {
+            *slot = Some(def);
+        }
+    }
+
+    /// Remove a field definition
+    pub fn remove_field(&mut self, number: u8) {
+        if let Some(slot) = self.table.get_mut(number as usize) {
+            *slot = None;
+        }
+    }
+
+    /// Mark a field as structured (subfields) or plain
+    ///
+    /// Numbers above 128 are ignored.
+    pub fn set_structured(&mut self, number: u8, structured: bool) {
+        if let Some(slot) = self.structured.get_mut(number as usize) {
+            *slot = structured;
+        }
+    }
+
+    /// Check if a field carries subfields
+    pub fn is_structured(&self, number: u8) -> bool {
+        self.structured
+            .get(number as usize)
+            .copied()
+            .unwrap_or(false)
+    }
+
+    /// Override the wire encoding of one field, or `None` for the message default
+    ///
+    /// BCD packs two digits per byte and applies to numeric fields; variable
+    /// length fields keep their ASCII length prefix, which counts digits.
+    /// Numbers above 128 are ignored.
+    #[cfg(feature = "std")]
+    pub fn set_encoding(&mut self, number: u8, encoding: Option<crate::encoding::Encoding>) {
+        if let Some(slot) = self.encodings.get_mut(number as usize) {
+            *slot = encoding;
+        }
+    }
+
+    /// Wire encoding override of a field, if any
+    #[cfg(feature = "std")]
+    pub fn encoding(&self, number: u8) -> Option<crate::encoding::Encoding> {
+        self.encodings.get(number as usize).copied().flatten()
+    }
+
+    /// Set how a fixed-length binary field travels on the wire
+    ///
+    /// With [`BinaryEncoding::Hex`](crate::encoding::BinaryEncoding::Hex),
+    /// an 8-byte field such as 52, 53, 64, 96 or 128 is read and written as
+    /// 16 hex characters; the parsed value is still the 8 raw bytes.
+    /// Variable-length and non-binary fields are unaffected. Numbers above
+    /// 128 are ignored.
+    #[cfg(feature = "std")]
+    pub fn set_binary_encoding(&mut self, number: u8, encoding: crate::encoding::BinaryEncoding) {
+        if let Some(slot) = self.binary_encodings.get_mut(number as usize) {
+            *slot = encoding;
+        }
+    }
+
+    /// Wire representation of a fixed-length binary field
+    #[cfg(feature = "std")]
+    pub fn binary_encoding(&self, number: u8) -> crate::encoding::BinaryEncoding {
+        self.binary_encodings
+            .get(number as usize)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Iterate over every defined field as `(number, definition)`, in order
+    pub fn defined_fields(&self) -> DefinedFields<'_> {
+        DefinedFields {
+            table: &self.table,
+            next: 0,
+        }
+    }
+}
+
+impl Default for RuntimeSpec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A specification a raw message can be parsed against
+///
+/// Implemented for [`RuntimeSpec`] and for
+/// [`ParseOptions`](crate::message::ParseOptions), so a candidate for
+/// [`detect`] can be a field table or a complete wire format.
+#[cfg(feature = "std")]
+pub trait SpecLookup {
+    /// Parse options that read a message under this spec
+    fn parse_options(&self) -> crate::message::ParseOptions;
+}
+
+#[cfg(feature = "std")]
+impl SpecLookup for RuntimeSpec {
+    fn parse_options(&self) -> crate::message::ParseOptions {
+        crate::message::ParseOptions::new().runtime_spec(*self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl SpecLookup for crate::message::ParseOptions {
+    fn parse_options(&self) -> crate::message::ParseOptions {
+        *self
+    }
+}
+
+/// Find the first candidate spec that parses `bytes` exactly
+///
+/// A candidate matches when the message parses and the last field ends at
+/// the end of the buffer. Useful when the MTI version digit cannot be
+/// trusted. Each attempt only reads `bytes`; a candidate that fails to
+/// parse is skipped.
+///
+/// # Example
+/// ```
+/// use iso8583_core::spec::{detect, DataType, FieldDefinition, RuntimeSpec, SpecLookup};
+///
+/// // Field 62 sent as 4 fixed bytes instead of LLLVAR
+/// let mut raw = b"0200".to_vec();
+/// raw.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 0x04]);
+/// raw.extend_from_slice(b"0042");
+///
+/// let standard = RuntimeSpec::new();
+/// let mut private = RuntimeSpec::new();
+/// private.override_field(62, FieldDefinition::fixed(DataType::Numeric, 4));
+///
+/// let found = detect(&raw, &[&standard, &private]).unwrap();
+/// assert_eq!(found.parse_options().spec, Some(private));
+/// ```
+#[cfg(feature = "std")]
+pub fn detect<'a>(bytes: &[u8], candidates: &[&'a dyn SpecLookup]) -> Option<&'a dyn SpecLookup> {
+    candidates.iter().copied().find(|candidate| {
+        crate::message::MessageView::parse_counted(bytes, &candidate.parse_options())
+            .is_ok_and(|(_, consumed)| consumed == bytes.len())
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_defined_count() {
+        assert_eq!(Iso1987::DEFINED_COUNT, Iso1987::defined_fields().count());
+        assert_eq!(count_defined(&RuntimeSpec::empty().table), 0);
+        assert_eq!(
+            count_defined(&[None, Some(FieldDefinition::fixed(DataType::Binary, 8))]),
+            1
+        );
+    }
+
     #[test]
     fn test_field_lookup() {
         // Field 2 - PAN
@@ -383,6 +661,59 @@ mod tests {
         assert_eq!(field4.max_len, 12);
     }
 
+    #[test]
+    fn test_fee_amounts_are_signed() {
+        for field in 28..=31 {
+            let def = Iso1987::get_field(field).unwrap();
+            assert_eq!(def.data_type, DataType::SignedAmount);
+            assert_eq!(def.max_len, 9);
+        }
+    }
+
+    #[test]
+    fn test_defined_fields() {
+        let defined: usize = Iso1987::TABLE.iter().filter(|def| def.is_some()).count();
+        assert_eq!(Iso1987::defined_fields().count(), defined);
+
+        let mut previous = 0;
+        for (number, def) in Iso1987::defined_fields() {
+            assert!(number > previous);
+            assert_eq!(Iso1987::get_field(number), Some(def));
+            previous = number;
+        }
+
+        let (first, _) = Iso1987::defined_fields().next().unwrap();
+        assert_eq!(first, 1);
+        assert!(Iso1987::defined_fields().all(|(number, _)| number != 0));
+    }
+
+    #[test]
+    fn test_runtime_spec() {
+        let mut spec = RuntimeSpec::new();
+        assert!(spec.defined_fields().eq(Iso1987::defined_fields()));
+
+        spec.override_field(62, FieldDefinition::fixed(DataType::Binary, 8));
+        spec.remove_field(127);
+        spec.override_field(200, FieldDefinition::fixed(DataType::Binary, 8));
+
+        assert_eq!(
+            spec.get_field(62),
+            Some(&FieldDefinition::fixed(DataType::Binary, 8))
+        );
+        assert_eq!(spec.get_field(127), None);
+        assert_eq!(spec.get_field(200), None);
+        assert_eq!(
+            spec.defined_fields().count(),
+            Iso1987::defined_fields().count() - 1
+        );
+        assert_eq!(RuntimeSpec::empty().defined_fields().count(), 0);
+
+        assert!(!spec.is_structured(48));
+        spec.set_structured(48, true);
+        assert!(spec.is_structured(48));
+        assert!(!spec.is_structured(200));
+    }
+
     #[test]
     fn test_invalid_field() {
         assert!(Iso1987::get_field(0).is_none());
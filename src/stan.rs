@@ -0,0 +1,279 @@
+//! System Trace Audit Number (field 11) duplicate detection
+//!
+//! Switches reject a request whose STAN was already seen on the same
+//! channel within a short window, answering with response code 94
+//! (duplicate transmission). STANs wrap from 999999 back to 000000, so a
+//! value may legitimately reappear once the window has passed.
+
+use crate::response_code::ResponseCode;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Duration, Instant};
+
+/// Highest STAN that fits in field 11 (6 digits)
+pub const MAX_STAN: u32 = 999_999;
+
+/// Source of fresh STANs for outgoing messages
+///
+/// Implementations return values in `0..=MAX_STAN` and are shared by
+/// reference, so they use interior mutability.
+pub trait StanProvider {
+    /// Next STAN to send
+    fn next_stan(&self) -> u32;
+}
+
+/// Thread-safe counter wrapping from 999999 back to 000000
+///
+/// # Example
+/// ```
+/// use iso8583_core::stan::{SequentialStan, StanProvider, MAX_STAN};
+///
+/// let stans = SequentialStan::new(MAX_STAN);
+/// assert_eq!(stans.next_stan(), MAX_STAN);
+/// assert_eq!(stans.next_stan(), 0);
+/// ```
+#[derive(Debug, Default)]
+pub struct SequentialStan {
+    next: AtomicU32,
+}
+
+impl SequentialStan {
+    /// Create a counter whose first STAN is `start` (taken modulo 1000000)
+    pub fn new(start: u32) -> Self {
+        Self {
+            next: AtomicU32::new(start % (MAX_STAN + 1)),
+        }
+    }
+}
+
+impl StanProvider for SequentialStan {
+    fn next_stan(&self) -> u32 {
+        // The closure always returns Some, so the update cannot fail
+        self.next
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |stan| {
+                Some(if stan >= MAX_STAN { 0 } else { stan + 1 })
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Result of checking a STAN against a [`StanTracker`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StanStatus {
+    /// Not seen within the window; now recorded
+    New,
+    /// Already seen within the window
+    Duplicate,
+    /// Not a valid 6-digit STAN
+    Invalid,
+}
+
+impl StanStatus {
+    /// Response code to send back, if the request should be rejected
+    ///
+    /// `Duplicate` maps to 94 (duplicate transmission) and `Invalid` to 30
+    /// (format error).
+    pub fn response_code(&self) -> Option<ResponseCode> {
+        match self {
+            StanStatus::New => None,
+            StanStatus::Duplicate => Some(ResponseCode::DUPLICATE_TRANSMISSION),
+            StanStatus::Invalid => Some(ResponseCode::FORMAT_ERROR),
+        }
+    }
+}
+
+/// Tracks STANs seen on one channel or session within a sliding window
+///
+/// # Example
+/// ```
+/// use iso8583_core::stan::{StanStatus, StanTracker};
+/// use std::time::Duration;
+///
+/// let mut tracker = StanTracker::new();
+/// let window = Duration::from_secs(300);
+///
+/// assert_eq!(tracker.check(1, window), StanStatus::New);
+/// assert_eq!(tracker.check(1, window), StanStatus::Duplicate);
+/// assert_eq!(tracker.check(2, window), StanStatus::New);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StanTracker {
+    /// First-seen time of each STAN still inside the window
+    seen: HashMap<u32, Instant>,
+    /// STANs in the order they were recorded, for expiry
+    order: VecDeque<(Instant, u32)>,
+}
+
+impl StanTracker {
+    /// Create an empty tracker
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check a STAN at the current time and record it if new
+    pub fn check(&mut self, stan: u32, window: Duration) -> StanStatus {
+        self.check_at(stan, window, Instant::now())
+    }
+
+    /// Check a STAN at an explicit time and record it if new
+    ///
+    /// A STAN first seen at `t` is a duplicate for any check before
+    /// `t + window` and new again from `t + window` onwards. Duplicates do
+    /// not extend the window. `now` must not go backwards between calls.
+    pub fn check_at(&mut self, stan: u32, window: Duration, now: Instant) -> StanStatus {
+        if stan > MAX_STAN {
+            return StanStatus::Invalid;
+        }
+
+        self.expire(window, now);
+
+        if self.seen.contains_key(&stan) {
+            return StanStatus::Duplicate;
+        }
+
+        self.seen.insert(stan, now);
+        self.order.push_back((now, stan));
+        StanStatus::New
+    }
+
+    /// Number of STANs currently tracked
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    /// Check if no STANs are tracked
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+
+    /// Forget every tracked STAN (e.g. on session restart)
+    pub fn clear(&mut self) {
+        self.seen.clear();
+        self.order.clear();
+    }
+
+    /// Drop STANs recorded at or before `now - window`
+    fn expire(&mut self, window: Duration, now: Instant) {
+        while let Some(&(at, stan)) = self.order.front() {
+            if now.saturating_duration_since(at) < window {
+                break;
+            }
+            self.order.pop_front();
+            self.seen.remove(&stan);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sequential_stan() {
+        let stans = SequentialStan::new(999_998);
+        assert_eq!(stans.next_stan(), 999_998);
+        assert_eq!(stans.next_stan(), MAX_STAN);
+        assert_eq!(stans.next_stan(), 0);
+        assert_eq!(stans.next_stan(), 1);
+
+        assert_eq!(SequentialStan::new(1_000_005).next_stan(), 5);
+        assert_eq!(SequentialStan::default().next_stan(), 0);
+    }
+
+    #[test]
+    fn test_duplicate_within_window() {
+        let mut tracker = StanTracker::new();
+        let window = Duration::from_secs(60);
+        let t0 = Instant::now();
+
+        assert_eq!(tracker.check_at(42, window, t0), StanStatus::New);
+        assert_eq!(
+            tracker.check_at(42, window, t0 + Duration::from_secs(30)),
+            StanStatus::Duplicate
+        );
+        assert_eq!(
+            StanStatus::Duplicate.response_code(),
+            Some(ResponseCode::DUPLICATE_TRANSMISSION)
+        );
+        assert_eq!(StanStatus::New.response_code(), None);
+    }
+
+    #[test]
+    fn test_window_boundary() {
+        let mut tracker = StanTracker::new();
+        let window = Duration::from_secs(60);
+        let t0 = Instant::now();
+
+        assert_eq!(tracker.check_at(7, window, t0), StanStatus::New);
+        // Just inside the window
+        assert_eq!(
+            tracker.check_at(7, window, t0 + window - Duration::from_nanos(1)),
+            StanStatus::Duplicate
+        );
+        // Exactly at the boundary the entry has expired
+        assert_eq!(tracker.check_at(7, window, t0 + window), StanStatus::New);
+        assert_eq!(tracker.len(), 1);
+    }
+
+    #[test]
+    fn test_duplicate_does_not_extend_window() {
+        let mut tracker = StanTracker::new();
+        let window = Duration::from_secs(60);
+        let t0 = Instant::now();
+
+        assert_eq!(tracker.check_at(5, window, t0), StanStatus::New);
+        assert_eq!(
+            tracker.check_at(5, window, t0 + Duration::from_secs(59)),
+            StanStatus::Duplicate
+        );
+        assert_eq!(
+            tracker.check_at(5, window, t0 + Duration::from_secs(61)),
+            StanStatus::New
+        );
+    }
+
+    #[test]
+    fn test_wraparound() {
+        let mut tracker = StanTracker::new();
+        let window = Duration::from_secs(60);
+        let t0 = Instant::now();
+
+        assert_eq!(tracker.check_at(0, window, t0), StanStatus::New);
+        assert_eq!(
+            tracker.check_at(MAX_STAN, window, t0 + Duration::from_secs(1)),
+            StanStatus::New
+        );
+        // Counter wrapped back to 0 while the old 0 is still in the window
+        assert_eq!(
+            tracker.check_at(0, window, t0 + Duration::from_secs(2)),
+            StanStatus::Duplicate
+        );
+        // After the window the wrapped value is accepted again
+        assert_eq!(
+            tracker.check_at(0, window, t0 + Duration::from_secs(60)),
+            StanStatus::New
+        );
+        assert_eq!(
+            tracker.check_at(MAX_STAN + 1, window, t0),
+            StanStatus::Invalid
+        );
+    }
+
+    #[test]
+    fn test_expiry_prunes_old_entries() {
+        let mut tracker = StanTracker::new();
+        let window = Duration::from_secs(10);
+        let t0 = Instant::now();
+
+        for stan in 0..100 {
+            tracker.check_at(stan, window, t0 + Duration::from_millis(stan as u64));
+        }
+        assert_eq!(tracker.len(), 100);
+
+        tracker.check_at(500, window, t0 + Duration::from_secs(20));
+        assert_eq!(tracker.len(), 1);
+
+        tracker.clear();
+        assert!(tracker.is_empty());
+    }
+}
@@ -0,0 +1,115 @@
+//! Subfield codec for structured private fields (e.g. field 48)
+//!
+//! Some networks carry a sequence of subfields inside one field instead of
+//! free text. Each subfield is encoded as a 2-digit tag, a 2-digit length
+//! and the value:
+//!
+//! ```text
+//! 10 05 HELLO 42 03 ABC   ->   "1005HELLO4203ABC"
+//! ```
+//!
+//! Whether a field is structured is decided by the
+//! [`RuntimeSpec`](crate::spec::RuntimeSpec), not by the data itself, so
+//! free text that happens to start with digits is never mis-parsed.
+
+use crate::error::{ISO8583Error, Result};
+
+/// Longest value a subfield can carry (2-digit length)
+pub const MAX_SUBFIELD_LEN: usize = 99;
+
+/// Split structured field data into `(tag, value)` pairs, in order
+///
+/// `field` is only used for error reporting.
+///
+/// # Example
+/// ```
+/// use iso8583_core::subfield;
+///
+/// let subfields = subfield::parse("1005HELLO4203ABC", 48)?;
+/// assert_eq!(subfields, vec![(10, "HELLO"), (42, "ABC")]);
+/// # Ok::<(), iso8583_core::ISO8583Error>(())
+/// ```
+pub fn parse(data: &str, field: u8) -> Result<Vec<(u8, &str)>> {
+    let mut subfields = Vec::new();
+    let mut rest = data;
+
+    while !rest.is_empty() {
+        let header = rest
+            .get(..4)
+            .filter(|h| h.bytes().all(|b| b.is_ascii_digit()))
+            .ok_or_else(|| {
+                ISO8583Error::invalid_field_value(
+                    field,
+                    format!(
+                        "Invalid subfield header at offset {}",
+                        data.len() - rest.len()
+                    ),
+                )
+            })?;
+        let tag: u8 = header[..2].parse().unwrap_or_default();
+        let len: usize = header[2..].parse().unwrap_or_default();
+
+        let value = rest.get(4..4 + len).ok_or_else(|| {
+            ISO8583Error::invalid_field_value(
+                field,
+                format!("Subfield {:02} length {} exceeds remaining data", tag, len),
+            )
+        })?;
+        subfields.push((tag, value));
+        rest = &rest[4 + len..];
+    }
+
+    Ok(subfields)
+}
+
+/// Find the first subfield with `tag`
+pub fn find(data: &str, field: u8, tag: u8) -> Result<Option<&str>> {
+    Ok(parse(data, field)?
+        .into_iter()
+        .find(|&(t, _)| t == tag)
+        .map(|(_, value)| value))
+}
+
+/// Encode `(tag, value)` pairs as structured field data
+///
+/// Tags above 99 and values longer than [`MAX_SUBFIELD_LEN`] are rejected.
+pub fn encode(subfields: &[(u8, &str)], field: u8) -> Result<String> {
+    let mut out = String::new();
+
+    for &(tag, value) in subfields {
+        if tag > 99 || value.len() > MAX_SUBFIELD_LEN {
+            return Err(ISO8583Error::invalid_field_value(
+                field,
+                format!("Subfield {} cannot be encoded", tag),
+            ));
+        }
+        out.push_str(&format!("{:02}{:02}{}", tag, value.len(), value));
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let subfields = [(1, "A"), (10, ""), (99, "XYZ 123")];
+        let data = encode(&subfields, 48).unwrap();
+        assert_eq!(data, "0101A10009907XYZ 123");
+        assert_eq!(parse(&data, 48).unwrap(), subfields);
+        assert_eq!(find(&data, 48, 99).unwrap(), Some("XYZ 123"));
+        assert_eq!(find(&data, 48, 2).unwrap(), None);
+        assert!(parse("", 48).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_malformed() {
+        assert!(parse("10", 48).is_err()); // Truncated header
+        assert!(parse("1005HELL", 48).is_err()); // Truncated value
+        assert!(parse("FREE TEXT", 48).is_err());
+        assert!(encode(&[(100, "A")], 48).is_err());
+        assert!(encode(&[(1, &"A".repeat(100))], 48).is_err());
+    }
+}
@@ -0,0 +1,180 @@
+//! Canonical example messages for integration testing
+//!
+//! Each [`TestVector`] holds the wire bytes (ASCII MTI, binary bitmap,
+//! ASCII fields), the same bytes as hex, and the expected parse result.
+//! Dependent crates can feed these through their own parsing and
+//! framing code without hand-rolling messages.
+//!
+//! ```
+//! use iso8583_core::test_vectors;
+//! use iso8583_core::*;
+//!
+//! for vector in test_vectors::ALL {
+//!     let parsed = ISO8583Message::from_bytes(vector.bytes);
+//!     assert_eq!(parsed.is_ok(), vector.valid, "{}", vector.name);
+//! }
+//! ```
+
+/// A known-good or known-bad message and its expected parse result
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TestVector {
+    /// Short description
+    pub name: &'static str,
+    /// Wire bytes, without a length header
+    pub bytes: &'static [u8],
+    /// `bytes` as uppercase hex
+    pub hex: &'static str,
+    /// Whether the message parses
+    pub valid: bool,
+    /// Expected MTI (empty for malformed messages)
+    pub mti: &'static str,
+    /// Expected fields as `(number, value)`, in field order
+    pub fields: &'static [(u8, &'static str)],
+}
+
+/// Minimal 0100 authorization request (primary bitmap only)
+pub const AUTHORIZATION_REQUEST: TestVector = TestVector {
+    name: "0100 authorization request",
+    bytes: b"0100\
+    \x70\x38\x00\x00\x00\x00\x00\x00\
+    164111111111111111\
+    000000\
+    000000001000\
+    000001\
+    120000\
+    0115",
+    hex: "30313030703800000000000031363431313131313131313131313131313130303030303030303030303030303130303030303030303131323030303030313135",
+    valid: true,
+    mti: "0100",
+    fields: &[
+        (2, "4111111111111111"),
+        (3, "000000"),
+        (4, "000000001000"),
+        (11, "000001"),
+        (12, "120000"),
+        (13, "0115"),
+    ],
+};
+
+/// 0200 financial request with a secondary bitmap (field 102)
+pub const FINANCIAL_REQUEST: TestVector = TestVector {
+    name: "0200 financial request with secondary bitmap",
+    bytes: b"0200\
+    \xF2\x38\x00\x00\x00\xC0\x80\x00\
+    \x00\x00\x00\x00\x04\x00\x00\x00\
+    164111111111111111\
+    000000\
+    000000007550\
+    0115120000\
+    000002\
+    120000\
+    0115\
+    TERM0001\
+    MERCHANT0000001\
+    840\
+    101234567890",
+    hex: "30323030F238000000C08000000000000400000031363431313131313131313131313131313130303030303030303030303030303735353030313135313230303030303030303032313230303030303131355445524D303030314D45524348414E5430303030303031383430313031323334353637383930",
+    valid: true,
+    mti: "0200",
+    fields: &[
+        (2, "4111111111111111"),
+        (3, "000000"),
+        (4, "000000007550"),
+        (7, "0115120000"),
+        (11, "000002"),
+        (12, "120000"),
+        (13, "0115"),
+        (41, "TERM0001"),
+        (42, "MERCHANT0000001"),
+        (49, "840"),
+        (102, "1234567890"),
+    ],
+};
+
+/// 0210 approved response to [`FINANCIAL_REQUEST`]
+pub const FINANCIAL_RESPONSE: TestVector = TestVector {
+    name: "0210 financial response",
+    bytes: b"0210\
+    \x30\x38\x00\x00\x0E\x80\x00\x00\
+    000000\
+    000000007550\
+    000002\
+    120000\
+    0115\
+    260115000002\
+    ABC123\
+    00\
+    TERM0001",
+    hex: "30323130303800000E8000003030303030303030303030303030373535303030303030323132303030303031313532363031313530303030303241424331323330305445524D30303031",
+    valid: true,
+    mti: "0210",
+    fields: &[
+        (3, "000000"),
+        (4, "000000007550"),
+        (11, "000002"),
+        (12, "120000"),
+        (13, "0115"),
+        (37, "260115000002"),
+        (38, "ABC123"),
+        (39, "00"),
+        (41, "TERM0001"),
+    ],
+};
+
+/// 0200 whose bitmap declares fields 3 and 11 but field 11 is truncated
+pub const MALFORMED_TRUNCATED: TestVector = TestVector {
+    name: "0200 truncated field 11",
+    bytes: b"0200\
+    \x20\x20\x00\x00\x00\x00\x00\x00\
+    000000\
+    123",
+    hex: "303230302020000000000000303030303030313233",
+    valid: false,
+    mti: "",
+    fields: &[],
+};
+
+/// Every vector in this module
+pub const ALL: &[TestVector] = &[
+    AUTHORIZATION_REQUEST,
+    FINANCIAL_REQUEST,
+    FINANCIAL_RESPONSE,
+    MALFORMED_TRUNCATED,
+];
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::field::{Field, FieldValue};
+    use crate::message::ISO8583Message;
+
+    #[test]
+    fn test_vectors_match_parser() {
+        for vector in ALL {
+            assert_eq!(
+                hex::encode_upper(vector.bytes),
+                vector.hex,
+                "{}",
+                vector.name
+            );
+
+            let parsed = ISO8583Message::from_bytes(vector.bytes);
+            assert_eq!(parsed.is_ok(), vector.valid, "{}", vector.name);
+            let Ok(msg) = parsed else { continue };
+
+            assert_eq!(msg.mti.to_string(), vector.mti);
+            let numbers: Vec<u8> = vector.fields.iter().map(|&(num, _)| num).collect();
+            assert_eq!(msg.get_field_numbers(), numbers, "{}", vector.name);
+            for &(num, value) in vector.fields {
+                assert_eq!(
+                    msg.get_field(Field::from_number(num).unwrap()),
+                    Some(&FieldValue::from_string(value)),
+                    "{} field {}",
+                    vector.name,
+                    num
+                );
+            }
+            assert_eq!(msg.to_bytes(), vector.bytes, "{}", vector.name);
+        }
+    }
+}
@@ -1,7 +1,7 @@
 //! Utility functions for common ISO 8583 operations
 
 use crate::error::{ISO8583Error, Result};
-use chrono::Utc;
+use chrono::{DateTime, Datelike, Days, NaiveDate, NaiveTime, Utc};
 
 /// Mask PAN for display (shows first 6 and last 4 digits)
 ///
@@ -21,6 +21,72 @@ pub fn mask_pan(pan: &str) -> String {
     format!("{}****{}", first, last)
 }
 
+/// Mask a PAN, showing `prefix_visible` leading and `suffix_visible`
+/// trailing characters
+///
+/// Every hidden character is replaced by `mask_char`, so the output keeps
+/// the PAN's length. If the visible parts would cover the whole PAN,
+/// everything is masked rather than revealing the full number.
+///
+/// # Example
+/// ```
+/// use iso8583_core::utils::mask_pan_config;
+///
+/// // PCI DSS first 6 / last 4
+/// assert_eq!(mask_pan_config("4111111111111111", 6, 4, '*'), "411111******1111");
+/// // Last 4 only
+/// assert_eq!(mask_pan_config("4111111111111111", 0, 4, 'X'), "XXXXXXXXXXXX1111");
+/// ```
+pub fn mask_pan_config(
+    pan: &str,
+    prefix_visible: usize,
+    suffix_visible: usize,
+    mask_char: char,
+) -> String {
+    let len = pan.chars().count();
+    if prefix_visible + suffix_visible >= len {
+        return mask_char.to_string().repeat(len);
+    }
+
+    pan.chars()
+        .enumerate()
+        .map(|(i, c)| {
+            if i < prefix_visible || i >= len - suffix_visible {
+                c
+            } else {
+                mask_char
+            }
+        })
+        .collect()
+}
+
+/// Extract the BIN (leading `digits` digits) from a PAN
+///
+/// Non-digit characters (spaces, dashes) are stripped first. Use 6 for the
+/// classic BIN length or 8 for the extended length.
+///
+/// # Example
+/// ```
+/// use iso8583_core::utils::extract_bin;
+///
+/// assert_eq!(extract_bin("4111 1111 1111 1111", 6).unwrap(), "411111");
+/// assert_eq!(extract_bin("5500000000000004", 8).unwrap(), "55000000");
+/// assert!(extract_bin("41111", 6).is_err());
+/// ```
+pub fn extract_bin(pan: &str, digits: usize) -> Result<String> {
+    let pan: String = pan.chars().filter(|c| c.is_ascii_digit()).collect();
+
+    if digits == 0 || pan.len() < digits {
+        return Err(ISO8583Error::InvalidPAN(format!(
+            "PAN has {} digits, cannot extract a {}-digit BIN",
+            pan.len(),
+            digits
+        )));
+    }
+
+    Ok(pan[..digits].to_string())
+}
+
 /// Format amount from minor units (cents/kobo) to major units with currency symbol
 ///
 /// # Example
@@ -75,6 +141,58 @@ pub fn generate_local_date() -> String {
     now.format("%m%d").to_string()
 }
 
+/// Compute the settlement date (Field 15) - MMDD
+///
+/// Transactions at or after the acquirer's `cutover` time settle on the
+/// next calendar day; earlier ones settle on the transaction date.
+/// Weekend and holiday calendars are not applied.
+///
+/// # Example
+/// ```
+/// use chrono::{NaiveTime, TimeZone, Utc};
+/// use iso8583_core::utils::settlement_date;
+///
+/// let cutover = NaiveTime::from_hms_opt(22, 0, 0).unwrap();
+/// let txn = Utc.with_ymd_and_hms(2026, 3, 14, 23, 30, 0).unwrap();
+/// assert_eq!(settlement_date(txn, cutover), "0315");
+/// ```
+pub fn settlement_date(txn: DateTime<Utc>, cutover: NaiveTime) -> String {
+    let mut date = txn.date_naive();
+    if txn.time() >= cutover {
+        // Only fails at NaiveDate::MAX
+        date = date.checked_add_days(Days::new(1)).unwrap_or(date);
+    }
+    date.format("%m%d").to_string()
+}
+
+/// Parse a settlement date (Field 15) - MMDD - in the given year
+///
+/// # Example
+/// ```
+/// use chrono::NaiveDate;
+/// use iso8583_core::utils::parse_settlement_date;
+///
+/// assert_eq!(
+///     parse_settlement_date("0229", 2028).unwrap(),
+///     NaiveDate::from_ymd_opt(2028, 2, 29).unwrap()
+/// );
+/// assert!(parse_settlement_date("0229", 2027).is_err());
+/// ```
+pub fn parse_settlement_date(mmdd: &str, base_year: i32) -> Result<NaiveDate> {
+    if mmdd.len() != 4 || !mmdd.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(ISO8583Error::invalid_datetime(
+            15,
+            "Must be 4 digits (MMDD)",
+        ));
+    }
+
+    let month: u32 = mmdd[0..2].parse().unwrap_or_default();
+    let day: u32 = mmdd[2..4].parse().unwrap_or_default();
+
+    NaiveDate::from_ymd_opt(base_year, month, day)
+        .ok_or_else(|| ISO8583Error::invalid_datetime(15, "Invalid settlement date"))
+}
+
 /// Parse transmission date/time (Field 7) - MMDDhhmmss
 pub fn parse_transmission_datetime(s: &str) -> Result<(u32, u32, u32, u32, u32)> {
     if s.len() != 10 {
@@ -124,9 +242,67 @@ pub fn format_expiration_date(year: u32, month: u32) -> String {
     format!("{:02}{:02}", year % 100, month)
 }
 
+/// Interpretation of 2-digit years (fields 14, 73 and RRN dates)
+///
+/// Two-digit years up to and including the pivot fall in the 2000s; later
+/// ones fall in the 1900s. With a pivot of 70, `"70"` is 2070 and `"71"` is
+/// 1971.
+///
+/// The default pivot is 99, so every 2-digit year maps to 2000-2099. This
+/// matches what the crate's parsers have always assumed.
+///
+/// # Example
+/// ```
+/// use iso8583_core::utils::CenturyWindow;
+///
+/// assert_eq!(CenturyWindow::default().expand(99), 2099);
+/// assert_eq!(CenturyWindow::new(70).expand(99), 1999);
+/// assert_eq!(CenturyWindow::new(70).expand(70), 2070);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CenturyWindow {
+    pivot: u8,
+}
+
+impl CenturyWindow {
+    /// Every 2-digit year is in 2000-2099 (the default)
+    pub const ALWAYS_2000S: Self = Self { pivot: 99 };
+
+    /// Create a window with the given pivot (clamped to 99)
+    pub const fn new(pivot: u8) -> Self {
+        Self {
+            pivot: if pivot > 99 { 99 } else { pivot },
+        }
+    }
+
+    /// Last 2-digit year interpreted as 20xx
+    pub fn pivot(&self) -> u8 {
+        self.pivot
+    }
+
+    /// Expand a 2-digit year (0-99) to a full year
+    pub fn expand(&self, yy: u8) -> i32 {
+        let yy = i32::from(yy % 100);
+        if yy <= i32::from(self.pivot) {
+            2000 + yy
+        } else {
+            1900 + yy
+        }
+    }
+}
+
+impl Default for CenturyWindow {
+    fn default() -> Self {
+        Self::ALWAYS_2000S
+    }
+}
+
 /// Parse expiration date (Field 14) - YYMM
+///
+/// Returns the 2-digit year as written; use
+/// [`parse_expiration_date_with`] for a full year.
 pub fn parse_expiration_date(s: &str) -> Result<(u32, u32)> {
-    if s.len() != 4 {
+    if s.len() != 4 || !s.bytes().all(|b| b.is_ascii_digit()) {
         return Err(ISO8583Error::invalid_datetime(
             14,
             "Must be 4 digits (YYMM)",
@@ -147,6 +323,51 @@ pub fn parse_expiration_date(s: &str) -> Result<(u32, u32)> {
     Ok((year, month))
 }
 
+/// Parse expiration date (Field 14) - YYMM - into a full year and month
+pub fn parse_expiration_date_with(s: &str, window: CenturyWindow) -> Result<(i32, u32)> {
+    let (yy, month) = parse_expiration_date(s)?;
+    Ok((window.expand(yy as u8), month))
+}
+
+/// Check if a card with expiration date `expiry` (YYMM) has expired
+///
+/// Cards are valid through the last day of the expiration month.
+///
+/// # Example
+/// ```
+/// use iso8583_core::utils::{is_card_expired, CenturyWindow};
+/// use chrono::NaiveDate;
+///
+/// let today = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+/// assert!(is_card_expired("2602", today, CenturyWindow::default()).unwrap());
+/// assert!(!is_card_expired("2603", today, CenturyWindow::default()).unwrap());
+///
+/// // "99" is 2099 by default but 1999 with a pivot of 70
+/// assert!(!is_card_expired("9912", today, CenturyWindow::default()).unwrap());
+/// assert!(is_card_expired("9912", today, CenturyWindow::new(70)).unwrap());
+/// ```
+pub fn is_card_expired(expiry: &str, today: NaiveDate, window: CenturyWindow) -> Result<bool> {
+    let (year, month) = parse_expiration_date_with(expiry, window)?;
+    Ok((year, month) < (today.year(), today.month()))
+}
+
+/// Parse a YYMMDD date (e.g. field 73, action date) into a full date
+pub fn parse_yymmdd(s: &str, field: u8, window: CenturyWindow) -> Result<NaiveDate> {
+    if s.len() != 6 || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(ISO8583Error::invalid_datetime(
+            field,
+            "Must be 6 digits (YYMMDD)",
+        ));
+    }
+
+    let year: u8 = s[0..2].parse().unwrap_or_default();
+    let month: u32 = s[2..4].parse().unwrap_or_default();
+    let day: u32 = s[4..6].parse().unwrap_or_default();
+
+    NaiveDate::from_ymd_opt(window.expand(year), month, day)
+        .ok_or_else(|| ISO8583Error::invalid_datetime(field, "Invalid date"))
+}
+
 /// Generate System Trace Audit Number (Field 11)
 /// In production, this should be a monotonically increasing counter
 pub fn generate_stan() -> String {
@@ -159,11 +380,135 @@ pub fn generate_stan() -> String {
 
 /// Generate Retrieval Reference Number (Field 37)
 /// Format: YYMMDD + 6-digit sequence
+///
+/// The sequence comes from a counter of its own, independent of
+/// [`generate_stan`], so consecutive calls always differ.
 pub fn generate_rrn() -> String {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    static SEQUENCE: AtomicU32 = AtomicU32::new(1);
+
     let now = Utc::now();
     let date_part = now.format("%y%m%d").to_string();
-    let sequence = generate_stan();
-    format!("{}{}", date_part, sequence)
+    let sequence = SEQUENCE.fetch_add(1, Ordering::SeqCst) % 1_000_000;
+    format!("{}{:06}", date_part, sequence)
+}
+
+/// Generate a Julian-date Retrieval Reference Number (Field 37)
+///
+/// Format: `YYDDD` (year and day of year) + 1-digit terminal + 6-digit
+/// sequence, always 12 digits. The caller owns the sequence, e.g. a
+/// per-terminal counter.
+///
+/// # Example
+/// ```
+/// use iso8583_core::utils::generate_rrn_with;
+/// use chrono::NaiveDate;
+///
+/// let date = NaiveDate::from_ymd_opt(2026, 2, 1).unwrap();
+/// assert_eq!(generate_rrn_with(date, 3, 42).unwrap(), "260323000042");
+/// ```
+pub fn generate_rrn_with(date: NaiveDate, terminal_digit: u8, sequence: u32) -> Result<String> {
+    if terminal_digit > 9 {
+        return Err(ISO8583Error::invalid_field_value(
+            37,
+            "RRN terminal digit must be 0-9",
+        ));
+    }
+    if sequence > 999_999 {
+        return Err(ISO8583Error::invalid_field_value(
+            37,
+            "RRN sequence must fit in 6 digits",
+        ));
+    }
+
+    Ok(format!(
+        "{:02}{:03}{}{:06}",
+        date.year().rem_euclid(100),
+        date.ordinal(),
+        terminal_digit,
+        sequence
+    ))
+}
+
+/// Parse a Julian-date Retrieval Reference Number (Field 37)
+///
+/// Expects the format produced by [`generate_rrn_with`] and returns
+/// `(date, terminal_digit, sequence)`. The year is interpreted with
+/// `window`.
+pub fn parse_julian_rrn(s: &str, window: CenturyWindow) -> Result<(NaiveDate, u8, u32)> {
+    if s.len() != 12 || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(ISO8583Error::invalid_field_value(
+            37,
+            "RRN must be exactly 12 digits (YYDDD + terminal + sequence)",
+        ));
+    }
+
+    let year = window.expand(s[0..2].parse().unwrap_or_default());
+    let day: u32 = s[2..5].parse().unwrap_or_default();
+    let date = NaiveDate::from_yo_opt(year, day)
+        .ok_or_else(|| ISO8583Error::invalid_datetime(37, "Invalid RRN Julian date"))?;
+
+    let terminal_digit = s.as_bytes()[5] - b'0';
+    let sequence = parse_stan(&s[6..12])?;
+
+    Ok((date, terminal_digit, sequence))
+}
+
+/// Parse System Trace Audit Number (Field 11) into an integer
+///
+/// # Example
+/// ```
+/// use iso8583_core::utils::parse_stan;
+///
+/// assert_eq!(parse_stan("000001").unwrap(), 1);
+/// assert!(parse_stan("1234567").is_err());
+/// ```
+pub fn parse_stan(s: &str) -> Result<u32> {
+    if s.len() != 6 || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(ISO8583Error::invalid_field_value(
+            11,
+            "STAN must be exactly 6 digits",
+        ));
+    }
+
+    // Six digits always fit in a u32
+    Ok(s.bytes().fold(0, |acc, b| acc * 10 + (b - b'0') as u32))
+}
+
+/// Parse Retrieval Reference Number (Field 37) into its date and sequence
+///
+/// Expects the `YYMMDD` + 6-digit sequence format produced by [`generate_rrn`].
+/// The year is interpreted with the default [`CenturyWindow`].
+///
+/// # Example
+/// ```
+/// use iso8583_core::utils::parse_rrn;
+/// use chrono::NaiveDate;
+///
+/// let (date, sequence) = parse_rrn("260115000042").unwrap();
+/// assert_eq!(date, NaiveDate::from_ymd_opt(2026, 1, 15).unwrap());
+/// assert_eq!(sequence, 42);
+/// ```
+pub fn parse_rrn(s: &str) -> Result<(NaiveDate, u32)> {
+    parse_rrn_with(s, CenturyWindow::default())
+}
+
+/// Parse Retrieval Reference Number (Field 37) with an explicit century window
+pub fn parse_rrn_with(s: &str, window: CenturyWindow) -> Result<(NaiveDate, u32)> {
+    if s.len() != 12 || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(ISO8583Error::invalid_field_value(
+            37,
+            "RRN must be exactly 12 digits (YYMMDD + sequence)",
+        ));
+    }
+
+    let date = parse_yymmdd(&s[0..6], 37, window)
+        .map_err(|_| ISO8583Error::invalid_datetime(37, "Invalid RRN date"))?;
+
+    let sequence = parse_stan(&s[6..12])
+        .map_err(|_| ISO8583Error::invalid_field_value(37, "Invalid RRN sequence"))?;
+
+    Ok((date, sequence))
 }
 
 /// Convert currency code to symbol
@@ -219,6 +564,83 @@ pub fn validate_track2(track2: &str) -> bool {
     true
 }
 
+/// Parsed Track 1 data (Field 45), format code B
+///
+/// Layout: `[%]B<PAN>^<NAME>^<YYMM><service code><discretionary>[?]`.
+/// The start (`%`) and end (`?`) sentinels are optional.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Track1 {
+    /// Primary account number (13-19 digits)
+    pub pan: String,
+    /// Cardholder name (2-26 characters, e.g. "DOE/JOHN Q")
+    pub name: String,
+    /// Expiration date (YYMM)
+    pub expiry: String,
+    /// Service code (3 digits)
+    pub service_code: String,
+    /// Issuer discretionary data, if any
+    pub discretionary: Option<String>,
+}
+
+impl Track1 {
+    /// Parse and validate Track 1 data
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::utils::Track1;
+    ///
+    /// let track1 = Track1::parse("%B4111111111111111^DOE/JOHN Q^2512101123456?").unwrap();
+    /// assert_eq!(track1.name, "DOE/JOHN Q");
+    /// assert_eq!(track1.expiry, "2512");
+    /// assert_eq!(track1.service_code, "101");
+    /// assert_eq!(track1.discretionary.as_deref(), Some("123456"));
+    /// ```
+    pub fn parse(data: &str) -> Result<Self> {
+        let invalid = |reason: &str| ISO8583Error::invalid_field_value(45, reason);
+
+        let data = data.strip_prefix('%').unwrap_or(data);
+        let data = data.strip_suffix('?').unwrap_or(data);
+        let data = data
+            .strip_prefix('B')
+            .ok_or_else(|| invalid("Track 1 format code must be 'B'"))?;
+
+        let mut parts = data.splitn(3, '^');
+        let pan = parts.next().unwrap_or_default();
+        let name = parts
+            .next()
+            .ok_or_else(|| invalid("Missing '^' after PAN"))?;
+        let rest = parts
+            .next()
+            .ok_or_else(|| invalid("Missing '^' after name"))?;
+
+        if !(13..=19).contains(&pan.len()) || !pan.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid("PAN must be 13-19 digits"));
+        }
+        if !(2..=26).contains(&name.len()) || !name.bytes().all(|b| (0x20..0x7F).contains(&b)) {
+            return Err(invalid("Name must be 2-26 printable characters"));
+        }
+        if rest.len() < 7 || !rest.as_bytes()[..7].iter().all(u8::is_ascii_digit) {
+            return Err(invalid("Expiry (YYMM) and service code must be 7 digits"));
+        }
+
+        let discretionary = &rest[7..];
+        Ok(Self {
+            pan: pan.to_string(),
+            name: name.to_string(),
+            expiry: rest[..4].to_string(),
+            service_code: rest[4..7].to_string(),
+            discretionary: (!discretionary.is_empty()).then(|| discretionary.to_string()),
+        })
+    }
+}
+
+/// Validate Track 1 data format
+///
+/// See [`Track1::parse`] for the accepted layout.
+pub fn validate_track1(track1: &str) -> bool {
+    Track1::parse(track1).is_ok()
+}
+
 /// Generate random authorization ID (Field 38)
 pub fn generate_auth_id() -> String {
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -242,6 +664,41 @@ mod tests {
         assert_eq!(mask_pan("123"), "***"); // Too short
     }
 
+    #[test]
+    fn test_mask_pan_config() {
+        // 13, 16 and 19 digits with the PCI default
+        assert_eq!(mask_pan_config("4222222222222", 6, 4, '*'), "422222***2222");
+        assert_eq!(
+            mask_pan_config("5500000000000004", 6, 4, '*'),
+            "550000******0004"
+        );
+        assert_eq!(
+            mask_pan_config("6011000990139424123", 6, 4, '*'),
+            "601100*********4123"
+        );
+
+        // Co-brand BIN display and last-4-only
+        assert_eq!(
+            mask_pan_config("5500000000000004", 8, 4, '*'),
+            "55000000****0004"
+        );
+        assert_eq!(mask_pan_config("4222222222222", 0, 4, '#'), "#########2222");
+
+        // Short identifiers are never fully revealed
+        assert_eq!(mask_pan_config("1234567890", 6, 4, '*'), "**********");
+        assert_eq!(mask_pan_config("12345", 0, 4, '*'), "*2345");
+        assert_eq!(mask_pan_config("", 6, 4, '*'), "");
+    }
+
+    #[test]
+    fn test_extract_bin() {
+        assert_eq!(extract_bin("4111111111111111", 6).unwrap(), "411111");
+        assert_eq!(extract_bin("4111-1111-1111-1111", 8).unwrap(), "41111111");
+        assert_eq!(extract_bin("411111", 6).unwrap(), "411111");
+        assert!(extract_bin("4111111", 8).is_err());
+        assert!(extract_bin("4111111111111111", 0).is_err());
+    }
+
     #[test]
     fn test_format_amount() {
         assert_eq!(format_amount("000000010000", "$"), "$100.00");
@@ -280,6 +737,37 @@ mod tests {
         assert_eq!(second, 30);
     }
 
+    #[test]
+    fn test_settlement_date_cutover() {
+        use chrono::TimeZone;
+
+        let cutover = NaiveTime::from_hms_opt(22, 0, 0).unwrap();
+        let at = |m, d, h, min| Utc.with_ymd_and_hms(2025, m, d, h, min, 0).unwrap();
+
+        assert_eq!(settlement_date(at(6, 10, 21, 59), cutover), "0610");
+        assert_eq!(settlement_date(at(6, 10, 22, 0), cutover), "0611");
+        assert_eq!(settlement_date(at(6, 10, 23, 30), cutover), "0611");
+
+        // Month-end and year-end rollover
+        assert_eq!(settlement_date(at(4, 30, 23, 30), cutover), "0501");
+        assert_eq!(settlement_date(at(2, 28, 22, 15), cutover), "0301");
+        assert_eq!(settlement_date(at(12, 31, 23, 30), cutover), "0101");
+
+        // Midnight cutover never rolls over
+        assert_eq!(settlement_date(at(6, 10, 23, 59), NaiveTime::MIN), "0611");
+    }
+
+    #[test]
+    fn test_parse_settlement_date() {
+        let date = parse_settlement_date("1231", 2025).unwrap();
+        assert_eq!(date, NaiveDate::from_ymd_opt(2025, 12, 31).unwrap());
+
+        assert!(parse_settlement_date("1301", 2025).is_err());
+        assert!(parse_settlement_date("0431", 2025).is_err());
+        assert!(parse_settlement_date("123", 2025).is_err());
+        assert!(parse_settlement_date("12a1", 2025).is_err());
+    }
+
     #[test]
     fn test_expiration_date() {
         assert_eq!(format_expiration_date(2025, 12), "2512");
@@ -300,6 +788,95 @@ mod tests {
         assert_ne!(stan1, stan2); // Should be different
     }
 
+    #[test]
+    fn test_parse_stan() {
+        assert_eq!(parse_stan("000001").unwrap(), 1);
+        assert_eq!(parse_stan("999999").unwrap(), 999_999);
+        assert!(parse_stan("1234567").is_err());
+        assert!(parse_stan("12345").is_err());
+        assert!(parse_stan("12a456").is_err());
+        assert!(parse_stan("+12345").is_err());
+
+        let stan = generate_stan();
+        assert_eq!(format!("{:06}", parse_stan(&stan).unwrap()), stan);
+    }
+
+    #[test]
+    fn test_parse_rrn() {
+        let (date, sequence) = parse_rrn("251231999999").unwrap();
+        assert_eq!(date, NaiveDate::from_ymd_opt(2025, 12, 31).unwrap());
+        assert_eq!(sequence, 999_999);
+
+        assert!(parse_rrn(&generate_rrn()).is_ok());
+        assert!(parse_rrn("2512310000001").is_err());
+        assert!(parse_rrn("25123100000").is_err());
+        assert!(parse_rrn("251332000001").is_err()); // Month 13
+        assert!(parse_rrn("25123100000A").is_err());
+
+        let (date, _) = parse_rrn_with("991231000001", CenturyWindow::new(70)).unwrap();
+        assert_eq!(date, NaiveDate::from_ymd_opt(1999, 12, 31).unwrap());
+    }
+
+    #[test]
+    fn test_julian_rrn() {
+        let date = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap(); // Day 366
+        let rrn = generate_rrn_with(date, 7, 999_999).unwrap();
+        assert_eq!(rrn, "243667999999");
+        assert_eq!(
+            parse_julian_rrn(&rrn, CenturyWindow::default()).unwrap(),
+            (date, 7, 999_999)
+        );
+
+        let first = generate_rrn_with(date, 0, 1).unwrap();
+        let second = generate_rrn_with(date, 0, 2).unwrap();
+        assert_eq!(first.len(), 12);
+        assert_ne!(first, second);
+
+        assert!(generate_rrn_with(date, 10, 1).is_err());
+        assert!(generate_rrn_with(date, 0, 1_000_000).is_err());
+        assert!(parse_julian_rrn("253660000001", CenturyWindow::default()).is_err()); // Not a leap year
+        assert!(parse_julian_rrn("250000000001", CenturyWindow::default()).is_err());
+        assert!(parse_julian_rrn("2500100000001", CenturyWindow::default()).is_err());
+    }
+
+    #[test]
+    fn test_generate_rrn_sequence() {
+        let first = generate_rrn();
+        let second = generate_rrn();
+        assert_eq!(first.len(), 12);
+        assert_ne!(first, second);
+        assert!(parse_rrn(&second).is_ok());
+    }
+
+    #[test]
+    fn test_century_window() {
+        let window = CenturyWindow::new(70);
+        assert_eq!(window.expand(0), 2000);
+        assert_eq!(window.expand(70), 2070);
+        assert_eq!(window.expand(71), 1971);
+        assert_eq!(CenturyWindow::new(200).pivot(), 99);
+        assert_eq!(CenturyWindow::default().expand(99), 2099);
+
+        assert_eq!(
+            parse_expiration_date_with("9912", window).unwrap(),
+            (1999, 12)
+        );
+        assert_eq!(
+            parse_expiration_date_with("9912", CenturyWindow::default()).unwrap(),
+            (2099, 12)
+        );
+        assert!(parse_expiration_date("+912").is_err());
+
+        assert_eq!(
+            parse_yymmdd("700229", 73, CenturyWindow::new(69)).unwrap_err(),
+            ISO8583Error::invalid_datetime(73, "Invalid date") // 1970 is not a leap year
+        );
+        assert_eq!(
+            parse_yymmdd("680229", 73, CenturyWindow::new(69)).unwrap(),
+            NaiveDate::from_ymd_opt(2068, 2, 29).unwrap()
+        );
+    }
+
     #[test]
     fn test_currency_functions() {
         assert_eq!(currency_symbol("840"), "$");
@@ -316,6 +893,32 @@ mod tests {
         assert!(!validate_track2("123=25")); // PAN too short
     }
 
+    #[test]
+    fn test_track1_parse() {
+        let track1 = Track1::parse("B4111111111111111^SMITH/JANE MARIE^2512201").unwrap();
+        assert_eq!(track1.pan, "4111111111111111");
+        assert_eq!(track1.name, "SMITH/JANE MARIE");
+        assert_eq!(track1.expiry, "2512");
+        assert_eq!(track1.service_code, "201");
+        assert_eq!(track1.discretionary, None);
+
+        let track1 = Track1::parse("%B5500000000000004^VAN DER BERG/A^27011010000 123?").unwrap();
+        assert_eq!(track1.name, "VAN DER BERG/A");
+        assert_eq!(track1.discretionary.as_deref(), Some("0000 123"));
+    }
+
+    #[test]
+    fn test_track1_validation() {
+        assert!(validate_track1("%B4111111111111111^DOE/JOHN^2512101?"));
+        assert!(!validate_track1("%A4111111111111111^DOE/JOHN^2512101?")); // Format code
+        assert!(!validate_track1("B4111111111111111DOE/JOHN^2512101")); // Missing separator
+        assert!(!validate_track1("B4111111111111111^DOE/JOHN2512101")); // Missing separator
+        assert!(!validate_track1("B411111^DOE/JOHN^2512101")); // PAN too short
+        assert!(!validate_track1("B4111111111111111^D^2512101")); // Name too short
+        assert!(!validate_track1("B4111111111111111^DOE/JOHN^25121")); // No service code
+        assert!(!validate_track1("B4111111111111111^DOE/JOHN^25AB101")); // Non-digit expiry
+    }
+
     #[test]
     fn test_auth_id_generation() {
         let auth_id = generate_auth_id();
@@ -1,8 +1,215 @@
 //! Validation utilities for ISO 8583 messages and fields
 
+use crate::emv;
 use crate::error::{ISO8583Error, Result};
-use crate::field::{Field, FieldLength, FieldType, FieldValue};
+use crate::field::{Field, FieldValue};
 use crate::message::ISO8583Message;
+use crate::mti::MessageType;
+#[cfg(feature = "serde")]
+use crate::{field::FieldDefinition, schema::SpecSchema};
+use chrono::{DateTime, Datelike, Duration, TimeZone, Utc};
+
+/// Assigned ISO 4217 numeric currency codes (sorted, for binary search)
+///
+/// Excludes 999 ("no currency"), which is only accepted on request.
+pub const ISO4217_NUMERIC_CODES: &[&str] = &[
+    "008", "012", "032", "036", "044", "048", "050", "051", "052", "060", "064", "068", "072",
+    "084", "090", "096", "104", "108", "116", "124", "132", "136", "144", "152", "156", "170",
+    "174", "188", "192", "203", "208", "214", "222", "230", "232", "238", "242", "262", "270",
+    "292", "320", "324", "328", "332", "340", "344", "348", "352", "356", "360", "364", "368",
+    "376", "388", "392", "398", "400", "404", "408", "410", "414", "417", "418", "422", "426",
+    "430", "434", "446", "454", "458", "462", "480", "484", "496", "498", "504", "512", "516",
+    "524", "532", "533", "548", "554", "558", "566", "578", "586", "590", "598", "600", "604",
+    "608", "634", "643", "646", "654", "682", "690", "694", "702", "704", "706", "710", "728",
+    "748", "752", "756", "760", "764", "776", "780", "784", "788", "800", "807", "818", "826",
+    "834", "840", "858", "860", "882", "886", "901", "924", "925", "926", "927", "928", "929",
+    "930", "931", "932", "933", "934", "936", "938", "940", "941", "943", "944", "946", "947",
+    "948", "949", "950", "951", "952", "953", "955", "956", "957", "958", "959", "960", "961",
+    "962", "963", "964", "965", "967", "968", "969", "970", "971", "972", "973", "975", "976",
+    "977", "978", "979", "980", "981", "984", "985", "986", "990", "994", "997",
+];
+
+/// Assigned ISO 3166-1 numeric country codes (sorted, for binary search)
+pub const ISO3166_NUMERIC_CODES: &[&str] = &[
+    "004", "008", "010", "012", "016", "020", "024", "028", "031", "032", "036", "040", "044",
+    "048", "050", "051", "052", "056", "060", "064", "068", "070", "072", "074", "076", "084",
+    "086", "090", "092", "096", "100", "104", "108", "112", "116", "120", "124", "132", "136",
+    "140", "144", "148", "152", "156", "158", "162", "166", "170", "174", "175", "178", "180",
+    "184", "188", "191", "192", "196", "203", "204", "208", "212", "214", "218", "222", "226",
+    "231", "232", "233", "234", "238", "239", "242", "246", "248", "250", "254", "258", "260",
+    "262", "266", "268", "270", "275", "276", "288", "292", "296", "300", "304", "308", "312",
+    "316", "320", "324", "328", "332", "334", "336", "340", "344", "348", "352", "356", "360",
+    "364", "368", "372", "376", "380", "384", "388", "392", "398", "400", "404", "408", "410",
+    "414", "417", "418", "422", "426", "428", "430", "434", "438", "440", "442", "446", "450",
+    "454", "458", "462", "466", "470", "474", "478", "480", "484", "492", "496", "498", "499",
+    "500", "504", "508", "512", "516", "520", "524", "528", "531", "533", "534", "535", "540",
+    "548", "554", "558", "562", "566", "570", "574", "578", "580", "581", "583", "584", "585",
+    "586", "591", "598", "600", "604", "608", "612", "616", "620", "624", "626", "630", "634",
+    "638", "642", "643", "646", "652", "654", "659", "660", "662", "663", "666", "670", "674",
+    "678", "682", "686", "688", "690", "694", "702", "703", "704", "705", "706", "710", "716",
+    "724", "728", "729", "732", "740", "744", "748", "752", "756", "760", "762", "764", "768",
+    "772", "776", "780", "784", "788", "792", "795", "796", "798", "800", "804", "807", "818",
+    "826", "831", "832", "833", "834", "840", "850", "854", "858", "860", "862", "876", "882",
+    "887", "894",
+];
+
+/// Assigned ISO 18245 merchant category code ranges (inclusive)
+///
+/// 0000-0699 and 1000-1499 are reserved.
+pub const MCC_ASSIGNED_RANGES: &[(u16, u16)] = &[
+    (700, 999),   // Agricultural services
+    (1500, 2999), // Contracted services
+    (3000, 3999), // Airlines, car rental, lodging
+    (4000, 4799), // Transportation
+    (4800, 4999), // Utilities
+    (5000, 5599), // Retail outlets
+    (5600, 5699), // Clothing stores
+    (5700, 7299), // Miscellaneous stores
+    (7300, 7999), // Business services
+    (8000, 8999), // Professional services and membership organizations
+    (9000, 9999), // Government services
+];
+
+/// Descriptions of common merchant category codes (sorted, for binary search)
+pub const MCC_DESCRIPTIONS: &[(&str, &str)] = &[
+    ("0742", "Veterinary services"),
+    ("1520", "General contractors, residential and commercial"),
+    ("4111", "Local and suburban commuter transportation"),
+    ("4121", "Taxicabs and limousines"),
+    ("4511", "Airlines and air carriers"),
+    ("4812", "Telecommunication equipment and telephone sales"),
+    ("4814", "Telecommunication services"),
+    ("4900", "Utilities"),
+    ("5045", "Computers, peripherals and software"),
+    ("5311", "Department stores"),
+    ("5411", "Grocery stores and supermarkets"),
+    ("5499", "Miscellaneous food stores"),
+    ("5541", "Service stations"),
+    ("5542", "Automated fuel dispensers"),
+    ("5651", "Family clothing stores"),
+    ("5691", "Men's and women's clothing stores"),
+    ("5732", "Electronics stores"),
+    ("5812", "Eating places and restaurants"),
+    ("5813", "Drinking places (bars, taverns, nightclubs)"),
+    ("5814", "Fast food restaurants"),
+    ("5912", "Drug stores and pharmacies"),
+    ("5942", "Book stores"),
+    ("5999", "Miscellaneous and specialty retail stores"),
+    ("6010", "Financial institutions, manual cash disbursements"),
+    (
+        "6011",
+        "Financial institutions, automated cash disbursements",
+    ),
+    ("6012", "Financial institutions, merchandise and services"),
+    (
+        "6051",
+        "Non-financial institutions, foreign currency and quasi-cash",
+    ),
+    ("6300", "Insurance sales and underwriting"),
+    ("7011", "Lodging, hotels, motels and resorts"),
+    ("7230", "Barber and beauty shops"),
+    ("7299", "Miscellaneous personal services"),
+    ("7372", "Computer programming and data processing"),
+    ("7399", "Business services"),
+    ("7512", "Car rental agencies"),
+    ("7523", "Parking lots and garages"),
+    ("7832", "Motion picture theaters"),
+    ("7995", "Betting and gambling"),
+    ("8011", "Doctors and physicians"),
+    ("8021", "Dentists and orthodontists"),
+    ("8062", "Hospitals"),
+    ("8099", "Medical services"),
+    ("8220", "Colleges and universities"),
+    ("8398", "Charitable and social service organizations"),
+    ("8999", "Professional services"),
+    ("9211", "Court costs"),
+    ("9222", "Fines"),
+    ("9311", "Tax payments"),
+    ("9399", "Government services"),
+    ("9402", "Postal services"),
+];
+
+/// Mandatory fields for one message type
+///
+/// # Example
+/// ```
+/// use iso8583_core::validation::MtiProfile;
+/// use iso8583_core::*;
+///
+/// let profile = MtiProfile::for_mti(MessageType::AUTHORIZATION_REQUEST);
+/// let msg = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+/// assert_eq!(msg.missing_required(&profile).len(), profile.mandatory.len());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MtiProfile {
+    /// Message type the profile applies to
+    pub mti: MessageType,
+    /// Fields that must be present
+    pub mandatory: Vec<Field>,
+}
+
+impl MtiProfile {
+    /// Create a profile with an explicit mandatory field list
+    pub fn new(mti: MessageType, mandatory: Vec<Field>) -> Self {
+        Self { mti, mandatory }
+    }
+
+    /// Profile using the built-in [`Validator::required_fields`] rules
+    pub fn for_mti(mti: MessageType) -> Self {
+        Self::new(mti, Validator::required_fields(&mti))
+    }
+}
+
+/// Opt-in check between related fields, run by [`Validator::validate_message_with`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CrossFieldRule {
+    /// Field 4 (amount) requires field 49 (transaction currency)
+    AmountRequiresCurrency,
+    /// Field 49 (transaction currency) requires field 4 (amount)
+    CurrencyRequiresAmount,
+    /// Field 5 (settlement amount) requires field 50 (settlement currency)
+    SettlementAmountRequiresCurrency,
+    /// Field 6 (cardholder billing amount) requires field 51 (billing currency)
+    BillingAmountRequiresCurrency,
+}
+
+impl CrossFieldRule {
+    /// Fields 4 and 49 must be both present or both absent
+    pub const AMOUNT_AND_CURRENCY: &'static [Self] =
+        &[Self::AmountRequiresCurrency, Self::CurrencyRequiresAmount];
+
+    /// Every amount (fields 4, 5 and 6) must carry its currency (fields 49,
+    /// 50 and 51), for multi-currency messages
+    pub const MULTI_CURRENCY: &'static [Self] = &[
+        Self::AmountRequiresCurrency,
+        Self::SettlementAmountRequiresCurrency,
+        Self::BillingAmountRequiresCurrency,
+    ];
+
+    /// Check the rule against a message
+    pub fn check(&self, msg: &ISO8583Message) -> Result<()> {
+        let (present, required) = match self {
+            Self::AmountRequiresCurrency => {
+                (Field::TransactionAmount, Field::CurrencyCodeTransaction)
+            }
+            Self::CurrencyRequiresAmount => {
+                (Field::CurrencyCodeTransaction, Field::TransactionAmount)
+            }
+            Self::SettlementAmountRequiresCurrency => {
+                (Field::SettlementAmount, Field::CurrencyCodeSettlement)
+            }
+            Self::BillingAmountRequiresCurrency => (
+                Field::CardholderBillingAmount,
+                Field::CurrencyCodeCardholderBilling,
+            ),
+        };
+
+        if msg.has_field(present) && !msg.has_field(required) {
+            return Err(ISO8583Error::MissingRequiredField(required.number()));
+        }
+        Ok(())
+    }
+}
 
 /// Validator for ISO 8583 messages and fields
 pub struct Validator;
@@ -66,58 +273,7 @@ impl Validator {
 
     /// Validate field format based on field type
     pub fn validate_field_format(field: Field, value: &FieldValue) -> Result<()> {
-        let def = field.definition();
-
-        match value {
-            FieldValue::String(s) => {
-                // Check field type constraints
-                match def.field_type {
-                    FieldType::Numeric => {
-                        if !s.chars().all(|c: char| c.is_ascii_digit()) {
-                            return Err(ISO8583Error::invalid_field_value(
-                                field.number(),
-                                "Field must be numeric",
-                            ));
-                        }
-                    }
-                    FieldType::Alpha => {
-                        if !s.chars().all(|c: char| c.is_ascii_alphabetic() || c == ' ') {
-                            return Err(ISO8583Error::invalid_field_value(
-                                field.number(),
-                                "Field must be alphabetic",
-                            ));
-                        }
-                    }
-                    _ => {} // Other types allow more characters
-                }
-
-                // Check length
-                match def.length {
-                    FieldLength::Fixed(len) => {
-                        if s.len() != len {
-                            return Err(ISO8583Error::field_length_mismatch(
-                                field.number(),
-                                len,
-                                s.len(),
-                            ));
-                        }
-                    }
-                    FieldLength::LLVar(max_len) | FieldLength::LLLVar(max_len) => {
-                        if s.len() > max_len {
-                            return Err(ISO8583Error::invalid_field_value(
-                                field.number(),
-                                format!("Field exceeds maximum length of {}", max_len),
-                            ));
-                        }
-                    }
-                }
-            }
-            FieldValue::Binary(_) => {
-                // Binary fields have their own validation rules
-            }
-        }
-
-        Ok(())
+        field.definition().validate(value)
     }
 
     /// Validate specific field values
@@ -163,47 +319,147 @@ impl Validator {
         Ok(())
     }
 
-    /// Validate required fields for a message type
-    pub fn validate_required_fields(msg: &ISO8583Message) -> Result<()> {
+    /// Fields required for a message type
+    pub fn required_fields(mti: &MessageType) -> Vec<Field> {
         // Common required fields for most transactions
-        let common_required = vec![
+        let mut required = vec![
             Field::ProcessingCode,
             Field::SystemTraceAuditNumber,
             Field::LocalTransactionTime,
             Field::LocalTransactionDate,
         ];
 
-        for field in common_required {
+        // Message type specific requirements
+        if mti.is_request() {
+            // Requests typically need PAN and amount
+            if mti.class == crate::mti::MessageClass::Financial
+                || mti.class == crate::mti::MessageClass::Authorization
+            {
+                required.push(Field::PrimaryAccountNumber);
+                required.push(Field::TransactionAmount);
+            }
+        }
+
+        if mti.is_response() {
+            // Responses need a response code
+            required.push(Field::ResponseCode);
+        }
+
+        required
+    }
+
+    /// Validate required fields for a message type
+    pub fn validate_required_fields(msg: &ISO8583Message) -> Result<()> {
+        for field in Self::required_fields(&msg.mti) {
             if msg.get_field(field).is_none() {
                 return Err(ISO8583Error::MissingRequiredField(field.number()));
             }
         }
 
-        // Message type specific requirements
-        if msg.mti.is_request() {
-            // Requests typically need PAN and amount
-            if msg.mti.class == crate::mti::MessageClass::Financial
-                || msg.mti.class == crate::mti::MessageClass::Authorization
-            {
-                if msg.get_field(Field::PrimaryAccountNumber).is_none() {
-                    return Err(ISO8583Error::MissingRequiredField(2));
-                }
-                if msg.get_field(Field::TransactionAmount).is_none() {
-                    return Err(ISO8583Error::MissingRequiredField(4));
-                }
+        Ok(())
+    }
+
+    /// Validate a complete message
+    ///
+    /// Checks required fields and, for chip transactions (field 22 entry
+    /// mode 05, 07 or 95) carrying field 55, that the EMV tags needed for
+    /// online authorization are present.
+    pub fn validate_message(msg: &ISO8583Message) -> Result<()> {
+        Self::validate_required_fields(msg)?;
+        Self::validate_emv_data(msg)?;
+
+        Ok(())
+    }
+
+    /// Validate a complete message, then apply opt-in cross-field rules
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::field::FieldValue;
+    /// use iso8583_core::validation::CrossFieldRule;
+    /// use iso8583_core::*;
+    ///
+    /// let mut msg = ISO8583Message::new(MessageType::NETWORK_MANAGEMENT_REQUEST);
+    /// msg.set_field(Field::TransactionAmount, FieldValue::from_string("000000001000"))?;
+    ///
+    /// assert_eq!(
+    ///     CrossFieldRule::AmountRequiresCurrency.check(&msg),
+    ///     Err(ISO8583Error::MissingRequiredField(49))
+    /// );
+    /// assert!(CrossFieldRule::CurrencyRequiresAmount.check(&msg).is_ok());
+    /// # Ok::<(), ISO8583Error>(())
+    /// ```
+    pub fn validate_message_with(msg: &ISO8583Message, rules: &[CrossFieldRule]) -> Result<()> {
+        Self::validate_message(msg)?;
+        for rule in rules {
+            rule.check(msg)?;
+        }
+
+        Ok(())
+    }
+
+    /// Validate a message against a JSON-loaded [`SpecSchema`]
+    ///
+    /// Checks the schema's required fields for the message's MTI, then the
+    /// format of every present field the schema defines. Fields the schema
+    /// does not mention are not format-checked.
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::schema::SpecSchema;
+    /// use iso8583_core::*;
+    ///
+    /// let schema = SpecSchema::from_json(r#"{ "required": { "0800": [11, 70] } }"#)?;
+    /// let msg = ISO8583Message::new(MessageType::NETWORK_MANAGEMENT_REQUEST);
+    ///
+    /// assert_eq!(
+    ///     Validator::validate_with_schema(&msg, &schema),
+    ///     Err(ISO8583Error::MissingRequiredField(11))
+    /// );
+    /// # Ok::<(), ISO8583Error>(())
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn validate_with_schema(msg: &ISO8583Message, schema: &SpecSchema) -> Result<()> {
+        for number in schema.required_fields(&msg.mti) {
+            if !msg.has_field(Field::from_number(number)?) {
+                return Err(ISO8583Error::MissingRequiredField(number));
             }
         }
 
-        if msg.mti.is_response() {
-            // Responses need a response code
-            if msg.get_field(Field::ResponseCode).is_none() {
-                return Err(ISO8583Error::MissingRequiredField(39));
+        for number in msg.get_field_numbers() {
+            let Some(def) = schema.definition(number) else {
+                continue;
+            };
+            let field = Field::from_number(number)?;
+            let def = FieldDefinition::from_spec(number, &def);
+            if let Some(value) = msg.get_field(field) {
+                def.validate(value)?;
             }
         }
 
         Ok(())
     }
 
+    /// Validate field 55 EMV data for chip transactions
+    fn validate_emv_data(msg: &ISO8583Message) -> Result<()> {
+        let is_chip = msg.get_pos_entry_mode().is_ok_and(|mode| mode.is_chip());
+
+        let icc_data = match msg.get_field(Field::ReservedISO1) {
+            Some(value) if is_chip => value,
+            _ => return Ok(()),
+        };
+
+        let data = match icc_data {
+            FieldValue::Binary(b) => b.clone(),
+            FieldValue::String(s) => hex::decode(s).map_err(|e| {
+                ISO8583Error::invalid_field_value(55, format!("Invalid hex EMV data: {}", e))
+            })?,
+        };
+
+        let tlvs = emv::parse_tlvs(&data)?;
+        emv::validate_required_tags(&tlvs, emv::ONLINE_AUTH_REQUIRED_TAGS)
+    }
+
     /// Validate date format (MMDD)
     pub fn validate_date_mmdd(date: &str) -> bool {
         if date.len() != 4 {
@@ -236,9 +492,217 @@ impl Validator {
         false
     }
 
+    /// Check that a field 7 transmission date/time (MMDDhhmmss, UTC) is within
+    /// `max_skew` of `now`
+    ///
+    /// Field 7 carries no year, so the year closest to `now` is assumed: a
+    /// message stamped `1231235959` checked just after midnight on 1 January
+    /// is from the previous year. A time exactly `max_skew` away is accepted.
+    ///
+    /// # Example
+    /// ```
+    /// use chrono::{Duration, TimeZone, Utc};
+    /// use iso8583_core::validation::Validator;
+    ///
+    /// let now = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 30).unwrap();
+    /// let skew = Duration::minutes(5);
+    ///
+    /// assert!(Validator::validate_transmission_time("1231235959", now, skew).is_ok());
+    /// assert!(Validator::validate_transmission_time("0101001000", now, skew).is_err());
+    /// ```
+    pub fn validate_transmission_time(
+        field7: &str,
+        now: DateTime<Utc>,
+        max_skew: Duration,
+    ) -> Result<()> {
+        let (month, day, hour, minute, second) = crate::utils::parse_transmission_datetime(field7)?;
+
+        let sent = [now.year() - 1, now.year(), now.year() + 1]
+            .into_iter()
+            .filter_map(|year| {
+                Utc.with_ymd_and_hms(year, month, day, hour, minute, second)
+                    .single()
+            })
+            .min_by_key(|sent| (*sent - now).num_seconds().abs())
+            .ok_or_else(|| ISO8583Error::invalid_datetime(7, "Invalid calendar date"))?;
+
+        let skew = sent - now;
+        if skew > max_skew || -skew > max_skew {
+            return Err(ISO8583Error::invalid_datetime(
+                7,
+                format!(
+                    "Transmission time is {}s from now, beyond the allowed {}s",
+                    skew.num_seconds(),
+                    max_skew.num_seconds()
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Check that present fields hold values within their implied ranges
+    ///
+    /// The semantic layer above format validation, applied to every present
+    /// text field it knows:
+    ///
+    /// - 7: a valid MMDDhhmmss date and time
+    /// - 12: a valid hhmmss time
+    /// - 13, 15, 16, 17: a valid MMDD date
+    /// - 14: expiration date month 01-12
+    /// - 18: an assigned merchant category code
+    /// - 19, 20, 21, 68, 69: an assigned ISO 3166 numeric country code
+    /// - 49, 50, 51: an assigned ISO 4217 numeric currency code
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::field::FieldValue;
+    /// use iso8583_core::*;
+    ///
+    /// let mut msg = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+    /// msg.set_field(Field::ExpirationDate, FieldValue::from_string("2512"))?;
+    /// assert!(Validator::validate_semantic_ranges(&msg).is_ok());
+    ///
+    /// msg.set_field(Field::ExpirationDate, FieldValue::from_string("2513"))?;
+    /// assert!(Validator::validate_semantic_ranges(&msg).is_err());
+    /// # Ok::<(), ISO8583Error>(())
+    /// ```
+    pub fn validate_semantic_ranges(msg: &ISO8583Message) -> Result<()> {
+        for number in msg.get_field_numbers() {
+            let Some(value) = msg
+                .get_field(Field::from_number(number)?)
+                .and_then(FieldValue::as_string)
+            else {
+                continue;
+            };
+            let out_of_range = |what: &str| {
+                Err(ISO8583Error::invalid_field_value(
+                    number,
+                    format!("{} out of range: {}", what, value),
+                ))
+            };
+
+            match number {
+                7 => {
+                    crate::utils::parse_transmission_datetime(value)?;
+                }
+                12 if !Self::validate_time_hhmmss(value) => return out_of_range("Time"),
+                13 | 15 | 16 | 17 if !Self::validate_date_mmdd(value) => {
+                    return out_of_range("Date")
+                }
+                14 => {
+                    crate::utils::parse_expiration_date(value)?;
+                }
+                18 if !Self::validate_mcc(value) => return out_of_range("Merchant category code"),
+                19 | 20 | 21 | 68 | 69 if !Self::validate_country_code(value) => {
+                    return out_of_range("Country code")
+                }
+                49..=51 if !Self::validate_currency_code(value) => {
+                    return out_of_range("Currency code")
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validate country code (ISO 3166-1 numeric)
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::validation::Validator;
+    ///
+    /// assert!(Validator::validate_country_code("826"));
+    /// assert!(!Validator::validate_country_code("001"));
+    /// ```
+    pub fn validate_country_code(code: &str) -> bool {
+        ISO3166_NUMERIC_CODES.binary_search(&code).is_ok()
+    }
+
     /// Validate currency code (ISO 4217)
+    ///
+    /// Only assigned numeric codes are accepted; 999 ("no currency") is rejected.
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::validation::Validator;
+    ///
+    /// assert!(Validator::validate_currency_code("840"));
+    /// assert!(!Validator::validate_currency_code("001"));
+    /// ```
     pub fn validate_currency_code(code: &str) -> bool {
-        code.len() == 3 && code.chars().all(|c| c.is_ascii_digit())
+        Self::check_currency_code(code, false).is_ok()
+    }
+
+    /// Check currency code (ISO 4217) against the assigned code list
+    ///
+    /// Set `allow_no_currency` to also accept the 999 ("no currency") code
+    /// used for transactions without a currency, such as test messages.
+    pub fn check_currency_code(code: &str, allow_no_currency: bool) -> Result<()> {
+        if code.len() != 3 || !code.chars().all(|c| c.is_ascii_digit()) {
+            return Err(ISO8583Error::ValidationError(format!(
+                "Currency code must be 3 digits, got '{}'",
+                code
+            )));
+        }
+
+        if code == "999" && allow_no_currency {
+            return Ok(());
+        }
+
+        if ISO4217_NUMERIC_CODES.binary_search(&code).is_err() {
+            return Err(ISO8583Error::ValidationError(format!(
+                "Unassigned ISO 4217 currency code: {}",
+                code
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Validate a merchant category code (field 18)
+    ///
+    /// The code must be 4 digits within an assigned ISO 18245 range
+    /// ([`MCC_ASSIGNED_RANGES`]). Well-formed codes without a
+    /// [description](Self::mcc_description) are still valid.
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::validation::Validator;
+    ///
+    /// assert!(Validator::validate_mcc("5812"));
+    /// assert!(!Validator::validate_mcc("0500")); // Reserved
+    /// assert!(!Validator::validate_mcc("581"));
+    /// ```
+    pub fn validate_mcc(code: &str) -> bool {
+        if code.len() != 4 || !code.bytes().all(|b| b.is_ascii_digit()) {
+            return false;
+        }
+
+        let value: u16 = code.parse().unwrap_or_default();
+        MCC_ASSIGNED_RANGES
+            .iter()
+            .any(|&(start, end)| (start..=end).contains(&value))
+    }
+
+    /// Describe a common merchant category code
+    ///
+    /// Returns `None` for codes not in [`MCC_DESCRIPTIONS`], including valid
+    /// but less common ones.
+    ///
+    /// # Example
+    /// ```
+    /// use iso8583_core::validation::Validator;
+    ///
+    /// assert_eq!(Validator::mcc_description("5411"), Some("Grocery stores and supermarkets"));
+    /// assert_eq!(Validator::mcc_description("5998"), None);
+    /// ```
+    pub fn mcc_description(code: &str) -> Option<&'static str> {
+        MCC_DESCRIPTIONS
+            .binary_search_by_key(&code, |&(mcc, _)| mcc)
+            .ok()
+            .map(|i| MCC_DESCRIPTIONS[i].1)
     }
 }
 
@@ -246,6 +710,230 @@ impl Validator {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_semantic_ranges() {
+        let with = |field: Field, value: &str| {
+            let mut msg = ISO8583Message::new(MessageType::AUTHORIZATION_REQUEST);
+            msg.set_field(field, FieldValue::from_string(value))
+                .unwrap();
+            Validator::validate_semantic_ranges(&msg)
+        };
+
+        assert!(with(Field::ExpirationDate, "2512").is_ok());
+        assert!(with(Field::ExpirationDate, "2513").is_err());
+        assert!(with(Field::ExpirationDate, "2500").is_err());
+        assert!(with(Field::AcquiringInstitutionCountryCode, "840").is_ok());
+        assert!(with(Field::PANExtendedCountryCode, "999").is_err());
+        assert!(with(Field::ForwardingInstitutionCountryCode, "000").is_err());
+        assert!(with(Field::CurrencyCodeTransaction, "978").is_ok());
+        assert!(with(Field::CurrencyCodeTransaction, "001").is_err());
+        assert!(with(Field::LocalTransactionDate, "1332").is_err());
+        assert!(with(Field::LocalTransactionTime, "246000").is_err());
+        assert!(with(Field::MerchantType, "0500").is_err());
+        assert!(Validator::validate_semantic_ranges(&ISO8583Message::new(
+            MessageType::AUTHORIZATION_REQUEST
+        ))
+        .is_ok());
+
+        assert!(ISO3166_NUMERIC_CODES.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_mcc() {
+        assert!(Validator::validate_mcc("0742"));
+        assert!(Validator::validate_mcc("5998")); // Assigned, no description
+        assert!(Validator::validate_mcc("9999"));
+        assert!(!Validator::validate_mcc("0000"));
+        assert!(!Validator::validate_mcc("1234")); // Reserved range
+        assert!(!Validator::validate_mcc("58A2"));
+        assert!(!Validator::validate_mcc("58120"));
+
+        assert_eq!(
+            Validator::mcc_description("5812"),
+            Some("Eating places and restaurants")
+        );
+        assert_eq!(Validator::mcc_description("5998"), None);
+        assert_eq!(Validator::mcc_description("abcd"), None);
+
+        assert!(MCC_DESCRIPTIONS.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(MCC_DESCRIPTIONS
+            .iter()
+            .all(|&(code, _)| Validator::validate_mcc(code)));
+    }
+
+    #[test]
+    fn test_transmission_time_skew() {
+        let skew = Duration::seconds(300);
+        let now = Utc.with_ymd_and_hms(2024, 6, 15, 12, 0, 0).unwrap();
+
+        assert!(Validator::validate_transmission_time("0615120000", now, skew).is_ok());
+        // Exactly at the boundary, past and future
+        assert!(Validator::validate_transmission_time("0615115500", now, skew).is_ok());
+        assert!(Validator::validate_transmission_time("0615120500", now, skew).is_ok());
+        // One second beyond
+        assert!(Validator::validate_transmission_time("0615115459", now, skew).is_err());
+        assert!(Validator::validate_transmission_time("0615120501", now, skew).is_err());
+        // Malformed
+        assert!(Validator::validate_transmission_time("1332120000", now, skew).is_err());
+        assert!(Validator::validate_transmission_time("061512", now, skew).is_err());
+    }
+
+    #[test]
+    fn test_transmission_time_year_boundary() {
+        let skew = Duration::seconds(300);
+
+        // Sent on Dec 31, received on Jan 1
+        let new_year = Utc.with_ymd_and_hms(2025, 1, 1, 0, 2, 0).unwrap();
+        assert!(Validator::validate_transmission_time("1231235900", new_year, skew).is_ok());
+        assert!(Validator::validate_transmission_time("1231235000", new_year, skew).is_err());
+
+        // Sender clock slightly ahead, across the boundary the other way
+        let new_years_eve = Utc.with_ymd_and_hms(2024, 12, 31, 23, 59, 0).unwrap();
+        assert!(Validator::validate_transmission_time("0101000100", new_years_eve, skew).is_ok());
+
+        // Feb 29 only exists in the previous year
+        let march = Utc.with_ymd_and_hms(2025, 3, 1, 0, 0, 0).unwrap();
+        assert!(Validator::validate_transmission_time("0229235900", march, skew).is_err());
+        assert!(
+            Validator::validate_transmission_time("0229235900", march, Duration::days(400)).is_ok()
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_validate_with_schema() {
+        use crate::field::FieldValue;
+
+        let schema = SpecSchema::from_json(
+            r#"{
+                "fields": { "48": { "type": "numeric", "length": "llvar", "max_len": 6 } },
+                "required": { "0200": [2, 48] }
+            }"#,
+        )
+        .unwrap();
+
+        let mut msg = ISO8583Message::new(MessageType::FINANCIAL_REQUEST);
+        msg.set_field(
+            Field::PrimaryAccountNumber,
+            FieldValue::from_string("4111111111111111"),
+        )
+        .unwrap();
+        assert_eq!(
+            Validator::validate_with_schema(&msg, &schema),
+            Err(ISO8583Error::MissingRequiredField(48))
+        );
+
+        msg.set_field(
+            Field::AdditionalDataPrivate,
+            FieldValue::from_string("12AB"),
+        )
+        .unwrap();
+        assert!(Validator::validate_with_schema(&msg, &schema).is_err());
+
+        msg.set_field(
+            Field::AdditionalDataPrivate,
+            FieldValue::from_string("1234567"),
+        )
+        .unwrap();
+        assert!(Validator::validate_with_schema(&msg, &schema).is_err());
+
+        msg.set_field(
+            Field::AdditionalDataPrivate,
+            FieldValue::from_string("123456"),
+        )
+        .unwrap();
+        assert!(Validator::validate_with_schema(&msg, &schema).is_ok());
+
+        // Requirements are per MTI
+        let other = ISO8583Message::new(MessageType::NETWORK_MANAGEMENT_REQUEST);
+        assert!(Validator::validate_with_schema(&other, &schema).is_ok());
+    }
+
+    #[test]
+    fn test_amount_currency_rules() {
+        use crate::field::FieldValue;
+
+        let mut msg = ISO8583Message::new(MessageType::NETWORK_MANAGEMENT_REQUEST);
+        for (field, value) in [
+            (Field::ProcessingCode, "000000"),
+            (Field::SystemTraceAuditNumber, "123456"),
+            (Field::LocalTransactionTime, "120000"),
+            (Field::LocalTransactionDate, "1016"),
+            (Field::TransactionAmount, "000000001000"),
+        ] {
+            msg.set_field(field, FieldValue::from_string(value))
+                .unwrap();
+        }
+        let rules = CrossFieldRule::AMOUNT_AND_CURRENCY;
+
+        assert!(Validator::validate_message(&msg).is_ok());
+        assert_eq!(
+            Validator::validate_message_with(&msg, rules),
+            Err(ISO8583Error::MissingRequiredField(49))
+        );
+
+        msg.set_field(
+            Field::CurrencyCodeTransaction,
+            FieldValue::from_string("840"),
+        )
+        .unwrap();
+        assert!(Validator::validate_message_with(&msg, rules).is_ok());
+
+        msg.remove_field(Field::TransactionAmount).unwrap();
+        assert_eq!(
+            Validator::validate_message_with(&msg, rules),
+            Err(ISO8583Error::MissingRequiredField(4))
+        );
+        assert!(
+            Validator::validate_message_with(&msg, &[CrossFieldRule::AmountRequiresCurrency])
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_multi_currency_rules() {
+        let mut msg = ISO8583Message::new(MessageType::NETWORK_MANAGEMENT_REQUEST);
+        for (field, value) in [
+            (Field::ProcessingCode, "000000"),
+            (Field::SystemTraceAuditNumber, "123456"),
+            (Field::LocalTransactionTime, "120000"),
+            (Field::LocalTransactionDate, "1016"),
+            (Field::TransactionAmount, "000000001000"),
+            (Field::CurrencyCodeTransaction, "978"),
+            (Field::SettlementAmount, "000000001085"),
+        ] {
+            msg.set_field(field, FieldValue::from_string(value))
+                .unwrap();
+        }
+        let rules = CrossFieldRule::MULTI_CURRENCY;
+
+        // Settlement amount without its currency
+        assert_eq!(
+            Validator::validate_message_with(&msg, rules),
+            Err(ISO8583Error::MissingRequiredField(50))
+        );
+        assert!(
+            Validator::validate_message_with(&msg, CrossFieldRule::AMOUNT_AND_CURRENCY).is_ok()
+        );
+
+        msg.set_field(
+            Field::CurrencyCodeSettlement,
+            FieldValue::from_string("840"),
+        )
+        .unwrap();
+        assert!(Validator::validate_message_with(&msg, rules).is_ok());
+
+        msg.set_field(
+            Field::CardholderBillingAmount,
+            FieldValue::from_string("000000000900"),
+        )
+        .unwrap();
+        assert_eq!(
+            CrossFieldRule::BillingAmountRequiresCurrency.check(&msg),
+            Err(ISO8583Error::MissingRequiredField(51))
+        );
+    }
+
     #[test]
     fn test_luhn_valid() {
         // Valid test card numbers
@@ -272,6 +960,42 @@ mod tests {
         assert!(!Validator::validate_pan("12345678901234567890")); // Too long
     }
 
+    fn chip_request(entry_mode: &str, icc_data: Vec<u8>) -> ISO8583Message {
+        ISO8583Message::builder()
+            .mti(crate::mti::MessageType::AUTHORIZATION_REQUEST)
+            .field(Field::PrimaryAccountNumber, "4111111111111111")
+            .field(Field::ProcessingCode, "000000")
+            .field(Field::TransactionAmount, "000000001000")
+            .field(Field::SystemTraceAuditNumber, "123456")
+            .field(Field::LocalTransactionTime, "120000")
+            .field(Field::LocalTransactionDate, "0115")
+            .field(Field::PointOfServiceEntryMode, entry_mode)
+            .binary_field(Field::ReservedISO1, icc_data)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_validate_message_emv() {
+        let mut complete = Vec::new();
+        for tag in emv::ONLINE_AUTH_REQUIRED_TAGS {
+            complete.extend_from_slice(tag);
+            complete.extend_from_slice(&[0x01, 0x00]);
+        }
+        let mut nested = vec![0x77, complete.len() as u8];
+        nested.extend_from_slice(&complete);
+
+        assert!(Validator::validate_message(&chip_request("051", complete)).is_ok());
+        assert!(Validator::validate_message(&chip_request("071", nested)).is_ok());
+
+        // Chip entry with an ARQC only
+        let partial = vec![0x9F, 0x26, 0x01, 0x00];
+        assert!(Validator::validate_message(&chip_request("051", partial.clone())).is_err());
+
+        // Magstripe entry does not require EMV tags
+        assert!(Validator::validate_message(&chip_request("021", partial)).is_ok());
+    }
+
     #[test]
     fn test_validate_date_mmdd() {
         assert!(Validator::validate_date_mmdd("0101")); // Jan 1
@@ -298,5 +1022,24 @@ mod tests {
         assert!(Validator::validate_currency_code("978")); // EUR
         assert!(!Validator::validate_currency_code("USD")); // Not numeric
         assert!(!Validator::validate_currency_code("84")); // Too short
+        assert!(!Validator::validate_currency_code("001")); // Unassigned
+        assert!(!Validator::validate_currency_code("999")); // No currency
+    }
+
+    #[test]
+    fn test_check_currency_code() {
+        for code in ["840", "978", "826", "392", "566", "356", "710"] {
+            assert!(Validator::check_currency_code(code, false).is_ok());
+        }
+
+        assert_eq!(
+            Validator::check_currency_code("001", false),
+            Err(ISO8583Error::ValidationError(
+                "Unassigned ISO 4217 currency code: 001".to_string()
+            ))
+        );
+        assert!(Validator::check_currency_code("999", false).is_err());
+        assert!(Validator::check_currency_code("999", true).is_ok());
+        assert!(Validator::check_currency_code("001", true).is_err());
     }
 }
@@ -377,3 +377,174 @@ fn test_field_numbers() {
     let field_numbers = message.get_field_numbers();
     assert_eq!(field_numbers, vec![2, 3, 4, 11, 12, 13]);
 }
+
+#[test]
+fn test_runtime_spec_overrides_field_62() {
+    // Field 62 is LLLVAR in the 1987 table; this acquirer sends fixed 8-byte binary
+    let mut raw = b"0200".to_vec();
+    raw.extend_from_slice(&[0x20, 0x20, 0, 0, 0, 0, 0, 0x06]); // Fields 3, 11, 62, 63
+    raw.extend_from_slice(b"000000123456");
+    raw.extend_from_slice(&[0xDE, 0xAD, 0xBE, 0xEF, 0x01, 0x02, 0x03, 0x04]);
+    raw.extend_from_slice(b"005HELLO");
+
+    assert!(ISO8583Message::from_bytes(&raw).is_err());
+
+    let mut spec = RuntimeSpec::new();
+    spec.override_field(62, FieldDefinition::fixed(DataType::Binary, 8));
+    let options = ParseOptions::new().runtime_spec(spec);
+
+    let parsed = ISO8583Message::from_bytes_with_options(&raw, &options).unwrap();
+    assert_eq!(
+        parsed.get_field(Field::ReservedPrivate3),
+        Some(&FieldValue::from_binary(vec![
+            0xDE, 0xAD, 0xBE, 0xEF, 0x01, 0x02, 0x03, 0x04
+        ]))
+    );
+    assert_eq!(
+        parsed.get_field(Field::ReservedPrivate4),
+        Some(&FieldValue::from_string("HELLO"))
+    );
+    assert_eq!(parsed.to_bytes(), raw);
+    assert_eq!(parsed.encoded_len(), raw.len());
+
+    // Building with the spec attached encodes the same way
+    let mut built = ISO8583Message::new(MessageType::FINANCIAL_REQUEST);
+    built.set_runtime_spec(Some(spec));
+    built
+        .set_field(Field::ProcessingCode, FieldValue::from_string("000000"))
+        .unwrap();
+    built
+        .set_field(
+            Field::SystemTraceAuditNumber,
+            FieldValue::from_string("123456"),
+        )
+        .unwrap();
+    built
+        .set_field(
+            Field::ReservedPrivate3,
+            FieldValue::from_binary(vec![0xDE, 0xAD, 0xBE, 0xEF, 0x01, 0x02, 0x03, 0x04]),
+        )
+        .unwrap();
+    built
+        .set_field(Field::ReservedPrivate4, FieldValue::from_string("HELLO"))
+        .unwrap();
+    assert_eq!(built.to_bytes(), raw);
+}
+
+#[test]
+fn test_bcd_field_in_ascii_message() {
+    use iso8583_core::encoding::Encoding;
+
+    let mut raw = b"0200".to_vec();
+    raw.extend_from_slice(&[0x20, 0x20, 0, 0, 0, 0, 0, 0]); // Fields 3, 11
+    raw.extend_from_slice(&[0x00, 0x10, 0x00]); // Field 3 as packed BCD
+    raw.extend_from_slice(b"123456");
+
+    let mut spec = RuntimeSpec::new();
+    spec.set_encoding(3, Some(Encoding::BCD));
+    let options = ParseOptions::new().runtime_spec(spec);
+
+    let parsed = ISO8583Message::from_bytes_with_options(&raw, &options).unwrap();
+    assert_eq!(
+        parsed.get_field(Field::ProcessingCode),
+        Some(&FieldValue::from_string("001000"))
+    );
+    assert_eq!(
+        parsed.get_field(Field::SystemTraceAuditNumber),
+        Some(&FieldValue::from_string("123456"))
+    );
+    assert_eq!(parsed.to_bytes(), raw);
+    assert_eq!(parsed.encoded_len(), raw.len());
+
+    let mut built = ISO8583Message::new(MessageType::FINANCIAL_REQUEST);
+    built.set_runtime_spec(Some(spec));
+    assert!(built
+        .set_field(Field::ProcessingCode, FieldValue::from_string("00100A"))
+        .is_err());
+    built
+        .set_field(Field::ProcessingCode, FieldValue::from_string("001000"))
+        .unwrap();
+    built
+        .set_field(
+            Field::SystemTraceAuditNumber,
+            FieldValue::from_string("123456"),
+        )
+        .unwrap();
+    assert_eq!(built.try_to_bytes().unwrap(), raw);
+}
+
+#[test]
+fn test_hex_pin_block_in_ascii_message() {
+    use iso8583_core::encoding::BinaryEncoding;
+
+    let pin_block = vec![0x04, 0x12, 0x34, 0xFF, 0xFF, 0xED, 0xCB, 0xA9];
+    let mut raw = b"0200".to_vec();
+    raw.extend_from_slice(&[0x00, 0x20, 0, 0, 0, 0, 0x10, 0]); // Fields 11, 52
+    raw.extend_from_slice(b"123456");
+    raw.extend_from_slice(b"041234FFFFEDCBA9");
+
+    let mut spec = RuntimeSpec::new();
+    spec.set_binary_encoding(52, BinaryEncoding::Hex);
+    let options = ParseOptions::new().runtime_spec(spec);
+
+    let parsed = ISO8583Message::from_bytes_with_options(&raw, &options).unwrap();
+    assert_eq!(
+        parsed.get_field(Field::PersonalIdentificationNumberData),
+        Some(&FieldValue::from_binary(pin_block.clone()))
+    );
+    assert_eq!(parsed.to_bytes(), raw);
+    assert_eq!(parsed.encoded_len(), raw.len());
+
+    // Lowercase hex is accepted, non-hex is not
+    let mut lower = raw.clone();
+    lower[raw.len() - 16..].make_ascii_lowercase();
+    assert_eq!(
+        ISO8583Message::from_bytes_with_options(&lower, &options).unwrap(),
+        parsed
+    );
+    let mut bad = raw.clone();
+    bad[raw.len() - 1] = b'G';
+    assert!(ISO8583Message::from_bytes_with_options(&bad, &options).is_err());
+
+    // Without the spec the 16 characters are read as 8 raw bytes
+    assert!(ISO8583Message::from_bytes(&raw).is_ok_and(|msg| msg
+        .get_field(Field::PersonalIdentificationNumberData)
+        .is_some_and(|value| value.as_binary() == Some(&b"041234FF"[..]))));
+}
+
+#[test]
+fn test_detect_spec() {
+    use iso8583_core::spec::{detect, SpecLookup};
+
+    // Field 62 as fixed 8-byte binary, followed by field 63
+    let mut raw = b"0200".to_vec();
+    raw.extend_from_slice(&[0x20, 0x20, 0, 0, 0, 0, 0, 0x06]);
+    raw.extend_from_slice(b"000000123456");
+    raw.extend_from_slice(&[0xDE, 0xAD, 0xBE, 0xEF, 0x01, 0x02, 0x03, 0x04]);
+    raw.extend_from_slice(b"005HELLO");
+
+    let standard = RuntimeSpec::new();
+    let mut private = RuntimeSpec::new();
+    private.override_field(62, FieldDefinition::fixed(DataType::Binary, 8));
+    // Parses, but stops short of the end of the buffer
+    let mut short = RuntimeSpec::new();
+    short.override_field(62, FieldDefinition::fixed(DataType::Binary, 7));
+    short.override_field(63, FieldDefinition::fixed(DataType::Binary, 4));
+
+    let candidates: [&dyn SpecLookup; 3] = [&standard, &short, &private];
+    let found = detect(&raw, &candidates).unwrap();
+    assert_eq!(found.parse_options().spec, Some(private));
+
+    // The built-in table as plain parse options
+    let plain = ISO8583Message::new(MessageType::NETWORK_MANAGEMENT_REQUEST).to_bytes();
+    let options = ParseOptions::new();
+    assert!(detect(&plain, &[&private, &options]).is_some());
+
+    // Truncations never panic, and never match the full-length spec
+    for len in 0..raw.len() {
+        if let Some(found) = detect(&raw[..len], &candidates) {
+            assert_ne!(found.parse_options().spec, Some(private));
+        }
+    }
+    assert!(detect(&raw, &[]).is_none());
+}
@@ -0,0 +1,124 @@
+//! Property tests: generating then parsing any valid message is lossless
+
+use iso8583_core::field::{FieldDefinition, FieldLength, FieldType, FieldValue};
+use iso8583_core::*;
+use proptest::collection::{btree_map, vec};
+use proptest::prelude::*;
+
+/// Longest variable-length value generated, to keep cases small
+const MAX_VAR_LEN: usize = 40;
+
+/// MTIs that pass `MessageType::check`
+fn mti_strategy() -> impl Strategy<Value = MessageType> {
+    (0u8..=9, 0u8..=9, 0u8..=9, 0u8..=9).prop_filter_map("invalid MTI", |(v, c, f, o)| {
+        let mti: MessageType = format!("{}{}{}{}", v, c, f, o).parse().ok()?;
+        mti.check().ok()?;
+        Some(mti)
+    })
+}
+
+/// Characters allowed by a field type
+fn charset(field_type: FieldType) -> &'static [u8] {
+    const DIGITS: &[u8] = b"0123456789";
+    const ALPHA: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz ";
+    const ALNUM: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+    const SPECIAL: &[u8] =
+        b" !\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstuvwxyz{|}~";
+    const TRACK2: &[u8] = b"0123456789=";
+
+    match field_type {
+        FieldType::Numeric | FieldType::Track3 => DIGITS,
+        FieldType::Alpha => ALPHA,
+        FieldType::AlphaNumeric => ALNUM,
+        FieldType::AlphaNumericSpecial => SPECIAL,
+        FieldType::Track2 => TRACK2,
+        FieldType::Binary | FieldType::SignedAmount => &[],
+    }
+}
+
+/// Spec-valid values for a field definition
+fn value_strategy(def: FieldDefinition) -> BoxedStrategy<FieldValue> {
+    let lengths = match def.length {
+        FieldLength::Fixed(len) => len..=len,
+        FieldLength::LLVar(max) | FieldLength::LLLVar(max) => 0..=max.min(MAX_VAR_LEN),
+    };
+
+    if def.field_type == FieldType::Binary {
+        return vec(any::<u8>(), lengths)
+            .prop_map(FieldValue::from_binary)
+            .boxed();
+    }
+
+    if def.field_type == FieldType::SignedAmount {
+        let digits = lengths.start().saturating_sub(1);
+        return (prop_oneof![Just('C'), Just('D')], vec(0u8..=9, digits))
+            .prop_map(|(sign, digits)| {
+                let digits: String = digits.iter().map(|d| (b'0' + d) as char).collect();
+                FieldValue::from_string(format!("{}{}", sign, digits))
+            })
+            .boxed();
+    }
+
+    let chars = charset(def.field_type);
+    vec(0..chars.len(), lengths)
+        .prop_map(move |indices| {
+            FieldValue::from_string(
+                indices
+                    .iter()
+                    .map(|&i| chars[i] as char)
+                    .collect::<String>(),
+            )
+        })
+        .boxed()
+}
+
+/// A random subset of data fields with valid values
+fn fields_strategy() -> impl Strategy<Value = Vec<(Field, FieldValue)>> {
+    btree_map(2u8..=128, Just(()), 0..=24)
+        .prop_map(|numbers| {
+            numbers
+                .into_keys()
+                .filter(|&n| n != 65) // Tertiary bitmap indicator
+                .map(|n| Field::from_number(n).unwrap())
+                .collect::<Vec<_>>()
+        })
+        .prop_flat_map(|fields| {
+            fields
+                .into_iter()
+                .map(|field| value_strategy(field.definition()).prop_map(move |v| (field, v)))
+                .collect::<Vec<_>>()
+        })
+}
+
+fn message_strategy() -> impl Strategy<Value = ISO8583Message> {
+    (mti_strategy(), fields_strategy()).prop_map(|(mti, fields)| {
+        let mut msg = ISO8583Message::new(mti);
+        for (field, value) in fields {
+            msg.set_field(field, value).unwrap();
+        }
+        msg
+    })
+}
+
+proptest! {
+    #[test]
+    fn prop_generate_parse_roundtrip(msg in message_strategy()) {
+        for num in msg.get_field_numbers() {
+            let field = Field::from_number(num).unwrap();
+            let value = msg.get_field(field).unwrap();
+            prop_assert!(field.definition().validate(value).is_ok(), "field {} generated invalid", num);
+        }
+
+        let bytes = msg.try_to_bytes().unwrap();
+        let parsed = ISO8583Message::from_bytes(&bytes).unwrap();
+        prop_assert_eq!(&parsed, &msg);
+        prop_assert_eq!(msg.encoded_len(), bytes.len());
+        prop_assert_eq!(parsed.to_bytes(), bytes);
+    }
+
+    #[test]
+    fn prop_hex_and_journal_roundtrip(msg in message_strategy()) {
+        prop_assert_eq!(&ISO8583Message::from_hex(&msg.to_hex()).unwrap(), &msg);
+        prop_assert_eq!(&ISO8583Message::from_journal_bytes(&msg.to_journal_bytes().unwrap()).unwrap(), &msg);
+    }
+}